@@ -1,6 +1,13 @@
-use geo::Rect;
+use geo::{Contains, Coord, EuclideanDistance, Intersects, Rect};
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fs,
+    path::Path,
+    sync::{Arc, Mutex},
+};
 
-use crate::{constants::*, location::*, utils::*};
+use crate::{constants::*, location::*, utils::*, GeoError};
 
 /// The Geo client for the AlertsInUa
 #[derive(Debug, Clone)]
@@ -11,20 +18,21 @@ pub struct AlertsInUaGeoClient {
     pub boundary: CountryBoundary,
     /// Administrative units
     pub locations: [Location; 27],
+    /// Precomputed neighbors (location_uid => neighbor location_uids)
+    pub adjacency: HashMap<i32, Vec<i32>>,
+    /// Simplified boundaries cached by epsilon bits
+    simplified: Arc<Mutex<HashMap<u64, CountryBoundary>>>,
 }
 
+/// Bundled country boundary
+const UKRAINE_WKT: &str = include_str!("../assets/ukraine.wkt");
+/// Bundled administrative units
+const UKRAINE_GEOJSON: &str = include_str!("../assets/ukraine.json");
+
 impl Default for AlertsInUaGeoClient {
     #[inline]
     fn default() -> Self {
-        let wkt_str = include_str!("../assets/ukraine.wkt");
-        let geojson_str = include_str!("../assets/ukraine.json");
-
-        Self {
-            bounding_rect: *UKRAINE_BBOX,
-            boundary: CountryBoundary(from_wkt_into(wkt_str).unwrap()),
-            locations: deserialize_feature_collection_to_fixed_array(geojson_str, "uk")
-                .unwrap(),
-        }
+        Self::try_new().expect("bundled geo assets are valid")
     }
 }
 
@@ -40,6 +48,42 @@ impl AlertsInUaGeoClient {
         Self::default()
     }
 
+    /// Client from bundled assets, with malformed assets reported as [`GeoError`]
+    pub fn try_new() -> Result<Self, GeoError> {
+        Self::from_strs(UKRAINE_WKT, UKRAINE_GEOJSON)
+    }
+
+    /// Client from country boundary WKT file and locations GeoJSON file, absent paths
+    /// fall back to bundled assets
+    pub fn from_paths(
+        wkt_path: Option<&Path>,
+        geojson_path: Option<&Path>,
+    ) -> Result<Self, GeoError> {
+        let wkt_str = read_or(wkt_path, UKRAINE_WKT)?;
+        let geojson_str = read_or(geojson_path, UKRAINE_GEOJSON)?;
+        Self::from_strs(&wkt_str, &geojson_str)
+    }
+
+    /// Client from country boundary WKT and GeoJSON feature collection of 27 locations
+    pub fn from_strs(wkt_str: &str, geojson_str: &str) -> Result<Self, GeoError> {
+        let boundary = CountryBoundary::from_wkt(wkt_str)?;
+        let locations: [Location; 27] =
+            deserialize_feature_collection_to_fixed_array(geojson_str, "uk").map_err(
+                |e| match e.downcast::<GeoError>() {
+                    Ok(e) => *e,
+                    Err(e) => GeoError::InvalidGeoJson(e.to_string()),
+                },
+            )?;
+
+        Ok(Self {
+            bounding_rect: *UKRAINE_BBOX,
+            boundary,
+            adjacency: build_adjacency(&locations),
+            locations,
+            simplified: Arc::default(),
+        })
+    }
+
     fn get_location_by<P>(&self, mut predicate: P) -> Option<Location>
     where
         P: FnMut(&Location) -> bool,
@@ -48,12 +92,52 @@ impl AlertsInUaGeoClient {
     }
 }
 
+/// Contents of file at `path`, or `fallback` if path is absent
+fn read_or<'a>(path: Option<&Path>, fallback: &'a str) -> Result<Cow<'a, str>, GeoError> {
+    match path {
+        Some(path) => {
+            fs::read_to_string(path)
+                .map(Cow::Owned)
+                .map_err(|e| GeoError::ReadFile {
+                    path: path.display().to_string(),
+                    message: e.to_string(),
+                })
+        }
+        None => Ok(Cow::Borrowed(fallback)),
+    }
+}
+
+/// Two locations are neighbors if their boundaries intersect or are closer than
+/// [`NEIGHBOR_TOLERANCE`], since boundaries are simplified and don't match exactly
+fn build_adjacency(locations: &[Location]) -> HashMap<i32, Vec<i32>> {
+    let mut adjacency: HashMap<i32, Vec<i32>> = HashMap::new();
+    for (i, a) in locations.iter().enumerate() {
+        for b in locations.iter().skip(i + 1) {
+            let (pa, pb) = (a.boundary(), b.boundary());
+            if pa.intersects(pb) || pa.euclidean_distance(pb) <= NEIGHBOR_TOLERANCE {
+                adjacency
+                    .entry(a.location_uid)
+                    .or_default()
+                    .push(b.location_uid);
+                adjacency
+                    .entry(b.location_uid)
+                    .or_default()
+                    .push(a.location_uid);
+            }
+        }
+    }
+    adjacency
+}
+
 /// The Geo client for the AlertsInUa
 pub trait AlertsInUaGeo: WithBoundingRect + Sync + Send + core::fmt::Debug {
     fn boundary(&self) -> CountryBoundary;
+    fn simplified_boundary(&self, epsilon: f64) -> CountryBoundary;
     fn locations(&self) -> [Location; 27];
     fn get_location_by_uid(&self, uid: i32) -> Option<Location>;
     fn get_location_by_name(&self, name: &str) -> Option<Location>;
+    fn get_location_at(&self, coord: Coord) -> Option<Location>;
+    fn get_neighbors(&self, uid: i32) -> Vec<Location>;
 }
 
 impl AlertsInUaGeo for AlertsInUaGeoClient {
@@ -62,6 +146,14 @@ impl AlertsInUaGeo for AlertsInUaGeoClient {
         self.boundary.clone()
     }
 
+    fn simplified_boundary(&self, epsilon: f64) -> CountryBoundary {
+        let mut cache = self.simplified.lock().unwrap();
+        cache
+            .entry(epsilon.to_bits())
+            .or_insert_with(|| self.boundary.simplify(epsilon))
+            .clone()
+    }
+
     #[inline]
     fn locations(&self) -> [Location; 27] {
         self.locations.clone()
@@ -76,6 +168,23 @@ impl AlertsInUaGeo for AlertsInUaGeoClient {
     fn get_location_by_name(&self, name: &str) -> Option<Location> {
         self.get_location_by(|r| r.name == name)
     }
+
+    #[inline]
+    fn get_location_at(&self, coord: Coord) -> Option<Location> {
+        self.get_location_by(|r| r.geometry.contains(&coord))
+    }
+
+    #[inline]
+    fn get_neighbors(&self, uid: i32) -> Vec<Location> {
+        self.adjacency
+            .get(&uid)
+            .map(|uids| {
+                uids.iter()
+                    .filter_map(|&uid| self.get_location_by_uid(uid))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
 }
 
 #[cfg(test)]
@@ -92,6 +201,89 @@ mod tests {
         assert_eq!(geo.locations.len(), 27);
     }
 
+    #[test]
+    fn test_try_new() {
+        let geo = AlertsInUaGeoClient::try_new().unwrap();
+        assert_eq!(geo.locations.len(), 27);
+    }
+
+    #[test]
+    fn test_from_strs_malformed_wkt() {
+        let result = AlertsInUaGeoClient::from_strs("POLYGON((", UKRAINE_GEOJSON);
+        assert!(matches!(result, Err(GeoError::InvalidWkt(_))));
+    }
+
+    #[test]
+    fn test_from_strs_malformed_geojson() {
+        let result = AlertsInUaGeoClient::from_strs(UKRAINE_WKT, "{\"type\":");
+        assert!(matches!(result, Err(GeoError::InvalidGeoJson(_))));
+
+        let mut collection: serde_json::Value =
+            serde_json::from_str(UKRAINE_GEOJSON).unwrap();
+        collection["features"].as_array_mut().unwrap().truncate(1);
+        let result = AlertsInUaGeoClient::from_strs(UKRAINE_WKT, &collection.to_string());
+        assert!(matches!(
+            result,
+            Err(GeoError::FeatureCountMismatch {
+                expected: 27,
+                actual: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn test_from_paths() {
+        let dir = std::env::temp_dir();
+        let wkt_path = dir.join("ralertsinua_geo_test_from_paths.wkt");
+        let geojson_path = dir.join("ralertsinua_geo_test_from_paths.json");
+        fs::write(&wkt_path, "POLYGON((22 44, 41 44, 41 53, 22 53, 22 44))").unwrap();
+        fs::write(&geojson_path, UKRAINE_GEOJSON).unwrap();
+
+        let geo =
+            AlertsInUaGeoClient::from_paths(Some(&wkt_path), Some(&geojson_path)).unwrap();
+        assert_eq!(geo.boundary.coords_count(), 5);
+        assert_eq!(geo.locations.len(), 27);
+
+        // absent paths fall back to bundled assets
+        let geo = AlertsInUaGeoClient::from_paths(Some(&wkt_path), None).unwrap();
+        assert_eq!(geo.boundary.coords_count(), 5);
+        let geo = AlertsInUaGeoClient::from_paths(None, None).unwrap();
+        assert_eq!(geo.boundary.coords_count(), 955);
+
+        fs::remove_file(wkt_path).unwrap();
+        fs::remove_file(geojson_path).unwrap();
+    }
+
+    #[test]
+    fn test_from_paths_errors() {
+        let dir = std::env::temp_dir();
+        let missing = dir.join("ralertsinua_geo_test_missing.wkt");
+        let result = AlertsInUaGeoClient::from_paths(Some(&missing), None);
+        assert!(matches!(result, Err(GeoError::ReadFile { .. })));
+
+        // malformed file fails at load time, not when drawn
+        let malformed = dir.join("ralertsinua_geo_test_malformed.wkt");
+        fs::write(&malformed, "POLYGON((22 44, 41 44, 41").unwrap();
+        let result = AlertsInUaGeoClient::from_paths(Some(&malformed), None);
+        assert!(matches!(result, Err(GeoError::InvalidWkt(_))));
+        fs::remove_file(malformed).unwrap();
+
+        let mut collection: serde_json::Value =
+            serde_json::from_str(UKRAINE_GEOJSON).unwrap();
+        collection["features"].as_array_mut().unwrap().truncate(26);
+        let geojson_path = dir.join("ralertsinua_geo_test_26_features.json");
+        fs::write(&geojson_path, collection.to_string()).unwrap();
+        let result = AlertsInUaGeoClient::from_paths(None, Some(&geojson_path));
+        assert!(matches!(
+            result,
+            Err(GeoError::FeatureCountMismatch {
+                expected: 27,
+                actual: 26
+            })
+        ));
+        fs::remove_file(geojson_path).unwrap();
+    }
+
     #[test]
     fn test_trait() {
         let geo_client: Arc<dyn AlertsInUaGeo> = Arc::new(AlertsInUaGeoClient::default());
@@ -99,4 +291,46 @@ mod tests {
         assert_eq!(geo_client.boundary().0.coords_count(), 955);
         assert_eq!(geo_client.locations().len(), 27);
     }
+
+    #[test]
+    fn test_get_location_at() {
+        let geo = AlertsInUaGeoClient::default();
+        // Біла Церква
+        let location = geo.get_location_at(Coord { x: 30.11, y: 49.80 });
+        assert_eq!(location.map(|l| l.location_uid), Some(14));
+        // Київ
+        let location = geo.get_location_at(Coord { x: 30.52, y: 50.45 });
+        assert_eq!(location.map(|l| l.location_uid), Some(31));
+        // Black Sea
+        let location = geo.get_location_at(Coord { x: 31.0, y: 44.5 });
+        assert!(location.is_none());
+    }
+
+    #[test]
+    fn test_get_neighbors() {
+        let geo = AlertsInUaGeoClient::default();
+        // Київська область
+        let mut neighbors: Vec<i32> = geo
+            .get_neighbors(14)
+            .iter()
+            .map(|l| l.location_uid)
+            .collect();
+        neighbors.sort();
+        // Вінницька, Житомирська, Полтавська, Черкаська, Чернігівська, Київ
+        assert_eq!(neighbors, vec![8, 10, 19, 24, 25, 31]);
+        // unknown location
+        assert!(geo.get_neighbors(0).is_empty());
+    }
+
+    #[test]
+    fn test_simplified_boundary() {
+        let geo = AlertsInUaGeoClient::default();
+        let simplified = geo.simplified_boundary(0.05);
+        assert!(simplified.coords_count() < geo.boundary().coords_count());
+        assert!(simplified.coords_count() > 3);
+        // cached
+        assert_eq!(geo.simplified.lock().unwrap().len(), 1);
+        assert_eq!(geo.simplified_boundary(0.05), simplified);
+        assert_eq!(geo.simplified.lock().unwrap().len(), 1);
+    }
 }
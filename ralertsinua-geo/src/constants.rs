@@ -1,6 +1,9 @@
 use geo::{Coord, Point, Rect};
 use lazy_static::lazy_static;
 
+/// Max distance (in degrees) between boundaries of neighbor locations
+pub const NEIGHBOR_TOLERANCE: f64 = 0.01;
+
 lazy_static! {
     /// Ukraine bounding box coords tuple - (min_x, min_y), (max_x, max_y)
     ///
@@ -6,6 +6,14 @@ pub mod utils;
 
 #[derive(thiserror::Error, Debug)]
 pub enum GeoError {
+    #[error("Expected {expected} features, found {actual}")]
+    FeatureCountMismatch { expected: usize, actual: usize },
+    #[error("Invalid WKT: {0}")]
+    InvalidWkt(String),
+    #[error("Invalid GeoJSON: {0}")]
+    InvalidGeoJson(String),
+    #[error("Failed to read {path}: {message}")]
+    ReadFile { path: String, message: String },
     #[error("Unknown error Geo")]
     Unknown,
 }
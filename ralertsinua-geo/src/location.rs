@@ -1,13 +1,16 @@
-use geo::{BoundingRect, Geometry, Polygon, Rect};
-use geojson::de::deserialize_geometry;
+use geo::{
+    BoundingRect, Centroid, Coord, CoordsIter, Geometry, Point, Polygon, Rect, Simplify,
+};
+use geojson::{de::deserialize_geometry, ser::serialize_geometry};
 #[cfg(feature = "tui")]
 use ratatui::{
     prelude::*,
     widgets::canvas::{Painter, Shape},
 };
 use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
 
-use crate::utils::*;
+use crate::{utils::*, GeoError};
 
 /// WKT string
 pub type WktString = String;
@@ -16,6 +19,9 @@ pub type WktString = String;
 pub type XY_Bounds = ([f64; 2], [f64; 2]);
 
 const PADDING: f64 = 0.5;
+/// Distance (in degrees) between the center and arms of [`PointMarker`]
+#[cfg(feature = "tui")]
+const MARKER_SIZE: f64 = 0.1;
 
 /// Trait for objects that have a bounding rectangle to return x/y bounds as pairs (e.g. for ratatui)
 pub trait WithBoundingRect {
@@ -45,7 +51,10 @@ pub struct Location {
     #[serde(rename = "place")]
     pub location_type: String,
     /// Geometry for boundary (Polygon or MultiPolygon)
-    #[serde(deserialize_with = "deserialize_geometry")]
+    #[serde(
+        serialize_with = "serialize_geometry",
+        deserialize_with = "deserialize_geometry"
+    )]
     pub geometry: Geometry,
     /// Name in uk
     #[serde(rename = "name")]
@@ -53,12 +62,18 @@ pub struct Location {
     /// Name in en
     #[serde(rename = "name:en")]
     pub name_en: String,
+    /// Centroid computed on first use
+    #[serde(skip)]
+    centroid: OnceLock<Point>,
 }
 
 impl WithBoundingRect for Location {
     #[inline]
     fn bounding_rect(&self) -> Rect {
-        self.geometry.bounding_rect().unwrap()
+        // empty geometry has no bounding rect, collapse it into a point
+        self.geometry
+            .bounding_rect()
+            .unwrap_or_else(|| Rect::new(Coord::zero(), Coord::zero()))
     }
 }
 
@@ -90,6 +105,7 @@ impl Default for Location {
             geometry: default_polygon().into(),
             name: String::default(),
             name_en: String::default(),
+            centroid: OnceLock::new(),
         }
     }
 }
@@ -105,6 +121,15 @@ impl Location {
         }
     }
 
+    /// Center of mass of the boundary, or center of bounding rect for empty geometry
+    pub fn centroid(&self) -> Point {
+        *self.centroid.get_or_init(|| {
+            self.geometry
+                .centroid()
+                .unwrap_or_else(|| self.bounding_rect().center().into())
+        })
+    }
+
     #[inline]
     pub fn center(&self) -> (f64, f64) {
         let rect = self.bounding_rect();
@@ -136,6 +161,27 @@ impl Default for CountryBoundary {
     }
 }
 
+impl CountryBoundary {
+    /// Parse boundary from WKT polygon once, malformed or empty input is an error
+    pub fn from_wkt(wkt_str: &str) -> Result<Self, GeoError> {
+        from_wkt_into(wkt_str)
+            .map(Self)
+            .map_err(|e| GeoError::InvalidWkt(e.to_string()))
+    }
+
+    /// Simplified boundary (Ramer–Douglas–Peucker), points closer than `epsilon` (in degrees) are dropped
+    #[inline]
+    pub fn simplify(&self, epsilon: f64) -> Self {
+        Self(self.0.simplify(&epsilon))
+    }
+
+    /// Total number of coordinates, i.e. painter calls per frame
+    #[inline]
+    pub fn coords_count(&self) -> usize {
+        self.0.coords_count()
+    }
+}
+
 /// Draws country boundary with [`Canvas`]
 #[cfg(feature = "tui")]
 impl Shape for CountryBoundary {
@@ -150,6 +196,76 @@ impl Shape for CountryBoundary {
     }
 }
 
+/// Single point (e.g. country center) to be marked on the map
+#[derive(Debug, Clone, PartialEq)]
+#[cfg(feature = "tui")]
+pub struct PointMarker {
+    pub point: geo::Point,
+    pub color: Color,
+}
+
+#[cfg(feature = "tui")]
+impl PointMarker {
+    pub fn new(point: geo::Point, color: Color) -> Self {
+        Self { point, color }
+    }
+}
+
+/// Draws point marker with [`Canvas`]
+#[cfg(feature = "tui")]
+impl Shape for PointMarker {
+    /// This method paints a small plus-shaped cluster of points around the marker, so it's still visible at typical terminal resolutions
+    #[inline]
+    fn draw(&self, painter: &mut Painter) {
+        let (x, y) = self.point.x_y();
+        [
+            (x, y),
+            (x - MARKER_SIZE, y),
+            (x + MARKER_SIZE, y),
+            (x, y - MARKER_SIZE),
+            (x, y + MARKER_SIZE),
+        ]
+        .iter()
+        .for_each(|&(x, y)| {
+            if let Some((x, y)) = painter.get_point(x, y) {
+                painter.paint(x, y, self.color);
+            }
+        });
+    }
+}
+
+/// Location boundary drawn in a distinct color, e.g. for selected location
+#[derive(Debug, Clone, PartialEq)]
+#[cfg(feature = "tui")]
+pub struct LocationHighlight<'a> {
+    pub location: &'a Location,
+    pub color: Color,
+}
+
+#[cfg(feature = "tui")]
+impl<'a> LocationHighlight<'a> {
+    pub fn new(location: &'a Location, color: Color) -> Self {
+        Self { location, color }
+    }
+}
+
+/// Draws location boundary with [`Canvas`] in highlight color
+#[cfg(feature = "tui")]
+impl Shape for LocationHighlight<'_> {
+    #[inline]
+    fn draw(&self, painter: &mut Painter) {
+        self.location
+            .boundary()
+            .exterior()
+            .coords()
+            .for_each(|coord| {
+                if let Some((x, y)) = painter.get_point(coord.x, coord.y) {
+                    painter.paint(x, y, self.color);
+                }
+            });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use geo::CoordsIter;
@@ -182,4 +298,88 @@ mod tests {
         assert_eq!(location.bounding_rect().coords_count(), 4);
         assert_eq!(location.geometry().coords_count(), 12);
     }
+
+    #[test]
+    fn test_bounding_rect_and_centroid() {
+        use geo::Contains;
+
+        let geo = crate::AlertsInUaGeoClient::default();
+        let approx = |location_uid: i32, x: f64, y: f64| {
+            let location = geo
+                .locations
+                .iter()
+                .find(|l| l.location_uid == location_uid);
+            let centroid = location.unwrap().centroid();
+            assert!((centroid.x() - x).abs() < 0.3, "{:?}", centroid);
+            assert!((centroid.y() - y).abs() < 0.3, "{:?}", centroid);
+            assert!(location
+                .unwrap()
+                .bounding_rect()
+                .to_polygon()
+                .contains(&centroid));
+        };
+        // Київ
+        approx(31, 30.52, 50.45);
+        // Львівська область
+        approx(27, 24.0, 49.75);
+        // Одеська область
+        approx(18, 29.9, 46.6);
+
+        let location = Location::default();
+        assert_eq!(location.bounding_rect().width(), 0.0);
+        assert_eq!(location.centroid(), Point::new(0.0, 0.0));
+    }
+
+    #[test]
+    fn test_country_boundary_from_invalid_wkt() {
+        for wkt_str in ["", "POLYGON((30 50, 31", "POINT(30 50)"] {
+            let result = CountryBoundary::from_wkt(wkt_str);
+            assert!(
+                matches!(result, Err(GeoError::InvalidWkt(_))),
+                "{}",
+                wkt_str
+            );
+        }
+        let boundary = CountryBoundary::from_wkt("POLYGON((30 50, 31 50, 31 51, 30 50))");
+        assert_eq!(boundary.unwrap().coords_count(), 4);
+    }
+
+    #[test]
+    #[cfg(feature = "tui")]
+    fn test_empty_country_boundary_draws_nothing() {
+        use ratatui::{buffer::Buffer, layout::Rect as TuiRect, widgets::canvas::Canvas};
+
+        let area = TuiRect::new(0, 0, 20, 10);
+        let mut buf = Buffer::empty(area);
+        let boundary = CountryBoundary::from_wkt("").unwrap_or_default();
+        Canvas::default()
+            .x_bounds([0.0, 10.0])
+            .y_bounds([0.0, 10.0])
+            .paint(|ctx| ctx.draw(&boundary))
+            .render(area, &mut buf);
+
+        assert!(buf.content().iter().all(|c| c.symbol() == " "));
+    }
+
+    #[test]
+    #[cfg(feature = "tui")]
+    fn test_point_marker_draw() {
+        use ratatui::{buffer::Buffer, layout::Rect as TuiRect, widgets::canvas::Canvas};
+
+        let area = TuiRect::new(0, 0, 20, 10);
+        let mut buf = Buffer::empty(area);
+        let marker = PointMarker::new(geo::Point::new(5.0, 5.0), Color::Magenta);
+        Canvas::default()
+            .x_bounds([0.0, 10.0])
+            .y_bounds([0.0, 10.0])
+            .paint(|ctx| ctx.draw(&marker))
+            .render(area, &mut buf);
+
+        // resolution is 40x40 dots, center (5.0, 5.0) projects to dot (19, 19) => cell (9, 4)
+        let cell = buf.get(9, 4);
+        assert_eq!(cell.fg, Color::Magenta);
+        assert_ne!(cell.symbol(), " ");
+        let painted = buf.content().iter().filter(|c| c.fg == Color::Magenta);
+        assert!(painted.count() >= 1);
+    }
 }
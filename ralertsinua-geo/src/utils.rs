@@ -23,8 +23,14 @@ pub fn deserialize_feature_collection_to_fixed_array<T, const CAP: usize>(
 where
     T: serde::de::DeserializeOwned + Clone + WithName,
 {
-    let mut features: Vec<T> =
-        deserialize_feature_collection_str_to_vec(geojson_str).unwrap();
+    let mut features: Vec<T> = deserialize_feature_collection_str_to_vec(geojson_str)?;
+    // every slot must be filled by exactly one feature, otherwise fields end up misplaced
+    if features.len() != CAP {
+        return Err(Box::new(crate::GeoError::FeatureCountMismatch {
+            expected: CAP,
+            actual: features.len(),
+        }));
+    }
     features.sort_by_key_icu(|f| f.name().to_string(), locale_str);
     let fixed_array: [T; CAP] = core::array::from_fn(|i| features[i].clone());
     Ok(fixed_array)
@@ -50,10 +56,10 @@ pub trait WithName {
     {
         let locale: Language = locale.into();
         if locale.as_str() == "uk" {
-            return self.name();
+            self.name()
         } else {
-            return self.name_en();
-        };
+            self.name_en()
+        }
     }
 }
 
@@ -93,6 +99,7 @@ impl<T> SortByKeyIcu<T> for Vec<T> {
 mod tests {
     use super::*;
     use crate::location::Location;
+    use geo::CoordsIter;
 
     #[test]
     fn test_sort_by_key_uk() {
@@ -123,6 +130,18 @@ mod tests {
         assert_eq!(test_data[3].name, "Київська область");
     }
 
+    #[test]
+    fn test_from_wkt_into_errors() {
+        assert!(from_wkt_into::<Polygon>("").is_err());
+        assert!(from_wkt_into::<Polygon>("POLYGON((30 50, 31").is_err());
+        // valid WKT of another geometry type
+        assert!(from_wkt_into::<Polygon>("POINT(30 50)").is_err());
+
+        let polygon: Polygon =
+            from_wkt_into("POLYGON((30 50, 31 50, 31 51, 30 50))").unwrap();
+        assert_eq!(polygon.exterior().coords().count(), 4);
+    }
+
     #[test]
     fn test_default_polygon() {
         let poly = default_polygon();
@@ -143,10 +162,23 @@ mod tests {
                 ]
             }"#;
         let locations: [Location; 1] =
-            deserialize_feature_collection_to_fixed_array(&geojson_str, "uk").unwrap();
+            deserialize_feature_collection_to_fixed_array(geojson_str, "uk").unwrap();
         assert_eq!(locations.len(), 1);
 
         let location = &locations[0];
         assert_eq!(location.location_uid, 31);
+        assert_eq!(location.relation_id, "relation/421866");
+        assert_eq!(location.geometry.coords_count(), 12);
+    }
+
+    #[test]
+    fn test_deserialize_feature_collection_to_fixed_array_count_mismatch() {
+        let geojson_str = include_str!("../assets/ukraine.json");
+        let result: Result<[Location; 1]> =
+            deserialize_feature_collection_to_fixed_array(geojson_str, "uk");
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Expected 1 features, found 27"
+        );
     }
 }
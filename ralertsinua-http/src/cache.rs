@@ -8,17 +8,19 @@ use crate::ApiError;
 
 type Result<T> = miette::Result<T, ApiError>;
 type LastModified = String;
-type ApiCache = Cache<String, (Bytes, LastModified)>;
+type ETag = String;
+type ApiCache = Cache<String, (Bytes, LastModified, ETag)>;
 
+/// Cached response body with its `Last-Modified` and `ETag`, empty if not sent
 #[derive(Debug)]
-pub struct CacheEntry(pub Bytes, pub LastModified);
+pub struct CacheEntry(pub Bytes, pub LastModified, pub ETag);
 
 /// A trait providing methods for storing, reading, and removing cache records.
 pub trait CacheManagerSync: Send + Sync + 'static {
-    /// Attempts to pull a cached response and related last_modified from cache.
+    /// Attempts to pull a cached response and related last_modified and etag from cache.
     fn get(&self, cache_key: &str) -> Result<Option<CacheEntry>>;
-    /// Attempts to cache a response and related last_modified.
-    fn put(&self, cache_key: &str, last_modified: &str, bytes: Bytes) -> Result<()>;
+    /// Attempts to cache a response and related last_modified and etag.
+    fn put(&self, cache_key: &str, entry: CacheEntry) -> Result<()>;
     /// Attempts to remove a record from cache.
     fn delete(&self, cache_key: &str) -> Result<()>;
 }
@@ -49,15 +51,16 @@ impl CacheManagerQuick {
 impl CacheManagerSync for CacheManagerQuick {
     fn get(&self, cache_key: &str) -> Result<Option<CacheEntry>> {
         let entry: CacheEntry = match self.cache.get(cache_key) {
-            Some((bytes, lm)) => CacheEntry(bytes, lm),
+            Some((bytes, lm, etag)) => CacheEntry(bytes, lm, etag),
             None => return Ok(None),
         };
         Ok(Some(entry))
     }
 
-    fn put(&self, cache_key: &str, last_modified: &str, bytes: Bytes) -> Result<()> {
+    fn put(&self, cache_key: &str, entry: CacheEntry) -> Result<()> {
+        let CacheEntry(bytes, last_modified, etag) = entry;
         self.cache
-            .insert(cache_key.into(), (bytes, last_modified.into()));
+            .insert(cache_key.into(), (bytes, last_modified, etag));
         Ok(())
     }
 
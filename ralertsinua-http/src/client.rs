@@ -5,12 +5,19 @@ use async_trait::async_trait;
 use bytes::Bytes;
 use ralertsinua_models::*;
 use reqwest::{
-    header::{HeaderMap, HeaderValue},
-    Client, ClientBuilder, Method, RequestBuilder, Response, StatusCode,
+    header::{
+        HeaderMap, HeaderValue, AUTHORIZATION, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH,
+        LAST_MODIFIED,
+    },
+    Client, ClientBuilder, Method, Proxy, RequestBuilder, Response, StatusCode,
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::fmt;
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 #[cfg(feature = "cache")]
 use crate::cache::*;
@@ -22,45 +29,215 @@ type Result<T> = miette::Result<T, ApiError>;
 pub const API_BASE_URL: &str = "https://api.alerts.in.ua";
 pub const API_VERSION: &str = "/v1";
 pub const API_CACHE_SIZE: usize = 1000;
+pub const API_MAX_RETRIES: u32 = 3;
+/// Max number of pages fetched for a paginated response
+pub const API_MAX_PAGES: usize = 10;
+pub const API_RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+/// Longest wait before a retry, requests are awaited by the UI loop and must not stall it
+pub const API_RETRY_MAX_DELAY: Duration = Duration::from_secs(5);
+pub const API_TIMEOUT: Duration = Duration::from_secs(10);
+/// Max number of body characters included in [`ApiError::DeserializationError`]
+pub const API_BODY_SNIPPET_LEN: usize = 200;
+/// Replaces secrets (e.g. token) in logs
+pub const REDACTED: &str = "[REDACTED]";
+/// Default `User-Agent` header, some APIs rate-limit or block unidentified clients
+pub const API_USER_AGENT: &str = concat!("alertsinua-cli/", env!("CARGO_PKG_VERSION"));
+/// Environment variables checked (in order) for a proxy URL
+pub const API_PROXY_ENV: [&str; 2] = ["ALERTSINUA_PROXY", "HTTPS_PROXY"];
+
+/// Result of a conditional (`If-None-Match`) request
+#[derive(Debug, Clone, PartialEq)]
+pub enum Cached<R> {
+    /// Resource changed (or requested for the first time), holds the new value
+    Fresh(R),
+    /// Server responded `304 Not Modified`, holds the value stored with the last `ETag`
+    NotModified(R),
+}
+
+impl<R> Cached<R> {
+    /// Value regardless of whether it changed since the previous request
+    pub fn into_inner(self) -> R {
+        match self {
+            Cached::Fresh(value) | Cached::NotModified(value) => value,
+        }
+    }
+}
 
+/// HTTP client for alerts.in.ua API. One underlying [`Client`] is built up front and reused
+/// by all requests, so connections are kept alive between periodic fetches (idle ones are
+/// closed by reqwest after 90 seconds)
 pub struct AlertsInUaClient {
     base_url: String,
     token: String,
     client: Client,
+    timeout: Duration,
+    proxy: Option<Proxy>,
+    /// Max idle connections kept per host, reqwest default (unlimited) if `None`
+    pool_size: Option<usize>,
+    user_agent: String,
+    max_retries: u32,
+    base_delay: Duration,
     #[cfg(feature = "cache")]
     cache_manager: Arc<dyn CacheManagerSync>,
 }
 
 impl std::fmt::Debug for AlertsInUaClient {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "AlertsInUaClient {{ base_url: {}, token: {}, client: {:?}, cache_manager: {:?} }}", self.base_url, self.token, self.client, "CACacheManager")
+        write!(f, "AlertsInUaClient {{ base_url: {}, token: {}, client: {:?}, cache_manager: {:?} }}", self.base_url, REDACTED, self.client, "CACacheManager")
     }
 }
 
 impl AlertsInUaClient {
-    const APP_USER_AGENT: &'static str =
-        concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"),);
-
-    pub fn new(base_url: &str, token: &str) -> Self {
+    /// Client with proxy taken from [`API_PROXY_ENV`], fails if its value is malformed
+    pub fn new(base_url: &str, token: &str) -> Result<Self> {
         let base_url = base_url.into();
         let token = token.into();
-        let client = ClientBuilder::new()
-            .timeout(std::time::Duration::from_secs(10))
-            .user_agent(Self::APP_USER_AGENT)
-            .build()
-            // building with these options cannot fail
-            .unwrap();
+        let timeout = API_TIMEOUT;
+        let proxy = Self::get_env_proxy()?;
+        let user_agent = API_USER_AGENT.to_string();
+        let client = Self::build_client(timeout, proxy.clone(), None, &user_agent);
 
         let cache_manager = Arc::new(CacheManagerQuick::new(API_CACHE_SIZE));
 
-        Self {
+        Ok(Self {
             base_url,
             token,
             client,
+            timeout,
+            proxy,
+            pool_size: None,
+            user_agent,
+            max_retries: API_MAX_RETRIES,
+            base_delay: API_RETRY_BASE_DELAY,
             #[cfg(feature = "cache")]
             cache_manager,
+        })
+    }
+
+    fn build_client(
+        timeout: Duration,
+        proxy: Option<Proxy>,
+        pool_size: Option<usize>,
+        user_agent: &str,
+    ) -> Client {
+        let mut builder = ClientBuilder::new().timeout(timeout).user_agent(user_agent);
+        if let Some(proxy) = proxy {
+            builder = builder.proxy(proxy);
+        }
+        if let Some(pool_size) = pool_size {
+            builder = builder.pool_max_idle_per_host(pool_size);
+        }
+        builder
+            .build()
+            // building with these options cannot fail
+            .unwrap()
+    }
+
+    fn get_env_proxy() -> Result<Option<Proxy>> {
+        Self::find_proxy(|name| std::env::var(name).ok())
+    }
+
+    /// Proxy from the first non-empty of [`API_PROXY_ENV`] variables looked up with `var`,
+    /// fails with [`ApiError::ProxyError`] if its value is malformed
+    fn find_proxy(var: impl Fn(&str) -> Option<String>) -> Result<Option<Proxy>> {
+        API_PROXY_ENV
+            .iter()
+            .filter_map(|name| var(name))
+            .find(|url| !url.is_empty())
+            .map(|url| Proxy::all(&url).map_err(ApiError::ProxyError))
+            .transpose()
+    }
+
+    /// Total request timeout, default is [`API_TIMEOUT`]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self.client = Self::build_client(
+            timeout,
+            self.proxy.clone(),
+            self.pool_size,
+            &self.user_agent,
+        );
+        self
+    }
+
+    /// Route all requests through the given proxy, fails if the proxy URL is malformed
+    pub fn with_proxy(mut self, url: &str) -> Result<Self> {
+        let proxy = Proxy::all(url).map_err(ApiError::ProxyError)?;
+        self.client = Self::build_client(
+            self.timeout,
+            Some(proxy.clone()),
+            self.pool_size,
+            &self.user_agent,
+        );
+        self.proxy = Some(proxy);
+        Ok(self)
+    }
+
+    /// Max idle keep-alive connections per host, for aggressive polling;
+    /// `0` disables keep-alive, so every request opens a new connection
+    pub fn with_connection_pool_size(mut self, pool_size: usize) -> Self {
+        self.pool_size = Some(pool_size);
+        self.client = Self::build_client(
+            self.timeout,
+            self.proxy.clone(),
+            self.pool_size,
+            &self.user_agent,
+        );
+        self
+    }
+
+    /// `User-Agent` header sent with every request, default is [`API_USER_AGENT`]
+    pub fn with_user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = user_agent.to_string();
+        self.client = Self::build_client(
+            self.timeout,
+            self.proxy.clone(),
+            self.pool_size,
+            &self.user_agent,
+        );
+        self
+    }
+
+    /// Retry policy for transient failures (429, 500): up to `max_retries` attempts
+    /// with exponential backoff starting from `base_delay`, capped at [`API_RETRY_MAX_DELAY`]
+    pub fn with_retry(mut self, max_retries: u32, base_delay: Duration) -> Self {
+        self.max_retries = max_retries;
+        self.base_delay = base_delay;
+        self
+    }
+}
+
+/// Deserialize response body, failure keeps serde error with start of the body
+fn deserialize_body<R>(bytes: &[u8]) -> Result<R>
+where
+    R: for<'de> Deserialize<'de>,
+{
+    serde_json::from_slice(bytes).map_err(|e| {
+        let body = String::from_utf8_lossy(bytes);
+        let mut snippet: String = body.chars().take(API_BODY_SNIPPET_LEN).collect();
+        if body.chars().count() > API_BODY_SNIPPET_LEN {
+            snippet.push('…');
         }
+        ApiError::DeserializationError(format!("{} in '{}'", e, snippet))
+    })
+}
+
+/// Value of header `name` in `headers`, empty if missing or not ASCII
+fn get_header_str(headers: &HeaderMap, name: reqwest::header::HeaderName) -> String {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Copy of `headers` safe for logging, with `Authorization` value redacted
+fn redact_headers(headers: &HeaderMap) -> HeaderMap {
+    let mut headers = headers.clone();
+    if headers.contains_key(AUTHORIZATION) {
+        headers.insert(AUTHORIZATION, HeaderValue::from_static(REDACTED));
     }
+    headers
 }
 
 impl AlertsInUaClient {
@@ -68,53 +245,111 @@ impl AlertsInUaClient {
         format!("{}{}{}", self.base_url, API_VERSION, url)
     }
 
-    async fn request<R, D>(&self, method: Method, url: &str, add_data: D) -> Result<R>
+    /// Cache key of the request: full URL including query, so that e.g. cursor pages
+    /// of the same endpoint are cached separately
+    fn get_cache_key<D>(&self, method: &Method, url: &str, add_data: &D) -> String
     where
-        R: for<'de> Deserialize<'de>,
         D: Fn(RequestBuilder) -> RequestBuilder,
     {
-        let mut last_modified = String::new();
-        let mut cached_data: Bytes = Bytes::new();
-        // Build full URL
-        let url = self.get_api_url(url);
-        let mut req = self.client.request(method.clone(), &url);
-        // Enable HTTP bearer authentication.
-        req = req.bearer_auth(&self.token);
-        // Get last_modified from cache
-        let mut headers = HeaderMap::new();
-        // Set the headers
-        headers.insert("Accept", HeaderValue::from_static("application/json"));
+        add_data(self.client.request(method.clone(), url))
+            .build()
+            .map(|req| req.url().to_string())
+            .unwrap_or_else(|_| url.to_string())
+    }
 
-        if cfg!(feature = "cache") {
-            if let Some(CacheEntry(bytes, lm)) = self.cache_manager.get(&url)? {
-                last_modified = lm;
-                cached_data = bytes;
-            }
-            // Here we set the If-Modified-Since header from the last_modified
-            headers.insert(
-                "If-Modified-Since",
-                last_modified.parse().map_err(http::Error::from)?,
-            );
+    /// Delay before the next retry: `Retry-After` (seconds) if the server sent one,
+    /// otherwise exponential backoff `base_delay * 2^attempt` plus jitter, at most
+    /// [`API_RETRY_MAX_DELAY`]. `None` if `Retry-After` asks to wait longer than that,
+    /// so the request fails instead of blocking the caller
+    fn get_retry_delay(&self, attempt: u32, headers: &HeaderMap) -> Option<Duration> {
+        let retry_after = headers
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse::<u64>().ok());
+        if let Some(secs) = retry_after {
+            let delay = Duration::from_secs(secs);
+            return (delay <= API_RETRY_MAX_DELAY).then_some(delay);
         }
+        let backoff = self.base_delay.saturating_mul(2u32.saturating_pow(attempt));
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.subsec_nanos())
+            .unwrap_or_default();
+        let jitter = self.base_delay.mul_f64(f64::from(nanos % 1000) / 1000.0);
+        Some((backoff + jitter).min(API_RETRY_MAX_DELAY))
+    }
+
+    /// Distinguishes timeouts and proxy failures from other transport errors
+    fn map_reqwest_error(&self, e: reqwest::Error) -> ApiError {
+        if e.is_timeout() {
+            ApiError::Timeout(self.timeout)
+        } else if e.is_connect() && self.proxy.is_some() {
+            ApiError::ProxyError(e)
+        } else {
+            ApiError::from(e)
+        }
+    }
+
+    /// Sends the request with bearer auth and given headers, retrying transient failures
+    /// up to `max_retries` times, and maps error status codes into [`ApiError`]
+    async fn send<D>(
+        &self,
+        method: Method,
+        url: &str,
+        headers: HeaderMap,
+        max_retries: u32,
+        add_data: D,
+    ) -> Result<Response>
+    where
+        D: Fn(RequestBuilder) -> RequestBuilder,
+    {
+        let mut attempt: u32 = 0;
+        let res: Response = loop {
+            let mut req = self.client.request(method.clone(), url);
+            // Enable HTTP bearer authentication.
+            req = req.bearer_auth(&self.token);
+            req = req.headers(headers.clone());
+            // Configuring the request for the specific type (get/post/put/delete)
+            req = add_data(req);
+            // Finally performing the request and handling the response
+            let req = req.build().map_err(|e| self.map_reqwest_error(e))?;
+            log::debug!(target: env!("CARGO_PKG_NAME"), "Request {} {} headers={:?}", req.method(), req.url(), redact_headers(req.headers()));
+            let started_at = Instant::now();
+            let res: Response = self
+                .client
+                .execute(req)
+                .await
+                .inspect_err(|e| {
+                    log::error!(target: env!("CARGO_PKG_NAME"),  "Error making request: {:?}", e);
+                })
+                .map_err(|e| self.map_reqwest_error(e))?;
+            log::debug!(target: env!("CARGO_PKG_NAME"), "Response {} {} status '{}' in {:?}", method, res.url(), res.status(), started_at.elapsed());
+            log::trace!(target: env!("CARGO_PKG_NAME"), "Response {:?}", res);
+
+            let is_transient = matches!(
+                res.status(),
+                StatusCode::TOO_MANY_REQUESTS | StatusCode::INTERNAL_SERVER_ERROR
+            );
+            if !is_transient || attempt >= max_retries {
+                break res;
+            }
+            let Some(delay) = self.get_retry_delay(attempt, res.headers()) else {
+                log::warn!(target: env!("CARGO_PKG_NAME"), "Response status '{}', Retry-After exceeds {:?}, give up", res.status(), API_RETRY_MAX_DELAY);
+                break res;
+            };
+            attempt += 1;
+            log::warn!(target: env!("CARGO_PKG_NAME"), "Response status '{}', retry {}/{} in {:?}", res.status(), attempt, max_retries, delay);
+            tokio::time::sleep(delay).await;
+        };
 
-        req = req.headers(headers);
-        // Configuring the request for the specific type (get/post/put/delete)
-        req = add_data(req);
-        // Finally performing the request and handling the response
-        log::trace!(target: env!("CARGO_PKG_NAME"), "Request {:?}", req);
-        let res: Response = req.send().await.inspect_err(|e| {
-            log::error!(target: env!("CARGO_PKG_NAME"),  "Error making request: {:?}", e);
-        })?;
-        log::trace!(target: env!("CARGO_PKG_NAME"), "Response {:?}", res);
         // Making sure that the status code is OK
         if let Err(err) = res.error_for_status_ref() {
             let err = match err.status() {
                 Some(StatusCode::BAD_REQUEST) => Err(ApiError::InvalidParameterException),
                 Some(StatusCode::UNAUTHORIZED) => Err(ApiError::UnauthorizedError(err)),
                 Some(StatusCode::FORBIDDEN) => Err(ApiError::InvalidParameterException),
-                Some(StatusCode::METHOD_NOT_ALLOWED) | Some(StatusCode::NOT_FOUND) => {
-                    Err(ApiError::InvalidURL(err))
-                }
+                Some(StatusCode::METHOD_NOT_ALLOWED) => Err(ApiError::InvalidURL(err)),
+                Some(StatusCode::NOT_FOUND) => Err(ApiError::NotFound(url.to_string())),
                 Some(StatusCode::TOO_MANY_REQUESTS) => Err(ApiError::RateLimitError),
                 Some(StatusCode::INTERNAL_SERVER_ERROR) => {
                     Err(ApiError::InternalServerError)
@@ -125,7 +360,45 @@ impl AlertsInUaClient {
             return err;
         }
 
-        last_modified = format!("{:?}", res.headers().get("Last-Modified").unwrap());
+        Ok(res)
+    }
+
+    async fn request<R, D>(&self, method: Method, url: &str, add_data: D) -> Result<R>
+    where
+        R: for<'de> Deserialize<'de>,
+        D: Fn(RequestBuilder) -> RequestBuilder,
+    {
+        let mut last_modified = String::new();
+        let mut cached_data: Bytes = Bytes::new();
+        // Build full URL
+        let url = self.get_api_url(url);
+        let cache_key = self.get_cache_key(&method, &url, &add_data);
+        // Get last_modified from cache
+        let mut headers = HeaderMap::new();
+        // Set the headers
+        headers.insert("Accept", HeaderValue::from_static("application/json"));
+
+        if cfg!(feature = "cache") {
+            if let Some(CacheEntry(bytes, lm, _)) = self.cache_manager.get(&cache_key)? {
+                last_modified = lm;
+                cached_data = bytes;
+            }
+            // Here we set the If-Modified-Since header from the last_modified
+            if !last_modified.is_empty() {
+                headers.insert(
+                    IF_MODIFIED_SINCE,
+                    last_modified.parse().map_err(http::Error::from)?,
+                );
+            }
+        }
+
+        let res: Response = self
+            .send(method, &url, headers, self.max_retries, add_data)
+            .await?;
+
+        // missing or non-ASCII header is stored as no Last-Modified
+        last_modified = get_header_str(res.headers(), LAST_MODIFIED);
+        let etag = get_header_str(res.headers(), ETAG);
         // -------------------------------------------------------------
         let data: Bytes = match res.status() {
             #[cfg(feature = "cache")]
@@ -134,14 +407,13 @@ impl AlertsInUaClient {
                 cached_data
             }
             _ => {
-                let bytes = res.bytes().await?;
+                let bytes = res.bytes().await.map_err(|e| self.map_reqwest_error(e))?;
                 if cfg!(feature = "cache") {
                     // Save the data to the cache
-                    self.cache_manager
-                        .put(&url, &last_modified, bytes.clone())
-                        .inspect_err(|e| {
-                            log::error!("Error writing to cache: {:?}", e);
-                        })?;
+                    let entry = CacheEntry(bytes.clone(), last_modified, etag);
+                    self.cache_manager.put(&cache_key, entry).inspect_err(|e| {
+                        log::error!("Error writing to cache: {:?}", e);
+                    })?;
                 }
 
                 bytes
@@ -149,7 +421,73 @@ impl AlertsInUaClient {
         };
 
         // Return deserialized data
-        Ok(serde_json::from_slice(&data)?)
+        deserialize_body(&data)
+    }
+
+    /// Sends the request with no caching, retrying transient failures up to `max_retries`
+    async fn request_uncached<R, D>(
+        &self,
+        method: Method,
+        url: &str,
+        max_retries: u32,
+        add_data: D,
+    ) -> Result<R>
+    where
+        R: for<'de> Deserialize<'de>,
+        D: Fn(RequestBuilder) -> RequestBuilder,
+    {
+        let url = self.get_api_url(url);
+        let mut headers = HeaderMap::new();
+        headers.insert("Accept", HeaderValue::from_static("application/json"));
+        let res = self
+            .send(method, &url, headers, max_retries, add_data)
+            .await?;
+        let bytes = res.bytes().await.map_err(|e| self.map_reqwest_error(e))?;
+        deserialize_body(&bytes)
+    }
+
+    /// Conditional request: sends `If-None-Match` with the `ETag` cached for the URL and query
+    /// and returns [`Cached::NotModified`] with the body cached with it on `304 Not Modified`
+    async fn request_cached<R, D>(
+        &self,
+        method: Method,
+        url: &str,
+        add_data: D,
+    ) -> Result<Cached<R>>
+    where
+        R: for<'de> Deserialize<'de>,
+        D: Fn(RequestBuilder) -> RequestBuilder,
+    {
+        // Build full URL
+        let url = self.get_api_url(url);
+        let cache_key = self.get_cache_key(&method, &url, &add_data);
+        let mut headers = HeaderMap::new();
+        headers.insert("Accept", HeaderValue::from_static("application/json"));
+        let cached = self.cache_manager.get(&cache_key)?;
+        if let Some(CacheEntry(_, _, etag)) = cached.as_ref().filter(|e| !e.2.is_empty()) {
+            headers.insert(IF_NONE_MATCH, etag.parse().map_err(http::Error::from)?);
+        }
+
+        let res: Response = self
+            .send(method, &url, headers, self.max_retries, add_data)
+            .await?;
+
+        if res.status() == StatusCode::NOT_MODIFIED {
+            log::trace!(target: env!("CARGO_PKG_NAME"), "Response status '304 Not Modified' for ETag, return cached data");
+            // 304 is only expected in reply to `If-None-Match`, which is sent with cached body
+            let CacheEntry(bytes, ..) = cached.ok_or(ApiError::Internal)?;
+            return Ok(Cached::NotModified(deserialize_body(&bytes)?));
+        }
+        let last_modified = get_header_str(res.headers(), LAST_MODIFIED);
+        let etag = get_header_str(res.headers(), ETAG);
+        let bytes = res.bytes().await.map_err(|e| self.map_reqwest_error(e))?;
+        let data = deserialize_body(&bytes)?;
+        if !etag.is_empty() {
+            let entry = CacheEntry(bytes, last_modified, etag);
+            self.cache_manager.put(&cache_key, entry)?;
+        }
+
+        Ok(Cached::Fresh(data))
     }
 }
 
@@ -169,21 +507,64 @@ pub trait BaseHttpClient: Send + fmt::Debug {
     async fn get<R>(&self, url: &str, payload: &Query) -> Result<R>
     where
         R: for<'de> Deserialize<'de>;
+
+    /// Same as `get`, but uses `ETag` / `If-None-Match` to skip unchanged responses
+    #[allow(async_fn_in_trait)]
+    async fn get_cached<R>(&self, url: &str, payload: &Query) -> Result<Cached<R>>
+    where
+        R: for<'de> Deserialize<'de>;
+
+    /// Sends `body` serialized as JSON, never cached or retried since POST is not idempotent
+    #[allow(async_fn_in_trait)]
+    async fn post<R, B>(&self, url: &str, body: &B) -> Result<R>
+    where
+        R: for<'de> Deserialize<'de>,
+        B: Serialize + Sync;
+}
+
+/// Appends query parameters to the request, leaving it untouched if there are none
+#[inline]
+fn with_query(req: RequestBuilder, payload: &Query) -> RequestBuilder {
+    if payload.is_empty() {
+        req
+    } else {
+        req.query(payload)
+    }
 }
 
 impl BaseHttpClient for AlertsInUaClient {
     #[inline]
-    async fn get<R>(&self, url: &str, _payload: &Query<'_>) -> Result<R>
+    async fn get<R>(&self, url: &str, payload: &Query<'_>) -> Result<R>
+    where
+        R: for<'de> Deserialize<'de>,
+    {
+        self.request(Method::GET, url, |r| with_query(r, payload))
+            .await
+    }
+
+    #[inline]
+    async fn get_cached<R>(&self, url: &str, payload: &Query<'_>) -> Result<Cached<R>>
+    where
+        R: for<'de> Deserialize<'de>,
+    {
+        self.request_cached(Method::GET, url, |r| with_query(r, payload))
+            .await
+    }
+
+    #[inline]
+    async fn post<R, B>(&self, url: &str, body: &B) -> Result<R>
     where
         R: for<'de> Deserialize<'de>,
+        B: Serialize + Sync,
     {
-        self.request(Method::GET, url, |r| r).await
+        self.request_uncached(Method::POST, url, 0, |r| r.json(body))
+            .await
     }
 }
 
 /// The API for the AlertsInUaClient
 #[async_trait]
-pub trait AlertsInUaApi: fmt::Debug {
+pub trait AlertsInUaApi: fmt::Debug + Send + Sync {
     async fn get_active_alerts(&self) -> Result<Alerts>;
 
     async fn get_alerts_history(&self, location_aid: &i8, period: &str) -> Result<Alerts>;
@@ -199,7 +580,26 @@ pub trait AlertsInUaApi: fmt::Debug {
 impl AlertsInUaApi for AlertsInUaClient {
     async fn get_active_alerts(&self) -> Result<Alerts> {
         let url = "/alerts/active.json";
-        self.get(url, &Query::default()).await
+        let mut alerts: Alerts =
+            self.get_cached(url, &Query::default()).await?.into_inner();
+        let mut pages = 1;
+        // follow cursor of paginated response, single page has none. Only the first page
+        // is cached by ETag, cursors change between fetches and would pile up in the cache
+        while let Some(cursor) = alerts.next_cursor().map(str::to_string) {
+            if pages >= API_MAX_PAGES {
+                log::warn!(target: env!("CARGO_PKG_NAME"), "Stop fetching alerts after {} pages, next cursor '{}'", pages, cursor);
+                break;
+            }
+            let query = Query::from([("cursor", cursor.as_str())]);
+            let page: Alerts = self
+                .request_uncached(Method::GET, url, self.max_retries, |r| {
+                    with_query(r, &query)
+                })
+                .await?;
+            alerts.append_page(page);
+            pages += 1;
+        }
+        Ok(alerts)
     }
 
     async fn get_alerts_history(&self, location_aid: &i8, period: &str) -> Result<Alerts> {
@@ -216,8 +616,8 @@ impl AlertsInUaApi for AlertsInUaClient {
         &self,
     ) -> Result<AirRaidAlertOblastStatuses> {
         let url = "/iot/active_air_raid_alerts_by_oblast.json";
-        let data: String = self.get(url, &Query::default()).await?;
-        let result = AirRaidAlertOblastStatuses::new(data, Some(true));
+        let data: String = self.get_cached(url, &Query::default()).await?.into_inner();
+        let result = AirRaidAlertOblastStatuses::new(data, Some(false));
         Ok(result)
     }
 }
@@ -234,24 +634,73 @@ mod tests {
     use mockall::predicate::*;
     use mockito::Server as MockServer;
     use serde_json::json;
-    use std::sync::Arc;
+    use std::sync::{Arc, Mutex};
 
     #[test]
     fn test_trait() {
-        let api_client: Arc<dyn AlertsInUaApi> = Arc::new(AlertsInUaClient::new("", ""));
+        let api_client: Arc<dyn AlertsInUaApi> =
+            Arc::new(AlertsInUaClient::new("", "").unwrap());
         println!("{:?}", api_client);
     }
 
     /* #[tokio::test]
     async fn test_get_last_modified() {
-        let client = AlertsInUaClient::new("https://api.alerts.in.ua", "token");
+        let client = AlertsInUaClient::new("https://api.alerts.in.ua", "token").unwrap();
         let result = client.get_last_modified().await;
         assert!(result.is_ok());
     } */
 
+    /// Logger collecting formatted records of this crate (not of mock server),
+    /// to check what would be written to log files
+    struct CaptureLogger(Mutex<Vec<String>>);
+
+    impl log::Log for CaptureLogger {
+        fn enabled(&self, metadata: &log::Metadata) -> bool {
+            metadata.target() == env!("CARGO_PKG_NAME")
+        }
+
+        fn log(&self, record: &log::Record) {
+            if self.enabled(record.metadata()) {
+                self.0.lock().unwrap().push(record.args().to_string());
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    static CAPTURE_LOGGER: CaptureLogger = CaptureLogger(Mutex::new(Vec::new()));
+
+    #[tokio::test]
+    async fn test_request_logging_redacts_token() -> Result<()> {
+        let token = "secret-token-8f2a";
+        log::set_logger(&CAPTURE_LOGGER).unwrap();
+        log::set_max_level(log::LevelFilter::Trace);
+        let mut server = MockServer::new_async().await;
+        let client = AlertsInUaClient::new(server.url().as_str(), token).unwrap();
+        let mock = server
+            .mock("GET", "/v1/alerts/active.json")
+            .match_header("Authorization", format!("Bearer {}", token).as_str())
+            .with_header("Last-Modified", "Tue, 14 May 2024 18:18:18 GMT")
+            .with_body(r#"{"alerts":[],"disclaimer":"","meta":{"last_updated_at":"2024/05/06 10:02:45 +0000"}}"#)
+            .create_async()
+            .await;
+
+        client.get_active_alerts().await?;
+
+        mock.assert();
+        let logs = CAPTURE_LOGGER.0.lock().unwrap().join("\n");
+        assert!(logs.contains("Request GET http"));
+        assert!(logs.contains("/v1/alerts/active.json"));
+        assert!(logs.contains("status '200 OK'"));
+        assert!(logs.contains(REDACTED));
+        assert!(!logs.contains(token));
+        assert!(!format!("{:?}", client).contains(token));
+        Ok(())
+    }
+
     #[test]
     fn test_get_api_url() {
-        let client = AlertsInUaClient::new("https://api.alerts.in.ua", "token");
+        let client = AlertsInUaClient::new("https://api.alerts.in.ua", "token").unwrap();
         let url = client.get_api_url("/alerts/active.json");
         assert_eq!(url, "https://api.alerts.in.ua/v1/alerts/active.json");
     }
@@ -259,7 +708,7 @@ mod tests {
     #[tokio::test]
     async fn test_get_active_alerts() -> Result<()> {
         let mut server = MockServer::new_async().await;
-        let client = AlertsInUaClient::new(server.url().as_str(), "token");
+        let client = AlertsInUaClient::new(server.url().as_str(), "token").unwrap();
         let mock = server
             .mock(
                 "GET",
@@ -280,10 +729,162 @@ mod tests {
         Ok(())
     }
 
+    /// Active alerts response body with single alert and optional next page cursor
+    fn alerts_page(id: i32, next: Option<&str>) -> String {
+        json!({
+            "alerts": [{
+                "id": id,
+                "location_title": "Луганська область",
+                "location_type": "oblast",
+                "started_at": "2022-04-04T16:45:39.000Z",
+                "finished_at": null,
+                "updated_at": "2023-10-29T18:22:37.357Z",
+                "alert_type": "air_raid",
+                "location_uid": "16",
+                "location_oblast": "Луганська область",
+                "location_oblast_uid": 16,
+                "notes": null,
+                "calculated": null
+            }],
+            "disclaimer": "",
+            "meta": { "last_updated_at": "2024/05/06 10:02:45 +0000", "next": next }
+        })
+        .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_get_active_alerts_paginated() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+        let client = AlertsInUaClient::new(server.url().as_str(), "token").unwrap();
+        let first = server
+            .mock("GET", "/v1/alerts/active.json")
+            .match_query(mockito::Matcher::Missing)
+            .with_header("Last-Modified", "Tue, 14 May 2024 18:18:18 GMT")
+            .with_body(alerts_page(1, Some("page2")))
+            .create_async()
+            .await;
+        let second = server
+            .mock("GET", "/v1/alerts/active.json")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "cursor".into(),
+                "page2".into(),
+            ))
+            .with_header("Last-Modified", "Tue, 14 May 2024 18:18:18 GMT")
+            .with_body(alerts_page(2, None))
+            .create_async()
+            .await;
+
+        let result = client.get_active_alerts().await?;
+
+        first.assert_async().await;
+        second.assert_async().await;
+        let ids: Vec<i32> = result.iter().map(|a| a.id).collect();
+        assert_eq!(ids, vec![1, 2]);
+        assert_eq!(result.next_cursor(), None);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_active_alerts_paginated_not_modified() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+        let client = AlertsInUaClient::new(server.url().as_str(), "token").unwrap();
+        let first_query = mockito::Matcher::Missing;
+        let second_query = mockito::Matcher::UrlEncoded("cursor".into(), "page2".into());
+        let first = server
+            .mock("GET", "/v1/alerts/active.json")
+            .match_query(first_query.clone())
+            .with_header("ETag", r#""p1""#)
+            .with_body(alerts_page(1, Some("page2")))
+            .create_async()
+            .await;
+        let second = server
+            .mock("GET", "/v1/alerts/active.json")
+            .match_query(second_query.clone())
+            .with_header("ETag", r#""p2""#)
+            .with_body(alerts_page(2, None))
+            .create_async()
+            .await;
+
+        client.get_active_alerts().await?;
+        first.assert_async().await;
+        second.assert_async().await;
+
+        // cursor pages are not cached
+        let cursor_key = format!("{}/v1/alerts/active.json?cursor=page2", server.url());
+        assert!(client.cache_manager.get(&cursor_key)?.is_none());
+
+        // first page is revalidated with its ETag, cursor page is fetched again
+        let first = server
+            .mock("GET", "/v1/alerts/active.json")
+            .match_query(first_query)
+            .match_header("If-None-Match", r#""p1""#)
+            .with_status(304)
+            .expect(1)
+            .create_async()
+            .await;
+        let second = server
+            .mock("GET", "/v1/alerts/active.json")
+            .match_query(second_query)
+            .match_header("If-None-Match", mockito::Matcher::Missing)
+            .with_body(alerts_page(2, None))
+            .expect(1)
+            .create_async()
+            .await;
+
+        let result = client.get_active_alerts().await?;
+
+        first.assert_async().await;
+        second.assert_async().await;
+        let ids: Vec<i32> = result.iter().map(|a| a.id).collect();
+        assert_eq!(ids, vec![1, 2]);
+        assert!(client.cache_manager.get(&cursor_key)?.is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_caches_pages_by_query() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+        let client = AlertsInUaClient::new(server.url().as_str(), "token").unwrap();
+        let url = "/alerts/active.json";
+        server
+            .mock("GET", "/v1/alerts/active.json")
+            .match_query(mockito::Matcher::Missing)
+            .with_header("Last-Modified", "Tue, 14 May 2024 18:18:18 GMT")
+            .with_body(alerts_page(1, Some("page2")))
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/v1/alerts/active.json")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "cursor".into(),
+                "page2".into(),
+            ))
+            .with_header("Last-Modified", "Tue, 14 May 2024 18:18:18 GMT")
+            .with_body(alerts_page(2, None))
+            .create_async()
+            .await;
+
+        let _: Alerts = client.get(url, &Query::default()).await?;
+        let _: Alerts = client.get(url, &Query::from([("cursor", "page2")])).await?;
+
+        let cached = |key: &str| -> Alerts {
+            let CacheEntry(bytes, ..) = client.cache_manager.get(key).unwrap().unwrap();
+            deserialize_body(&bytes).unwrap()
+        };
+        let first = cached(&format!("{}/v1/alerts/active.json", server.url()));
+        let second = cached(&format!(
+            "{}/v1/alerts/active.json?cursor=page2",
+            server.url()
+        ));
+        assert_eq!(first.iter().next().unwrap().id, 1);
+        assert_eq!(second.iter().next().unwrap().id, 2);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_get_air_raid_alert_statuses_by_location() -> Result<()> {
         let mut server = MockServer::new_async().await;
-        let client = AlertsInUaClient::new(server.url().as_str(), "token");
+        let client = AlertsInUaClient::new(server.url().as_str(), "token").unwrap();
         let mock = server
             .mock(
                 "GET",
@@ -294,11 +895,467 @@ mod tests {
             .create_async()
             .await;
 
-        let _result = client.get_air_raid_alert_statuses_by_location().await?;
+        let result = client.get_air_raid_alert_statuses_by_location().await?;
 
         mock.assert();
-        // FIXME:
-        // assert_eq!(&*result, "ANNAANNANNNPANANANNNNAANNNN");
+        assert_eq!(result.raw_data(), "ANNAANNANNNPANANANNNNAANNNN");
+        assert_eq!(result.get(11).unwrap().status(), &AlertStatus::P);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_request_retry_on_rate_limit() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+        let client = AlertsInUaClient::new(server.url().as_str(), "token")
+            .unwrap()
+            .with_retry(3, Duration::from_millis(1));
+        let mock_429 = server
+            .mock("GET", mockito::Matcher::Any)
+            .with_status(429)
+            .expect(2)
+            .create_async()
+            .await;
+        let mock_200 = server
+            .mock("GET", mockito::Matcher::Any)
+            .with_header("Last-Modified", "Tue, 14 May 2024 18:18:18 GMT")
+            .with_body(r#"{"alerts":[],"disclaimer":"","meta":{"last_updated_at":"2024/05/06 10:02:45 +0000"}}"#)
+            .create_async()
+            .await;
+
+        let result = client.get_active_alerts().await?;
+
+        mock_429.assert_async().await;
+        mock_200.assert_async().await;
+        assert!(result.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_retry_delay_max() {
+        let client = AlertsInUaClient::new("", "")
+            .unwrap()
+            .with_retry(3, Duration::from_secs(60));
+        let retry_after = |secs: u64| {
+            let mut headers = HeaderMap::new();
+            headers.insert(reqwest::header::RETRY_AFTER, secs.into());
+            headers
+        };
+
+        // backoff is clamped
+        let delay = client.get_retry_delay(2, &HeaderMap::new());
+        assert_eq!(delay, Some(API_RETRY_MAX_DELAY));
+        // short Retry-After is honored
+        let delay = client.get_retry_delay(0, &retry_after(1));
+        assert_eq!(delay, Some(Duration::from_secs(1)));
+        // too long Retry-After gives up
+        let secs = API_RETRY_MAX_DELAY.as_secs() + 1;
+        assert_eq!(client.get_retry_delay(0, &retry_after(secs)), None);
+    }
+
+    #[tokio::test]
+    async fn test_request_retry_after_too_long() {
+        let mut server = MockServer::new_async().await;
+        let client = AlertsInUaClient::new(server.url().as_str(), "token")
+            .unwrap()
+            .with_retry(3, Duration::from_millis(1));
+        let mock = server
+            .mock("GET", mockito::Matcher::Any)
+            .with_status(429)
+            .with_header("Retry-After", "3600")
+            .expect(1)
+            .create_async()
+            .await;
+
+        let started_at = Instant::now();
+        let result = client.get_active_alerts().await;
+
+        mock.assert_async().await;
+        assert!(matches!(result, Err(ApiError::RateLimitError)));
+        assert!(started_at.elapsed() < API_RETRY_MAX_DELAY);
+    }
+
+    #[tokio::test]
+    async fn test_request_retry_exhausted() {
+        let mut server = MockServer::new_async().await;
+        let client = AlertsInUaClient::new(server.url().as_str(), "token")
+            .unwrap()
+            .with_retry(1, Duration::from_millis(1));
+        let mock = server
+            .mock("GET", mockito::Matcher::Any)
+            .with_status(500)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let result = client.get_active_alerts().await;
+
+        mock.assert_async().await;
+        assert!(matches!(result, Err(ApiError::InternalServerError)));
+    }
+
+    #[tokio::test]
+    async fn test_request_not_found() {
+        let mut server = MockServer::new_async().await;
+        let client = AlertsInUaClient::new(server.url().as_str(), "token").unwrap();
+        let mock = server
+            .mock("GET", mockito::Matcher::Any)
+            .with_status(404)
+            .create_async()
+            .await;
+
+        let result = client.get_active_alerts().await;
+
+        mock.assert_async().await;
+        let expected_url = format!("{}/v1/alerts/active.json", server.url());
+        assert!(matches!(result, Err(ApiError::NotFound(url)) if url == expected_url));
+    }
+
+    #[tokio::test]
+    async fn test_request_malformed_json() {
+        let mut server = MockServer::new_async().await;
+        let client = AlertsInUaClient::new(server.url().as_str(), "token").unwrap();
+        let body = format!(
+            r#"{{"alerts": "unexpected", "padding": "{}"}}"#,
+            "x".repeat(500)
+        );
+        server
+            .mock("GET", mockito::Matcher::Any)
+            .with_header("Last-Modified", "Tue, 14 May 2024 18:18:18 GMT")
+            .with_body(&body)
+            .create_async()
+            .await;
+
+        let result = client.get_active_alerts().await;
+
+        let Err(ApiError::DeserializationError(message)) = result else {
+            panic!("expected DeserializationError, got {:?}", result);
+        };
+        assert!(message.contains(r#"{"alerts": "unexpected""#));
+        assert!(message.contains("invalid type"));
+        // body is truncated
+        assert!(message.len() < body.len());
+        assert!(message.ends_with("…'"));
+    }
+
+    /// Serve `requests` statuses responses over keep-alive connections,
+    /// returns server URL and counter of accepted connections
+    async fn serve_statuses(requests: usize) -> (String, Arc<Mutex<usize>>) {
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let connections = Arc::new(Mutex::new(0));
+        let counter = connections.clone();
+        tokio::spawn(async move {
+            let mut served = 0;
+            while served < requests {
+                let (stream, _) = listener.accept().await.unwrap();
+                *counter.lock().unwrap() += 1;
+                let mut stream = BufReader::new(stream);
+                let mut line = String::new();
+                while served < requests {
+                    // read request head until empty line
+                    line.clear();
+                    if stream.read_line(&mut line).await.unwrap_or(0) == 0 {
+                        break;
+                    }
+                    if line != "\r\n" {
+                        continue;
+                    }
+                    let body = r#""NNNNNNNNNNNNNNNNNNNNNNNNNNN""#;
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\nLast-Modified: Tue, 14 May 2024 18:18:18 GMT\r\nContent-Length: {}\r\n\r\n{}",
+                        body.len(),
+                        body
+                    );
+                    stream
+                        .get_mut()
+                        .write_all(response.as_bytes())
+                        .await
+                        .unwrap();
+                    served += 1;
+                }
+            }
+        });
+        (url, connections)
+    }
+
+    #[tokio::test]
+    async fn test_client_reuses_connection() -> Result<()> {
+        let (url, connections) = serve_statuses(2).await;
+        let client = AlertsInUaClient::new(&url, "token").unwrap();
+
+        client.get_air_raid_alert_statuses_by_location().await?;
+        client.get_air_raid_alert_statuses_by_location().await?;
+
+        assert_eq!(*connections.lock().unwrap(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_with_connection_pool_size() -> Result<()> {
+        let (url, connections) = serve_statuses(2).await;
+        let client = AlertsInUaClient::new(&url, "token")
+            .unwrap()
+            .with_connection_pool_size(0);
+
+        client.get_air_raid_alert_statuses_by_location().await?;
+        client.get_air_raid_alert_statuses_by_location().await?;
+
+        // no idle connections are kept
+        assert_eq!(*connections.lock().unwrap(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_user_agent() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+        let body = r#""NNNNNNNNNNNNNNNNNNNNNNNNNNN""#;
+        let mock_default = server
+            .mock("GET", "/v1/iot/active_air_raid_alerts_by_oblast.json")
+            .match_header(
+                "User-Agent",
+                format!("alertsinua-cli/{}", env!("CARGO_PKG_VERSION")).as_str(),
+            )
+            .with_header("Last-Modified", "Tue, 14 May 2024 18:18:18 GMT")
+            .with_body(body)
+            .create_async()
+            .await;
+
+        let client = AlertsInUaClient::new(server.url().as_str(), "token").unwrap();
+        client.get_air_raid_alert_statuses_by_location().await?;
+        mock_default.assert_async().await;
+
+        let mock_custom = server
+            .mock("GET", "/v1/iot/active_air_raid_alerts_by_oblast.json")
+            .match_header("User-Agent", "my-dashboard/1.0")
+            .with_header("Last-Modified", "Tue, 14 May 2024 18:18:18 GMT")
+            .with_body(body)
+            .create_async()
+            .await;
+
+        let client = AlertsInUaClient::new(server.url().as_str(), "token")
+            .unwrap()
+            .with_user_agent("my-dashboard/1.0");
+        client.get_air_raid_alert_statuses_by_location().await?;
+        mock_custom.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_cached_sends_etag() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+        let client = AlertsInUaClient::new(server.url().as_str(), "token").unwrap();
+        let url = "/iot/active_air_raid_alerts_by_oblast.json";
+        let mock_fresh = server
+            .mock("GET", "/v1/iot/active_air_raid_alerts_by_oblast.json")
+            .with_header("ETag", r#""abc123""#)
+            .with_body(r#""ANNAANNANNNPANANANNNNAANNNN""#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let result: Cached<String> = client.get_cached(url, &Query::default()).await?;
+        assert_eq!(
+            result,
+            Cached::Fresh("ANNAANNANNNPANANANNNNAANNNN".to_string())
+        );
+        mock_fresh.assert_async().await;
+        // ETag is kept with the body in the bounded response cache
+        let key = format!("{}/v1{}", server.url(), url);
+        let CacheEntry(_, _, etag) = client.cache_manager.get(&key)?.unwrap();
+        assert_eq!(etag, r#""abc123""#);
+
+        let mock_not_modified = server
+            .mock("GET", "/v1/iot/active_air_raid_alerts_by_oblast.json")
+            .match_header("If-None-Match", r#""abc123""#)
+            .with_status(304)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let result: Cached<String> = client.get_cached(url, &Query::default()).await?;
+        assert_eq!(
+            result,
+            Cached::NotModified("ANNAANNANNNPANANANNNNAANNNN".to_string())
+        );
+        mock_not_modified.assert_async().await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_active_alerts_not_modified() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+        let client = AlertsInUaClient::new(server.url().as_str(), "token").unwrap();
+        let body = json!({
+            "alerts": [{
+                "id": 1,
+                "location_title": "Луганська область",
+                "location_type": "oblast",
+                "started_at": "2022-04-04T16:45:39.000Z",
+                "finished_at": null,
+                "updated_at": "2023-10-29T18:22:37.357Z",
+                "alert_type": "air_raid",
+                "location_uid": "16",
+                "location_oblast": "Луганська область",
+                "location_oblast_uid": 16,
+                "notes": null,
+                "calculated": null
+            }],
+            "disclaimer": "",
+            "meta": { "last_updated_at": "2024/05/06 10:02:45 +0000" }
+        })
+        .to_string();
+        let mock_fresh = server
+            .mock("GET", "/v1/alerts/active.json")
+            .with_header("ETag", r#""v1""#)
+            .with_body(body)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let fresh = client.get_active_alerts().await?;
+        mock_fresh.assert_async().await;
+
+        let mock_not_modified = server
+            .mock("GET", "/v1/alerts/active.json")
+            .match_header("If-None-Match", r#""v1""#)
+            .with_status(304)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let result = client.get_active_alerts().await?;
+        mock_not_modified.assert_async().await;
+        assert_eq!(result, fresh);
+        assert_eq!(result.len(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_request_timeout() {
+        let mut server = MockServer::new_async().await;
+        let client = AlertsInUaClient::new(server.url().as_str(), "token")
+            .unwrap()
+            .with_timeout(Duration::from_millis(1));
+        let _mock = server
+            .mock("GET", mockito::Matcher::Any)
+            .with_header("Last-Modified", "Tue, 14 May 2024 18:18:18 GMT")
+            .with_chunked_body(|w| {
+                std::thread::sleep(Duration::from_millis(100));
+                w.write_all(b"{}")
+            })
+            .create_async()
+            .await;
+
+        let result = client.get_active_alerts().await;
+
+        assert!(matches!(result, Err(ApiError::Timeout(_))));
+    }
+
+    #[test]
+    fn test_with_proxy() {
+        let client = AlertsInUaClient::new("https://api.alerts.in.ua", "token").unwrap();
+        let result = client.with_proxy("http://127.0.0.1:3128");
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_with_proxy_invalid_url() {
+        let client = AlertsInUaClient::new("https://api.alerts.in.ua", "token").unwrap();
+        let result = client.with_proxy("http://[::1");
+        assert!(matches!(result, Err(ApiError::ProxyError(_))));
+    }
+
+    #[test]
+    fn test_find_proxy() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+
+        let result = AlertsInUaClient::find_proxy(env(&[]));
+        assert!(matches!(result, Ok(None)));
+
+        let result = AlertsInUaClient::find_proxy(env(&[
+            ("ALERTSINUA_PROXY", ""),
+            ("HTTPS_PROXY", "http://127.0.0.1:3128"),
+        ]));
+        assert!(matches!(result, Ok(Some(_))));
+
+        let result =
+            AlertsInUaClient::find_proxy(env(&[("ALERTSINUA_PROXY", "http://[::1")]));
+        assert!(matches!(result, Err(ApiError::ProxyError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_post() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+        let client = AlertsInUaClient::new(server.url().as_str(), "token").unwrap();
+        let body = json!({"location_uids": [16, 31]});
+        let mock = server
+            .mock("POST", "/v1/alerts/active.json")
+            .match_header("Content-Type", "application/json")
+            .match_body(mockito::Matcher::Json(body.clone()))
+            .match_header("If-Modified-Since", mockito::Matcher::Missing)
+            .with_body(r#"{"alerts":[],"disclaimer":"","meta":{"last_updated_at":"2024/05/06 10:02:45 +0000"}}"#)
+            .expect(2)
+            .create_async()
+            .await;
+
+        // no Last-Modified in response, nothing is cached between calls
+        let result: Alerts = client.post("/alerts/active.json", &body).await?;
+        assert!(result.is_empty());
+        let result: Alerts = client.post("/alerts/active.json", &body).await?;
+        assert!(result.is_empty());
+
+        mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_post_not_retried() {
+        let mut server = MockServer::new_async().await;
+        let client = AlertsInUaClient::new(server.url().as_str(), "token")
+            .unwrap()
+            .with_retry(3, Duration::from_millis(1));
+        let mock = server
+            .mock("POST", "/v1/alerts/active.json")
+            .with_status(500)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let result: Result<Alerts> = client.post("/alerts/active.json", &json!({})).await;
+
+        mock.assert_async().await;
+        assert!(matches!(result, Err(ApiError::InternalServerError)));
+    }
+
+    #[tokio::test]
+    async fn test_get_with_query() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+        let client = AlertsInUaClient::new(server.url().as_str(), "token").unwrap();
+        let mock = server
+            .mock("GET", "/v1/alerts/active.json")
+            .match_query(mockito::Matcher::UrlEncoded("foo".into(), "bar".into()))
+            .with_header("Last-Modified", "Tue, 14 May 2024 18:18:18 GMT")
+            .with_body(r#"{"alerts":[],"disclaimer":"","meta":{"last_updated_at":"2024/05/06 10:02:45 +0000"}}"#)
+            .create_async()
+            .await;
+
+        let query = Query::from([("foo", "bar")]);
+        let result: Alerts = client.get("/alerts/active.json", &query).await?;
+
+        mock.assert_async().await;
+        assert!(result.is_empty());
 
         Ok(())
     }
@@ -1,26 +1,20 @@
 /// Custom enum that contains all the possible errors that may occur when using
-/// [`reqwest`].
+/// [`AlertsInUaClient`](crate::AlertsInUaClient).
 ///
 /// Sample usage:
 ///
-/// ```
+/// ```no_run
 /// # #[tokio::main]
-/// # async fn main() {
-/// use http::{HttpError, AlertsInUaClient, BaseHttpClient};
+/// # async fn main() -> Result<(), ralertsinua_http::ApiError> {
+/// use ralertsinua_http::{AlertsInUaApi, AlertsInUaClient, ApiError};
 ///
-/// let client = AlertsInUaClient::default();
-/// let response = client.get("wrongurl", None, &Default::default()).await;
-/// match response {
-///     Ok(data) => println!("request succeeded: {:?}", data),
-///     Err(HttpError::Client(e)) => eprintln!("request failed: {}", e),
-///     Err(HttpError::StatusCode(response)) => {
-///         let code = response.status().as_u16();
-///         match response.json::<rspotify_model::ApiError>().await {
-///             Ok(api_error) => eprintln!("status code {}: {:?}", code, api_error),
-///             Err(_) => eprintln!("status code {}", code),
-///         }
-///     },
+/// let client = AlertsInUaClient::new("https://api.alerts.in.ua", "token")?;
+/// match client.get_active_alerts().await {
+///     Ok(alerts) => println!("request succeeded: {} alerts", alerts.len()),
+///     Err(ApiError::UnauthorizedError(e)) => eprintln!("invalid token: {}", e),
+///     Err(e) => eprintln!("request failed: {}", e),
 /// }
+/// # Ok(())
 /// # }
 /// ```
 #[derive(thiserror::Error, miette::Diagnostic, Debug)]
@@ -39,6 +33,14 @@ pub enum ApiError {
     #[diagnostic(help("most likely token is invalid or missing\n check you've provided it via environment variable 'ALERTSINUA_TOKEN' or as a parameter '--token'"))]
     UnauthorizedError(reqwest::Error),
 
+    #[error("API Error: Request timed out after {0:?}")]
+    #[diagnostic(help("try to increase request timeout, e.g. on slow connections"))]
+    Timeout(std::time::Duration),
+
+    #[error("API Error: Proxy error: {0}")]
+    #[diagnostic(help("check the proxy URL provided via environment variable 'ALERTSINUA_PROXY' or 'HTTPS_PROXY'"))]
+    ProxyError(reqwest::Error),
+
     #[error("API Error: Rate limit exceeded")]
     RateLimitError,
 
@@ -54,12 +56,22 @@ pub enum ApiError {
     #[error("API Error: Invalid URL: {0}")]
     InvalidURL(reqwest::Error),
 
+    #[error("API Error: Not found: {0}")]
+    #[diagnostic(help(
+        "endpoint may have been moved, check for a newer version or pass '--base-url'"
+    ))]
+    NotFound(String),
+
     #[error("API Error: Generic Http error: {0}")]
     HttpError(#[from] http::Error),
 
     #[error("API Error: JSON parse error: {0}")]
     ParseJson(#[from] serde_json::Error),
 
+    #[error("API Error: Unexpected response body: {0}")]
+    #[diagnostic(help("API response format may have changed, check for a newer version"))]
+    DeserializationError(String),
+
     #[error("API Error: Internal error")]
     Internal,
 }
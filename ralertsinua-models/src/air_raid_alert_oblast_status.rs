@@ -31,7 +31,7 @@ impl AirRaidAlertOblastStatus {
         status: char,
         oblast_level_only: Option<bool>,
     ) -> Self {
-        let status: AlertStatus = if status == 'P' && oblast_level_only.unwrap() {
+        let status: AlertStatus = if status == 'P' && oblast_level_only.unwrap_or(false) {
             AlertStatus::N
         } else {
             AlertStatus::from(status)
@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{AirRaidAlertOblastStatus, AlertStatus, ModelError, REGIONS_DATA};
 
-#[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct AirRaidAlertOblastStatuses {
     #[serde(skip)]
     raw_data: String,
@@ -40,7 +40,31 @@ pub mod deserialize_oblast_statuses {
         let s = String::deserialize(deserializer)?
             .trim_matches('"')
             .to_string();
-        AirRaidAlertOblastStatuses::from_string(s, Some(true)).map_err(Error::custom)
+        AirRaidAlertOblastStatuses::from_string(s, Some(false)).map_err(Error::custom)
+    }
+}
+
+/// Restores `raw_data` from serialized statuses string, so round-trip keeps value equal
+impl<'de> Deserialize<'de> for AirRaidAlertOblastStatuses {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Serialized {
+            oblast_statuses: String,
+        }
+
+        let data = Serialized::deserialize(deserializer)?
+            .oblast_statuses
+            .trim_matches('"')
+            .to_string();
+        let oblast_statuses = Self::from_string(data.clone(), Some(false))
+            .map_err(serde::de::Error::custom)?;
+        Ok(Self {
+            raw_data: data,
+            oblast_statuses,
+        })
     }
 }
 
@@ -51,7 +75,7 @@ impl Default for AirRaidAlertOblastStatuses {
 }
 
 impl AirRaidAlertOblastStatuses {
-    pub fn iter(&self) -> std::slice::Iter<AirRaidAlertOblastStatus> {
+    pub fn iter(&self) -> std::slice::Iter<'_, AirRaidAlertOblastStatus> {
         self.oblast_statuses.iter()
     }
     pub fn len(&self) -> usize {
@@ -130,7 +154,70 @@ impl AirRaidAlertOblastStatuses {
         self.filter_by_status(AlertStatus::P)
     }
 
+    /// Number of oblasts with active alert on all territory ("A")
+    pub fn active_alerts_count(&self) -> usize {
+        self.iter()
+            .filter(|os| os.is_active_on_all_oblast())
+            .count()
+    }
+
+    /// Number of oblasts with active alert on part of territory ("P")
+    pub fn partly_active_alerts_count(&self) -> usize {
+        self.iter().filter(|os| os.is_partly_active()).count()
+    }
+
     pub fn get_no_alert_oblasts(&self) -> Vec<AirRaidAlertOblastStatus> {
         self.filter_by_status(AlertStatus::N)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: &str = "ANNAANNANNNPANANANNNNAANNNN";
+
+    #[test]
+    fn test_partly_active_status() {
+        let statuses = AirRaidAlertOblastStatuses::new(DATA.to_string(), Some(false));
+        let status = statuses.get(11).unwrap();
+
+        assert_eq!(status.status(), &AlertStatus::P);
+        assert!(status.is_partly_active());
+        assert_eq!(statuses.get_partly_active_alert_oblasts().len(), 1);
+        assert_eq!(statuses.get_active_alert_oblasts().len(), 9);
+    }
+
+    #[test]
+    fn test_alerts_counts() {
+        let statuses = AirRaidAlertOblastStatuses::new(DATA.to_string(), Some(false));
+        assert_eq!(statuses.active_alerts_count(), 9);
+        assert_eq!(statuses.partly_active_alerts_count(), 1);
+
+        let statuses = AirRaidAlertOblastStatuses::new(DATA.to_string(), Some(true));
+        assert_eq!(statuses.partly_active_alerts_count(), 0);
+
+        let statuses = AirRaidAlertOblastStatuses::default();
+        assert_eq!(statuses.active_alerts_count(), 0);
+        assert_eq!(statuses.len(), 27);
+    }
+
+    #[test]
+    fn test_partly_active_status_oblast_level_only() {
+        let statuses = AirRaidAlertOblastStatuses::new(DATA.to_string(), Some(true));
+
+        assert_eq!(statuses.get(11).unwrap().status(), &AlertStatus::N);
+        assert!(statuses.get_partly_active_alert_oblasts().is_empty());
+    }
+
+    #[test]
+    fn test_serde_keeps_partly_active_status() {
+        let statuses = AirRaidAlertOblastStatuses::new(DATA.to_string(), Some(false));
+        let value = serde_json::to_value(&statuses).unwrap();
+        assert_eq!(value["oblast_statuses"], DATA);
+
+        let statuses: AirRaidAlertOblastStatuses = serde_json::from_value(value).unwrap();
+        assert_eq!(statuses.get(11).unwrap().status(), &AlertStatus::P);
+        assert_eq!(statuses.raw_data(), DATA);
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{alert_type::*, LocationType};
 use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, skip_serializing_none, DisplayFromStr};
-use time::OffsetDateTime;
+use time::{Duration, OffsetDateTime};
 
 #[skip_serializing_none]
 #[serde_as]
@@ -15,14 +15,16 @@ pub struct Alert {
     pub started_at: OffsetDateTime,
     #[serde(with = "time::serde::iso8601")]
     pub updated_at: OffsetDateTime,
-    // #[serde(with = "time::serde::iso8601")]
-    pub finished_at: Option<String>, // TODO: parse Option to OffsetDateTime
+    #[serde(default, with = "time::serde::iso8601::option")]
+    pub finished_at: Option<OffsetDateTime>,
     #[serde_as(as = "DisplayFromStr")] // Serialize with Display, deserialize with FromStr
     pub alert_type: AlertType,
     pub location_oblast: String,
     #[serde(with = "into_int")]
     pub location_uid: i32,
-    pub location_oblast_uid: i32,
+    /// Missing (null) for some hromada-level alerts
+    #[serde(default)]
+    pub location_oblast_uid: Option<i32>,
     pub notes: Option<String>,
     #[serde(default)]
     pub country: Option<String>,
@@ -51,10 +53,41 @@ pub mod into_int {
 }
 
 impl Alert {
+    /// Alert is active until it has `finished_at`
+    #[inline]
+    pub fn is_active(&self) -> bool {
+        self.finished_at.is_none()
+    }
+
+    /// Alert covers the whole oblast (or city with special status, e.g. Kyiv),
+    /// not only a part of it
+    pub fn is_oblast_level(&self) -> bool {
+        self.location_type != LocationType::Hromada
+            && self.location_oblast_uid.unwrap_or(self.location_uid) == self.location_uid
+    }
+
+    /// Rank among alerts of the same oblast, higher goes first: whole-oblast alerts
+    /// before partial ones (as in [`AlertStatus::for_alerts`](crate::AlertStatus::for_alerts)),
+    /// then more severe type, see [`AlertType::severity`], then the most recently updated.
+    /// Coverage wins over severity, otherwise a hromada air raid would be shown for an oblast
+    /// that is fully active because of e.g. artillery shelling
+    pub fn rank(&self) -> (bool, u8, OffsetDateTime) {
+        (
+            self.is_oblast_level(),
+            self.alert_type.severity(),
+            self.updated_at,
+        )
+    }
+
+    /// Time between `started_at` and `finished_at` (or now, if still active),
+    /// clamped to zero if `started_at` is in the future (clock skew)
+    pub fn duration(&self) -> Duration {
+        let end = self.finished_at.unwrap_or_else(OffsetDateTime::now_utc);
+        (end - self.started_at).max(Duration::ZERO)
+    }
+
     pub fn get_alert_duration(&self) -> std::time::Duration {
-        let now = OffsetDateTime::now_utc();
-        let offset_duration = now - self.started_at;
-        offset_duration.try_into().unwrap()
+        self.duration().unsigned_abs()
     }
 }
 
@@ -93,7 +126,7 @@ mod tests {
         assert_eq!(alert.location_type, LocationType::Oblast);
         assert_eq!(alert.location_oblast, "Луганська область");
         assert_eq!(alert.location_uid, 16);
-        assert_eq!(alert.location_oblast_uid, 16);
+        assert_eq!(alert.location_oblast_uid, Some(16));
         assert_eq!(alert.alert_type, AlertType::AirRaid);
         assert_eq!(alert.notes, None);
         assert_eq!(alert.country, None);
@@ -102,4 +135,91 @@ mod tests {
         assert_eq!(alert.updated_at.unix_timestamp(), 1_698_603_757);
         assert_eq!(alert.finished_at, None);
     }
+
+    #[test]
+    fn test_alert_deserialization_finished() {
+        use super::*;
+        use serde_json::json;
+
+        let data = json!({
+            "alert_type": "air_raid",
+            "finished_at": "2024-05-05T16:10:02.000Z",
+            "id": 73992,
+            "location_oblast": "Дніпропетровська область",
+            "location_oblast_uid": 9,
+            "location_title": "Дніпропетровська область",
+            "location_type": "oblast",
+            "location_uid": "9",
+            "notes": null,
+            "started_at": "2024-05-05T15:48:31.000Z",
+            "updated_at": "2024-05-05T16:10:02.818Z"
+        });
+
+        let alert: Alert = serde_json::from_value(data).unwrap();
+
+        assert_eq!(alert.started_at.unix_timestamp(), 1_714_924_111);
+        assert_eq!(
+            alert.finished_at.map(|f| f.unix_timestamp()),
+            Some(1_714_925_402)
+        );
+        let alert: Alert =
+            serde_json::from_value(serde_json::to_value(&alert).unwrap()).unwrap();
+        assert_eq!(
+            alert.finished_at.map(|f| f.unix_timestamp()),
+            Some(1_714_925_402)
+        );
+    }
+
+    #[test]
+    fn test_alert_duration() {
+        use super::*;
+        use crate::test_util::AlertBuilder;
+        use time::macros::datetime;
+
+        let mut alert = AlertBuilder::new(31)
+            .with_started_at(datetime!(2024-05-05 15:00:00 UTC))
+            .with_finished_at(datetime!(2024-05-05 16:30:00 UTC))
+            .build();
+        assert!(!alert.is_active());
+        assert_eq!(alert.duration(), Duration::minutes(90));
+        assert_eq!(
+            alert.get_alert_duration(),
+            std::time::Duration::from_secs(90 * 60)
+        );
+
+        // started_at in the future (clock skew) is clamped to zero
+        alert.finished_at = None;
+        alert.started_at = OffsetDateTime::now_utc() + Duration::hours(1);
+        assert!(alert.is_active());
+        assert_eq!(alert.duration(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_alert_deserialization_unknown_alert_type() {
+        use super::*;
+        use serde_json::json;
+
+        let data = json!({
+            "alert_type": "drone_attack",
+            "finished_at": null,
+            "id": 8757,
+            "location_oblast": "Луганська область",
+            "location_oblast_uid": 16,
+            "location_title": "Луганська область",
+            "location_type": "oblast",
+            "location_uid": "16",
+            "notes": null,
+            "started_at": "2022-04-04T16:45:39.000Z",
+            "updated_at": "2023-10-29T18:22:37.357Z"
+        });
+
+        let alert: Alert = serde_json::from_value(data).unwrap();
+
+        assert_eq!(
+            alert.alert_type,
+            AlertType::Unknown("drone_attack".to_string())
+        );
+        let value = serde_json::to_value(&alert).unwrap();
+        assert_eq!(value["alert_type"], "drone_attack");
+    }
 }
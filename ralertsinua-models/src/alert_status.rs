@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::Alert;
+
 #[derive(
     Debug,
     Default,
@@ -42,3 +44,58 @@ impl From<char> for AlertStatus {
         }
     }
 }
+
+impl AlertStatus {
+    /// Aggregate status of a region from its alerts: active ("A") if any active alert
+    /// covers the whole oblast, partly active ("P") if active alerts cover only its parts,
+    /// no alert ("N") otherwise, including empty `alerts`
+    pub fn for_alerts(alerts: &[Alert]) -> AlertStatus {
+        let mut status = AlertStatus::N;
+        for alert in alerts.iter().filter(|a| a.is_active()) {
+            if alert.is_oblast_level() {
+                return AlertStatus::A;
+            }
+            status = AlertStatus::P;
+        }
+        status
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_util::AlertBuilder, LocationType};
+    use time::macros::datetime;
+
+    #[test]
+    fn test_for_alerts() {
+        let oblast = || AlertBuilder::new(22).build();
+        let hromada = || {
+            AlertBuilder::new(22)
+                .with_location(LocationType::Hromada, 1293)
+                .build()
+        };
+        let finished = || {
+            AlertBuilder::new(22)
+                .with_finished_at(datetime!(2024-05-05 16:00:00 UTC))
+                .build()
+        };
+
+        assert_eq!(AlertStatus::for_alerts(&[]), AlertStatus::N);
+        assert_eq!(AlertStatus::for_alerts(&[finished()]), AlertStatus::N);
+        assert_eq!(AlertStatus::for_alerts(&[hromada()]), AlertStatus::P);
+        // city within oblast covers only a part of it
+        let city = AlertBuilder::new(22)
+            .with_location(LocationType::City, 1290)
+            .build();
+        assert_eq!(AlertStatus::for_alerts(&[city]), AlertStatus::P);
+        assert_eq!(
+            AlertStatus::for_alerts(&[hromada(), oblast()]),
+            AlertStatus::A
+        );
+        assert_eq!(
+            AlertStatus::for_alerts(&[finished(), hromada()]),
+            AlertStatus::P
+        );
+    }
+}
@@ -1,16 +1,98 @@
 #[derive(
-    Debug, Default, Clone, strum_macros::Display, strum_macros::EnumString, PartialEq,
+    Debug,
+    Default,
+    Clone,
+    strum_macros::Display,
+    strum_macros::EnumString,
+    strum_macros::EnumProperty,
+    PartialEq,
 )]
 pub enum AlertType {
     #[default]
-    #[strum(to_string = "air_raid")]
+    #[strum(to_string = "air_raid", props(icon = "✈", color = "red"))]
     AirRaid,
-    #[strum(to_string = "artillery_shelling")]
+    #[strum(
+        to_string = "artillery_shelling",
+        props(icon = "✸", color = "lightred")
+    )]
     ArtilleryShelling,
-    #[strum(to_string = "urban_fights")]
+    #[strum(to_string = "urban_fights", props(icon = "⚔", color = "magenta"))]
     UrbanFights,
-    #[strum(to_string = "nuclear")]
+    #[strum(to_string = "nuclear", props(icon = "☢", color = "yellow"))]
     Nuclear,
-    #[strum(to_string = "chemical")]
+    #[strum(to_string = "chemical", props(icon = "☣", color = "lightyellow"))]
     Chemical,
+    /// Any alert type not known yet, keeps the raw value for forward compatibility
+    #[strum(default, props(icon = "?", color = "gray"))]
+    Unknown(String),
+}
+
+impl AlertType {
+    /// Severity used to pick one alert per region, higher is more severe:
+    /// air raid > artillery shelling > nuclear > chemical > urban fights > unknown.
+    /// Nuclear and chemical threats endanger the whole area, urban fights stay local.
+    /// Only breaks ties between alerts of the same coverage, see [`Alert::rank`](crate::Alert::rank)
+    pub fn severity(&self) -> u8 {
+        match self {
+            Self::AirRaid => 5,
+            Self::ArtilleryShelling => 4,
+            Self::Nuclear => 3,
+            Self::Chemical => 2,
+            Self::UrbanFights => 1,
+            Self::Unknown(_) => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_alert_type_from_str() {
+        let cases = [
+            ("air_raid", AlertType::AirRaid),
+            ("artillery_shelling", AlertType::ArtilleryShelling),
+            ("urban_fights", AlertType::UrbanFights),
+            ("nuclear", AlertType::Nuclear),
+            ("chemical", AlertType::Chemical),
+        ];
+        for (s, expected) in cases {
+            let alert_type = AlertType::from_str(s).unwrap();
+            assert_eq!(alert_type, expected);
+            assert_eq!(alert_type.to_string(), s);
+        }
+    }
+
+    #[test]
+    fn test_alert_type_severity() {
+        let ordered = [
+            AlertType::AirRaid,
+            AlertType::ArtilleryShelling,
+            AlertType::Nuclear,
+            AlertType::Chemical,
+            AlertType::UrbanFights,
+            AlertType::Unknown("drone_attack".to_string()),
+        ];
+        assert!(ordered
+            .windows(2)
+            .all(|pair| pair[0].severity() > pair[1].severity()));
+    }
+
+    #[test]
+    fn test_alert_type_props() {
+        use strum::EnumProperty;
+
+        assert_eq!(AlertType::AirRaid.get_str("color"), Some("red"));
+        let unknown = AlertType::Unknown("drone_attack".to_string());
+        assert_eq!(unknown.get_str("icon"), Some("?"));
+    }
+
+    #[test]
+    fn test_alert_type_unknown() {
+        let alert_type = AlertType::from_str("drone_attack").unwrap();
+        assert_eq!(alert_type, AlertType::Unknown("drone_attack".to_string()));
+        assert_eq!(alert_type.to_string(), "drone_attack");
+    }
 }
@@ -5,7 +5,10 @@ use time::{format_description::BorrowedFormatItem, OffsetDateTime};
 use time_macros::format_description;
 
 #[allow(unused)]
-use crate::{Alert, AlertType, LocationType, ModelError};
+use crate::{
+    AirRaidAlertOblastStatuses, Alert, AlertStatus, AlertType, LocationType, ModelError,
+    REGIONS_DATA,
+};
 
 /// "2024/05/06 10:02:45 +0000"
 /// const LAST_UPDATED_AT_FORMAT: &str = "%Y/%m/%d %H:%M:%S %z";
@@ -44,12 +47,16 @@ struct Meta {
     #[get = "pub with_prefix"]
     #[serde(with = "with_custom_date_format")]
     last_updated_at: OffsetDateTime,
+    /// Cursor of the next page, if response is paginated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    next: Option<String>,
 }
 
 impl Default for Meta {
     fn default() -> Self {
         Self {
             last_updated_at: OffsetDateTime::now_utc(),
+            next: None,
         }
     }
 }
@@ -62,12 +69,22 @@ pub struct Alerts {
     meta: Meta,
 }
 
+impl From<Vec<Alert>> for Alerts {
+    /// Collection of given alerts, last updated now
+    fn from(alerts: Vec<Alert>) -> Self {
+        Self {
+            alerts,
+            ..Default::default()
+        }
+    }
+}
+
 impl Alerts {
     pub fn get_alerts(&self) -> Vec<Alert> {
         self.alerts.clone()
     }
 
-    pub fn iter(&self) -> std::slice::Iter<Alert> {
+    pub fn iter(&self) -> std::slice::Iter<'_, Alert> {
         self.alerts.iter()
     }
 
@@ -79,6 +96,17 @@ impl Alerts {
         self.alerts.is_empty()
     }
 
+    /// Cursor of the next page, `None` for the last (or single) page
+    pub fn next_cursor(&self) -> Option<&str> {
+        self.meta.next.as_deref()
+    }
+
+    /// Append alerts of the next `page`, taking its cursor
+    pub fn append_page(&mut self, page: Alerts) {
+        self.alerts.extend(page.alerts);
+        self.meta.next = page.meta.next;
+    }
+
     pub fn get_last_updated_at(&self) -> &OffsetDateTime {
         &self.meta.last_updated_at
     }
@@ -123,6 +151,17 @@ impl Alerts {
             .collect()
     }
 
+    /// Alerts of the oblast itself and of its parts, e.g. hromadas
+    pub fn get_alerts_by_oblast_uid(&self, oblast_uid: i32) -> Vec<Alert> {
+        self.alerts
+            .iter()
+            .filter(|a| {
+                a.location_uid == oblast_uid || a.location_oblast_uid == Some(oblast_uid)
+            })
+            .cloned()
+            .collect()
+    }
+
     pub fn get_air_raid_alerts(&self) -> Vec<Alert> {
         self.get_alerts_by_alert_type(AlertType::AirRaid)
     }
@@ -142,6 +181,51 @@ impl Alerts {
     pub fn get_chemical_alerts(&self) -> Vec<Alert> {
         self.get_alerts_by_alert_type(AlertType::Chemical)
     }
+
+    /// One active alert per oblast, the highest by [`Alert::rank`]. Whole-oblast alerts win
+    /// over more severe partial ones, so the chosen alert agrees with oblast status derived
+    /// from all alerts; severity only decides between alerts of the same coverage
+    pub fn get_most_severe_alerts(&self) -> Vec<Alert> {
+        let mut by_oblast: Vec<Alert> = Vec::new();
+        for alert in self.alerts.iter().filter(|a| a.is_active()) {
+            let oblast_uid = alert.location_oblast_uid.unwrap_or(alert.location_uid);
+            match by_oblast
+                .iter_mut()
+                .find(|a| a.location_oblast_uid.unwrap_or(a.location_uid) == oblast_uid)
+            {
+                Some(chosen) if alert.rank() > chosen.rank() => *chosen = alert.clone(),
+                Some(_) => {}
+                None => by_oblast.push(alert.clone()),
+            }
+        }
+        by_oblast
+    }
+
+    /// Oblast statuses derived from active alerts: oblast-level alert makes the region
+    /// active ("A"), hromada-level alert only makes its parent oblast partly active ("P")
+    pub fn get_oblast_statuses(&self) -> AirRaidAlertOblastStatuses {
+        let statuses: String = REGIONS_DATA
+            .iter()
+            .map(|(_, location_uid, _, _)| {
+                let alerts = self.get_alerts_by_oblast_uid(*location_uid);
+                match AlertStatus::for_alerts(&alerts) {
+                    AlertStatus::A => 'A',
+                    AlertStatus::P => 'P',
+                    _ => 'N',
+                }
+            })
+            .collect();
+        AirRaidAlertOblastStatuses::new(statuses, Some(false))
+    }
+
+    /// Oblast statuses derived from active alerts of `alert_type` only, so regions
+    /// with only other alerts have no alert
+    pub fn get_oblast_statuses_by_alert_type(
+        &self,
+        alert_type: AlertType,
+    ) -> AirRaidAlertOblastStatuses {
+        Alerts::from(self.get_alerts_by_alert_type(alert_type)).get_oblast_statuses()
+    }
 }
 
 mod tests {
@@ -199,7 +283,7 @@ mod tests {
 
         assert_eq!(alerts.iter().next(), Some(alert1));
         assert_eq!(alerts.len(), 2);
-        assert_eq!(alerts.is_empty(), false);
+        assert!(!alerts.is_empty());
         assert_eq!(
             alerts.meta.get_last_updated_at().unix_timestamp(),
             1_714_989_765
@@ -245,4 +329,123 @@ mod tests {
         assert_eq!(expected_alert.len(), 1);
         assert_eq!(expected_alert[0].id, alert2.id);
     }
+
+    #[test]
+    fn test_alerts_from_vec() {
+        use super::*;
+        use crate::test_util::AlertBuilder;
+
+        let alert = AlertBuilder::new(16).with_notes("Some notes").build();
+
+        let alerts = Alerts::from(vec![alert.clone()]);
+
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts.get_alerts_by_location_uid(16), vec![alert]);
+    }
+
+    #[test]
+    fn test_get_oblast_statuses_mixed_levels() {
+        use super::*;
+        use crate::{test_util::AlertBuilder, AlertStatus};
+
+        let hromada = |oblast_uid: i32, location_uid: i32| {
+            AlertBuilder::new(oblast_uid)
+                .with_location(LocationType::Hromada, location_uid)
+                .build()
+        };
+        let alerts = Alerts::from(vec![
+            // Luhansk: oblast-level and hromada-level => active
+            AlertBuilder::new(16).build(),
+            hromada(16, 351),
+            // Kyiv Oblast: hromada-level only => partly active
+            hromada(14, 1234),
+            // Kyiv city
+            AlertBuilder::new(31)
+                .with_location(LocationType::City, 31)
+                .build(),
+        ]);
+
+        let statuses = alerts.get_oblast_statuses();
+        let status_of =
+            |uid: i32| statuses.get_by_location_uid(uid).unwrap().status().clone();
+
+        assert_eq!(statuses.len(), 27);
+        assert_eq!(status_of(16), AlertStatus::A);
+        assert_eq!(status_of(14), AlertStatus::P);
+        assert_eq!(status_of(31), AlertStatus::A);
+        assert_eq!(status_of(8), AlertStatus::N);
+        assert_eq!(statuses.get_active_alert_oblasts().len(), 2);
+    }
+
+    #[test]
+    fn test_get_oblast_statuses_by_alert_type() {
+        use super::*;
+        use crate::{test_util::AlertBuilder, AlertStatus};
+
+        let alerts = Alerts::from(vec![
+            AlertBuilder::new(31).build(),
+            AlertBuilder::new(16)
+                .with_alert_type(AlertType::ArtilleryShelling)
+                .build(),
+        ]);
+
+        let statuses = alerts.get_oblast_statuses_by_alert_type(AlertType::AirRaid);
+        let status_of =
+            |uid: i32| statuses.get_by_location_uid(uid).unwrap().status().clone();
+
+        assert_eq!(status_of(31), AlertStatus::A);
+        assert_eq!(status_of(16), AlertStatus::N);
+    }
+
+    #[test]
+    fn test_get_most_severe_alerts() {
+        use super::*;
+        use crate::test_util::AlertBuilder;
+        use time::macros::datetime;
+
+        let hromada = |id: i32, alert_type: AlertType, location_uid: i32| {
+            AlertBuilder::new(22)
+                .with_id(id)
+                .with_alert_type(alert_type)
+                .with_location(LocationType::Hromada, location_uid)
+        };
+        let hromada_alerts = Alerts::from(vec![
+            hromada(2, AlertType::ArtilleryShelling, 1293)
+                .with_updated_at(datetime!(2024-05-05 16:00:00 UTC))
+                .build(),
+            hromada(3, AlertType::AirRaid, 1293)
+                .with_updated_at(datetime!(2024-05-05 15:00:00 UTC))
+                .build(),
+            hromada(4, AlertType::AirRaid, 1294)
+                .with_updated_at(datetime!(2024-05-05 15:30:00 UTC))
+                .build(),
+        ]);
+
+        let chosen = hromada_alerts.get_most_severe_alerts();
+
+        // air raid wins over more recent artillery shelling, latest air raid is kept
+        assert_eq!(chosen.len(), 1);
+        assert_eq!(chosen[0].id, 4);
+        assert_eq!(chosen[0].alert_type, AlertType::AirRaid);
+
+        let mut alerts = hromada_alerts.clone();
+        alerts.append_page(Alerts::from(vec![AlertBuilder::new(22)
+            .with_id(1)
+            .with_alert_type(AlertType::ArtilleryShelling)
+            .with_updated_at(datetime!(2024-05-05 14:00:00 UTC))
+            .build()]));
+
+        let chosen = alerts.get_most_severe_alerts();
+
+        // whole-oblast alert wins over more severe hromada-level ones
+        assert_eq!(chosen.len(), 1);
+        assert_eq!(chosen[0].id, 1);
+        // chosen alert agrees with status derived from all alerts
+        for alerts in [hromada_alerts, alerts] {
+            let all = alerts.get_oblast_statuses();
+            let chosen =
+                Alerts::from(alerts.get_most_severe_alerts()).get_oblast_statuses();
+            assert_eq!(all, chosen);
+        }
+    }
 }
@@ -7,6 +7,8 @@ pub mod alerts;
 pub mod constants;
 pub mod error;
 pub mod location_type;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
 
 pub use air_raid_alert_oblast_status::*;
 pub use air_raid_alert_oblast_statuses::*;
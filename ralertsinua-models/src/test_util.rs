@@ -0,0 +1,88 @@
+//! Test data builders, available to dependent crates with `test-util` feature
+
+use crate::{Alert, AlertType, LocationType};
+use time::{macros::datetime, OffsetDateTime};
+
+/// Builder of [`Alert`], by default an active oblast-level air raid alert
+#[derive(Debug, Clone)]
+pub struct AlertBuilder {
+    alert: Alert,
+}
+
+impl AlertBuilder {
+    /// Alert covering the whole oblast `oblast_uid`, with the same `id`
+    pub fn new(oblast_uid: i32) -> Self {
+        let started_at = datetime!(2024-05-05 15:48:31 UTC);
+        Self {
+            alert: Alert {
+                id: oblast_uid,
+                location_title: String::new(),
+                location_type: LocationType::Oblast,
+                started_at,
+                updated_at: started_at,
+                finished_at: None,
+                alert_type: AlertType::AirRaid,
+                location_oblast: String::new(),
+                location_uid: oblast_uid,
+                location_oblast_uid: Some(oblast_uid),
+                notes: None,
+                country: None,
+                calculated: None,
+            },
+        }
+    }
+
+    pub fn with_id(mut self, id: i32) -> Self {
+        self.alert.id = id;
+        self
+    }
+
+    pub fn with_alert_type(mut self, alert_type: AlertType) -> Self {
+        self.alert.alert_type = alert_type;
+        self
+    }
+
+    /// Alert in location `location_uid` of `location_type` within the oblast,
+    /// e.g. [`LocationType::Hromada`] for alerts covering only a part of it
+    pub fn with_location(mut self, location_type: LocationType, location_uid: i32) -> Self {
+        self.alert.location_type = location_type;
+        self.alert.location_uid = location_uid;
+        self
+    }
+
+    pub fn with_oblast_uid(mut self, oblast_uid: Option<i32>) -> Self {
+        self.alert.location_oblast_uid = oblast_uid;
+        self
+    }
+
+    /// Both location and oblast title
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.alert.location_title = title.to_string();
+        self.alert.location_oblast = title.to_string();
+        self
+    }
+
+    pub fn with_notes(mut self, notes: &str) -> Self {
+        self.alert.notes = Some(notes.to_string());
+        self
+    }
+
+    pub fn with_started_at(mut self, started_at: OffsetDateTime) -> Self {
+        self.alert.started_at = started_at;
+        self
+    }
+
+    pub fn with_updated_at(mut self, updated_at: OffsetDateTime) -> Self {
+        self.alert.updated_at = updated_at;
+        self
+    }
+
+    pub fn with_finished_at(mut self, finished_at: OffsetDateTime) -> Self {
+        self.alert.finished_at = Some(finished_at);
+        self
+    }
+
+    pub fn build(self) -> Alert {
+        self.alert
+    }
+}
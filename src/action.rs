@@ -5,9 +5,12 @@ use serde::{
     Deserialize,
     Serialize,
 };
-use strum::{Display, VariantNames};
+use std::fmt;
+use strum::{AsRefStr, VariantNames};
+use time::OffsetDateTime;
 
-#[derive(Debug, Clone, PartialEq, Serialize, Display, VariantNames, Deserialize)]
+/// Serializable, so actions can be recorded to a log and replayed
+#[derive(Debug, Clone, PartialEq, Serialize, AsRefStr, VariantNames, Deserialize)]
 pub enum Action {
     Tick,
     Render,
@@ -16,17 +19,225 @@ pub enum Action {
     Resume,
     Quit,
     Refresh,
+    Reload,
     Error(String),
-    Help,
+    ToggleHelp,
+    ToggleFps,
     Online(bool),
     Locale,
+    NextTab,
+    PreviousTab,
     SelectTab(usize),
     SelectLocationByUid(Option<usize>),
+    SelectIndex(usize),
+    SelectNext,
+    SelectPrevious,
+    Unselect,
+    SelectTop,
+    SelectBottom,
+    NextActive,
+    PrevActive,
+    CycleAlertTypeFilter,
+    CycleSort,
+    CopySummary,
+    ZoomIn,
+    ZoomOut,
+    ZoomToSelected,
+    ZoomReset,
+    ToggleFullscreenMap,
+    PanLeft,
+    PanRight,
+    PanUp,
+    PanDown,
+    SearchStart,
+    SearchInput(char),
+    SearchSubmit,
+    SearchClear,
     FetchGeo,
-    GetLocations([Location; 27]),
+    GetLocations(Box<[Location; 27]>),
     GetBoundaries(CountryBoundary),
     FetchActiveAlerts,
     GetActiveAlerts(Alerts),
     FetchAirRaidAlertOblastStatuses,
     GetAirRaidAlertOblastStatuses(AirRaidAlertOblastStatuses),
+    /// Active locations count of last stored snapshots, oldest first
+    GetActiveCounts(Vec<u64>),
+    LastFetch(OffsetDateTime),
+}
+
+/// Variant name with payload, large payloads (e.g. geometries, alerts) are summarized
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = self.as_ref();
+        match self {
+            Action::Resize(width, height) => write!(f, "{}({}, {})", name, width, height),
+            Action::Error(message) => write!(f, "{}({})", name, message),
+            Action::Online(online) => write!(f, "{}({})", name, online),
+            Action::SelectTab(index) | Action::SelectIndex(index) => {
+                write!(f, "{}({})", name, index)
+            }
+            Action::SelectLocationByUid(uid) => write!(f, "{}({:?})", name, uid),
+            Action::SearchInput(c) => write!(f, "{}({:?})", name, c),
+            Action::GetLocations(locations) => {
+                write!(f, "{}({} locations)", name, locations.len())
+            }
+            Action::GetBoundaries(boundary) => {
+                write!(f, "{}({} points)", name, boundary.0.exterior().0.len())
+            }
+            Action::GetActiveAlerts(alerts) => {
+                write!(f, "{}({} alerts)", name, alerts.len())
+            }
+            Action::GetAirRaidAlertOblastStatuses(statuses) => {
+                write!(f, "{}({})", name, statuses.raw_data())
+            }
+            Action::GetActiveCounts(counts) => write!(f, "{}({:?})", name, counts),
+            Action::LastFetch(at) => write!(f, "{}({})", name, at),
+            Action::Tick
+            | Action::Render
+            | Action::Suspend
+            | Action::Resume
+            | Action::Quit
+            | Action::Refresh
+            | Action::Reload
+            | Action::ToggleHelp
+            | Action::ToggleFps
+            | Action::Locale
+            | Action::NextTab
+            | Action::PreviousTab
+            | Action::SelectNext
+            | Action::SelectPrevious
+            | Action::Unselect
+            | Action::SelectTop
+            | Action::SelectBottom
+            | Action::NextActive
+            | Action::PrevActive
+            | Action::CycleAlertTypeFilter
+            | Action::CycleSort
+            | Action::CopySummary
+            | Action::ZoomIn
+            | Action::ZoomOut
+            | Action::ZoomToSelected
+            | Action::ZoomReset
+            | Action::ToggleFullscreenMap
+            | Action::PanLeft
+            | Action::PanRight
+            | Action::PanUp
+            | Action::PanDown
+            | Action::SearchStart
+            | Action::SearchSubmit
+            | Action::SearchClear
+            | Action::FetchGeo
+            | Action::FetchActiveAlerts
+            | Action::FetchAirRaidAlertOblastStatuses => f.write_str(name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ralertsinua_geo::{AlertsInUaGeo, AlertsInUaGeoClient};
+    use time::macros::datetime;
+
+    /// One action of every variant, payloads included
+    fn all_actions() -> Vec<Action> {
+        let geo = AlertsInUaGeoClient::default();
+        let alerts: Alerts =
+            serde_json::from_str(crate::demo::DEMO_ALERTS_RESPONSE).unwrap();
+        vec![
+            Action::Tick,
+            Action::Render,
+            Action::Resize(80, 24),
+            Action::Suspend,
+            Action::Resume,
+            Action::Quit,
+            Action::Refresh,
+            Action::Reload,
+            Action::Error("failed to fetch".to_string()),
+            Action::ToggleHelp,
+            Action::ToggleFps,
+            Action::Online(false),
+            Action::Locale,
+            Action::NextTab,
+            Action::PreviousTab,
+            Action::SelectTab(2),
+            Action::SelectLocationByUid(Some(31)),
+            Action::SelectIndex(5),
+            Action::SelectNext,
+            Action::SelectPrevious,
+            Action::Unselect,
+            Action::SelectTop,
+            Action::SelectBottom,
+            Action::NextActive,
+            Action::PrevActive,
+            Action::CycleAlertTypeFilter,
+            Action::CycleSort,
+            Action::CopySummary,
+            Action::ZoomIn,
+            Action::ZoomOut,
+            Action::ZoomToSelected,
+            Action::ZoomReset,
+            Action::ToggleFullscreenMap,
+            Action::PanLeft,
+            Action::PanRight,
+            Action::PanUp,
+            Action::PanDown,
+            Action::SearchStart,
+            Action::SearchInput('К'),
+            Action::SearchSubmit,
+            Action::SearchClear,
+            Action::FetchGeo,
+            Action::GetLocations(Box::new(geo.locations())),
+            Action::GetBoundaries(geo.boundary()),
+            Action::FetchActiveAlerts,
+            Action::GetActiveAlerts(alerts),
+            Action::FetchAirRaidAlertOblastStatuses,
+            Action::GetAirRaidAlertOblastStatuses(AirRaidAlertOblastStatuses::new(
+                "ANNAANNANNNPANANANNNNAANNNN".to_string(),
+                Some(false),
+            )),
+            Action::GetActiveCounts(vec![0, 1, 3]),
+            Action::LastFetch(datetime!(2024-05-05 15:48:31 UTC)),
+        ]
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let actions = all_actions();
+        assert_eq!(actions.len(), Action::VARIANTS.len());
+
+        for action in actions {
+            let json = serde_json::to_string(&action).unwrap();
+            let restored: Action = serde_json::from_str(&json)
+                .unwrap_or_else(|e| panic!("{}: {}", action.as_ref(), e));
+            assert_eq!(restored, action);
+        }
+    }
+
+    #[test]
+    fn test_serde_keeps_payload() {
+        let json = serde_json::to_string(&Action::SelectIndex(5)).unwrap();
+        assert_eq!(json, r#"{"SelectIndex":5}"#);
+        let json = serde_json::to_string(&Action::Error("oops".to_string())).unwrap();
+        assert_eq!(json, r#"{"Error":"oops"}"#);
+    }
+
+    #[test]
+    fn test_display() {
+        let actions = all_actions();
+        let displayed: Vec<String> = actions.iter().map(Action::to_string).collect();
+
+        assert_eq!(displayed[0], "Tick");
+        assert!(displayed.contains(&"Resize(80, 24)".to_string()));
+        assert!(displayed.contains(&"Error(failed to fetch)".to_string()));
+        assert!(displayed.contains(&"SelectLocationByUid(Some(31))".to_string()));
+        assert!(displayed.contains(&"GetActiveAlerts(5 alerts)".to_string()));
+        assert!(displayed.contains(
+            &"GetAirRaidAlertOblastStatuses(ANNAANNANNNPANANANNNNAANNNN)".to_string()
+        ));
+        assert!(displayed.contains(&"GetActiveCounts([0, 1, 3])".to_string()));
+        assert!(
+            displayed.contains(&"LastFetch(2024-05-05 15:48:31.0 +00:00:00)".to_string())
+        );
+    }
 }
@@ -1,19 +1,34 @@
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::KeyEvent;
 #[allow(unused_imports)]
 use miette::{Context, WrapErr};
 use ralertsinua_geo::*;
 use ralertsinua_http::*;
 use ralertsinua_models::*;
-use ratatui::prelude::*;
-use std::sync::Arc;
+use ratatui::{
+    prelude::*,
+    widgets::{Clear, Paragraph, Wrap},
+};
+use rust_i18n::t;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use throbber_widgets_tui::{Throbber, ThrobberState, WhichUse, BRAILLE_SIX_DOUBLE};
+use time::OffsetDateTime;
 use tokio::{
     sync::mpsc::{self, UnboundedReceiver, UnboundedSender},
+    task::JoinHandle,
     time::{sleep, Duration},
 };
+use tokio_util::sync::CancellationToken;
 #[allow(unused)]
 use tracing::{debug, error, trace};
 
-use crate::{action::*, components::*, config::*, error::*, layout::*, tui};
+use crate::{
+    action::*, clipboard::*, components::*, config::*, constants::*, data::*, error::*,
+    keybindings::*, layout::*, mode::*, notifications::*, recording::*, state::*, theme::*,
+    tui, tui_helpers::*,
+};
 
 type Result<T> = miette::Result<T, AppError>;
 
@@ -29,28 +44,221 @@ pub struct App {
     pub should_quit: bool,
     pub should_suspend: bool,
     pub selected_tab: LayoutTab,
+    pub mode: Mode,
+    pub keybindings: KeyBindings,
+    /// Map is drawn over whole terminal, other components except popups are hidden
+    pub fullscreen_map: bool,
+    /// Fetch is dispatched and not completed yet, spinner is shown meanwhile
+    pub loading: bool,
+    spinner_state: ThrobberState,
     pub last_tick_key_events: Vec<KeyEvent>,
+    /// Time between periodic fetches, separate from tick rate
+    pub poll_interval: Duration,
+    /// Cancels background tasks (e.g. periodic fetch) on quit
+    pub cancellation_token: CancellationToken,
+    /// Config file the theme, keybindings and layout are loaded from, toggled locale is saved to
+    pub config_path: PathBuf,
+    /// UI state persisted across restarts
+    pub state: State,
+    /// File where `state` is loaded from and saved to on shutdown
+    pub state_path: PathBuf,
+    /// Storage of fetched statuses, used for offline startup
+    pub data_repository: Box<dyn DataSource>,
+    /// Time of last successful statuses fetch
+    pub last_fetch: Option<OffsetDateTime>,
+    /// Previous statuses string, compared with fetched one to find newly alerted regions
+    pub last_statuses: String,
+    /// Terminal bell for newly alerted regions, with cooldown
+    pub bell: Bell,
+    /// Appends dispatched actions to a file, see `--record`
+    pub recorder: Option<Recorder>,
+    /// Actions fed into the loop instead of live events, see `--replay`
+    pub replay: Option<Vec<RecordedAction>>,
+    /// While replaying, receives actions the app and components dispatch on their own,
+    /// these are discarded as the recording already contains them
+    replay_sink: Option<UnboundedReceiver<Action>>,
+}
+
+/// Spawn a task sending fetch actions every `interval`, until `cancellation_token` is cancelled
+pub fn spawn_periodic_fetch(
+    action_tx: UnboundedSender<Action>,
+    interval: Duration,
+    cancellation_token: CancellationToken,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = cancellation_token.cancelled() => break,
+                _ = sleep(interval) => {
+                    let _ = action_tx.send(Action::FetchAirRaidAlertOblastStatuses);
+                    let _ = action_tx.send(Action::FetchActiveAlerts);
+                }
+            }
+        }
+    })
+}
+
+/// Spawn a task sending quit action on SIGTERM or SIGINT, so that terminal is restored
+/// even when keys are not read, until `cancellation_token` is cancelled
+pub fn spawn_signal_handler(
+    action_tx: UnboundedSender<Action>,
+    cancellation_token: CancellationToken,
+) -> Result<JoinHandle<()>> {
+    #[cfg(not(windows))]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        // registered before spawning, so that no signal is missed
+        let mut terminate = signal(SignalKind::terminate())?;
+        let mut interrupt = signal(SignalKind::interrupt())?;
+        Ok(tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = cancellation_token.cancelled() => break,
+                    _ = terminate.recv() => {}
+                    _ = interrupt.recv() => {}
+                }
+                debug!(target: "app", "termination signal received, quit");
+                let _ = action_tx.send(Action::Quit);
+            }
+        }))
+    }
+    #[cfg(windows)]
+    Ok(tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = cancellation_token.cancelled() => break,
+                _ = tokio::signal::ctrl_c() => {}
+            }
+            debug!(target: "app", "termination signal received, quit");
+            let _ = action_tx.send(Action::Quit);
+        }
+    }))
+}
+
+/// Geo client reading boundary and locations from files in `config`, bundled ones otherwise
+pub fn load_geo_client(config: &Config) -> miette::Result<AlertsInUaGeoClient, GeoError> {
+    let path_of = |path: &str| (!path.is_empty()).then(|| PathBuf::from(path));
+    AlertsInUaGeoClient::from_paths(
+        path_of(config.boundary_path()).as_deref(),
+        path_of(config.locations_path()).as_deref(),
+    )
+}
+
+/// Window title with count of locations with active alert, e.g. "alertsinua — 5 active"
+pub fn terminal_title(active: usize) -> String {
+    format!(
+        "alertsinua — {}",
+        t!("messages.title_active", count = active)
+    )
+}
+
+/// Centered message asking to enlarge terminal, drawn instead of layout
+fn draw_terminal_too_small(f: &mut Frame<'_>) {
+    let (width, height) = MIN_TERMINAL_SIZE;
+    let message = t!(
+        "messages.terminal_too_small",
+        width = width,
+        height = height
+    );
+    let area = f.size();
+    // a few rows, so that message wraps on narrow terminals
+    let height = area.height.min(3);
+    let area = Rect::new(
+        area.x,
+        area.y + (area.height - height) / 2,
+        area.width,
+        height,
+    );
+    f.render_widget(
+        Paragraph::new(message.to_string())
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true }),
+        area,
+    );
+}
+
+/// Persist current locale to config file at `path`, keeping other file settings
+fn save_locale(config: &Config, path: &Path) -> miette::Result<()> {
+    let mut file_settings = FileSettings::load(path)?;
+    file_settings.locale = Some(config.get_locale().to_string());
+    file_settings.save(path)
 }
 
 impl App {
+    /// Create app storing fetched statuses in file at `config.db_path()`
     pub fn new(
         config: Config,
         api_client: Arc<dyn AlertsInUaApi>,
         geo_client: Arc<dyn AlertsInUaGeo>,
+        config_path: PathBuf,
+        state_path: PathBuf,
+    ) -> Result<Self> {
+        let db_path = match config.db_path() {
+            "" => DataRepository::default_path(),
+            path => PathBuf::from(path),
+        };
+        let data_repository = DataRepository::open(db_path)?;
+        Self::with_data_source(
+            config,
+            api_client,
+            geo_client,
+            config_path,
+            state_path,
+            Box::new(data_repository),
+        )
+    }
+
+    /// Create app storing fetched statuses in `data_repository`, with theme, keybindings
+    /// and layout loaded from config file at `config_path` and UI state kept at `state_path`
+    pub fn with_data_source(
+        config: Config,
+        api_client: Arc<dyn AlertsInUaApi>,
+        geo_client: Arc<dyn AlertsInUaGeo>,
+        config_path: PathBuf,
+        state_path: PathBuf,
+        data_repository: Box<dyn DataSource>,
     ) -> Result<Self> {
         let (action_tx, action_rx) = mpsc::unbounded_channel();
+        let file_settings = FileSettings::load(&config_path).unwrap_or_else(|e| {
+            error!(target: "app", "failed to load config file: {}", e);
+            FileSettings::default()
+        });
+        let theme = if *config.no_color() {
+            Theme::no_color()
+        } else {
+            Theme::from(&file_settings.theme)
+        };
+        let keybindings = KeyBindings::from_settings(&file_settings.keybindings)?;
+        set_layout_settings(file_settings.layout);
+        let poll_interval = Duration::from_secs(*config.polling_interval());
         let header = Header::new();
+        let status_bar = StatusBar::new();
         let map = Map::new();
         let list = LocationsList::new();
+        let details = LocationDetails::new().with_area(layout_settings().details_area());
+        let sparkline = AlertsSparkline::new();
+        let legend = Legend::new();
+        let toast = Toast::new();
         let fps = FpsCounter::new();
         let logger = Logger::new();
-        let components: Vec<Box<dyn Component<'static>>> = vec![
+        let help = Help::new().with_keybindings(keybindings.clone());
+        let mut components: Vec<Box<dyn Component<'static>>> = vec![
             Box::new(header),
+            Box::new(status_bar),
             Box::new(map),
             Box::new(list),
+            Box::new(details),
+            Box::new(sparkline),
+            Box::new(legend),
+            Box::new(toast),
             Box::new(fps),
             Box::new(logger),
+            // last, to be drawn over other components
+            Box::new(help),
         ];
+        for component in components.iter_mut() {
+            component.register_theme_handler(theme)?;
+        }
         Ok(Self {
             action_tx,
             action_rx,
@@ -61,18 +269,329 @@ impl App {
             should_quit: false,
             should_suspend: false,
             selected_tab: LayoutTab::default(),
+            mode: Mode::default(),
+            keybindings,
+            fullscreen_map: false,
+            loading: false,
+            spinner_state: ThrobberState::default(),
             last_tick_key_events: Vec::new(),
+            poll_interval,
+            cancellation_token: CancellationToken::new(),
+            config_path,
+            state: State::default(),
+            state_path,
+            data_repository,
+            last_fetch: None,
+            last_statuses: String::new(),
+            bell: Bell::default(),
+            recorder: None,
+            replay: None,
+            replay_sink: None,
         })
     }
 
     pub async fn init(&mut self) -> Result<()> {
+        self.state = State::load(&self.state_path);
+        if self.state.selected.is_some() {
+            debug!(target:"app", "restore selected location: {:?}", self.state.selected);
+            self.action_tx
+                .send(Action::SelectLocationByUid(self.state.selected))?;
+        }
         self.action_tx.send(Action::FetchGeo)?;
+        if let Err(e) = self.data_repository.migrate() {
+            error!(target: "app", "failed to migrate statuses storage: {}", e);
+        }
+        // display last known statuses before network call completes
+        match self.data_repository.fetch_last_known_alerts_string() {
+            Ok(Some(data)) => {
+                debug!(target:"app", "restore last known statuses: {}", data);
+                self.last_statuses.clone_from(&data);
+                self.action_tx.send(Action::GetAirRaidAlertOblastStatuses(
+                    AirRaidAlertOblastStatuses::new(data, Some(false)),
+                ))?;
+            }
+            Ok(None) => {}
+            Err(e) => error!(target: "app", "failed to read last known statuses: {}", e),
+        }
+        self.send_active_counts()?;
         self.action_tx
             .send(Action::FetchAirRaidAlertOblastStatuses)?;
         self.action_tx.send(Action::FetchActiveAlerts)?;
+        // nothing is fetched while replaying
+        self.loading = !self.is_replaying();
+        Ok(())
+    }
+
+    /// Re-read geo data from disk and send it to components, current data is kept on failure
+    pub fn reload_geo(&mut self) -> Result<()> {
+        match load_geo_client(&self.config) {
+            Ok(geo_client) => {
+                debug!(target:"app", "reloaded geo data");
+                self.geo_client = Arc::new(geo_client);
+                self.action_tx.send(Action::FetchGeo)?;
+            }
+            Err(e) => {
+                error!(target: "app", "failed to reload geo data: {}", e);
+                self.action_tx
+                    .send(Action::Error(format!("failed to reload geo data: {}", e)))?;
+            }
+        }
         Ok(())
     }
 
+    /// Report failed fetch, so that loop continues with last known data
+    fn report_fetch_error(&self, e: &ApiError) -> Result<()> {
+        error!(target: "app", "error from API catched, possibly offline: {}", e);
+        self.action_tx.send(Action::Error(e.to_string()))?;
+        self.action_tx.send(Action::Online(false))?;
+        Ok(())
+    }
+
+    /// Fetch active alerts, keeping last known alerts on failure
+    pub async fn fetch_active_alerts(&mut self) -> Result<()> {
+        match self.api_client.get_active_alerts().await {
+            Ok(response) => {
+                debug!(target:"app", "get_active_alerts: total {} alerts", response.len());
+                self.action_tx.send(Action::GetActiveAlerts(response))?;
+            }
+            Err(e) => self.report_fetch_error(&e)?,
+        }
+        Ok(())
+    }
+
+    /// Fetch statuses, keeping last known statuses on failure
+    pub async fn fetch_air_raid_alert_oblast_statuses(&mut self) -> Result<()> {
+        let response = match self
+            .api_client
+            .get_air_raid_alert_statuses_by_location()
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => {
+                self.report_fetch_error(&e)?;
+                if self.last_statuses.is_empty() {
+                    // nothing known yet, display all locations as offline
+                    self.action_tx.send(Action::GetAirRaidAlertOblastStatuses(
+                        AirRaidAlertOblastStatuses::default(),
+                    ))?;
+                }
+                return Ok(());
+            }
+        };
+        trace!(target: "app", "get_air_raid_alert_statuses_by_location: {}", response.raw_data());
+        if let Err(e) = self
+            .data_repository
+            .insert_alerts_string(response.raw_data())
+        {
+            error!(target: "app", "failed to store statuses: {}", e);
+        }
+        self.send_active_counts()?;
+        let activated = newly_activated(&self.last_statuses, response.raw_data());
+        if *self.config.notifications() && !activated.is_empty() {
+            notify_desktop(&activated, self.config.get_locale().as_str());
+        }
+        if *self.config.bell() && !activated.is_empty() {
+            self.bell.ring();
+        }
+        self.last_statuses = response.raw_data().to_string();
+        let last_fetch = OffsetDateTime::now_utc();
+        self.last_fetch = Some(last_fetch);
+        self.action_tx.send(Action::LastFetch(last_fetch))?;
+        debug!(target:"app", "get_air_raid_alert_statuses_by_location: total {} alerts", response.len());
+        self.action_tx
+            .send(Action::GetAirRaidAlertOblastStatuses(response))?;
+        Ok(())
+    }
+
+    /// Send active counts of stored snapshots to sparkline, failure to read them is logged only
+    fn send_active_counts(&self) -> Result<()> {
+        match self
+            .data_repository
+            .fetch_active_counts(SPARKLINE_SNAPSHOTS)
+        {
+            Ok(counts) => self.action_tx.send(Action::GetActiveCounts(counts))?,
+            Err(e) => error!(target: "app", "failed to read active counts: {}", e),
+        }
+        Ok(())
+    }
+
+    /// Copy summary of last fetched statuses to clipboard, failure is shown as error toast
+    fn copy_summary(&self) -> Result<()> {
+        let statuses = if self.last_statuses.is_empty() {
+            AirRaidAlertOblastStatuses::default()
+        } else {
+            AirRaidAlertOblastStatuses::new(self.last_statuses.clone(), Some(false))
+        };
+        let locale = self.config.get_locale();
+        let summary = alerts_summary(&statuses, locale.as_str(), self.last_fetch);
+        if let Err(e) = copy_to_clipboard(&summary) {
+            error!(target: "app", "{}", e);
+            self.action_tx.send(Action::Error(e))?;
+        }
+        Ok(())
+    }
+
+    /// Switch to replay mode: actions dispatched by the app and components are discarded
+    /// from now on, returns sender of actions that are still handled, e.g. replayed ones
+    pub fn start_replay(&mut self) -> UnboundedSender<Action> {
+        let (sink_tx, sink_rx) = mpsc::unbounded_channel();
+        self.replay_sink = Some(sink_rx);
+        std::mem::replace(&mut self.action_tx, sink_tx)
+    }
+
+    pub fn is_replaying(&self) -> bool {
+        self.replay_sink.is_some()
+    }
+
+    /// Draw components visible on selected tab. In fullscreen map mode the map takes the whole
+    /// frame and only popups are drawn over it
+    pub fn draw(&mut self, f: &mut Frame<'_>) {
+        let size = f.size();
+        let (min_width, min_height) = MIN_TERMINAL_SIZE;
+        if size.width < min_width || size.height < min_height {
+            draw_terminal_too_small(f);
+            return;
+        }
+        let selected_tab = *self.selected_tab();
+        let fullscreen = self.fullscreen_map && selected_tab == LayoutTab::Tab1;
+        for component in self
+            .components
+            .iter_mut()
+            .filter(|c| c.is_visible(&selected_tab))
+        {
+            let LayoutPoint(area, _) = component.placement();
+            let r = match area {
+                LayoutArea::Left if fullscreen => component.draw_in(f, size),
+                LayoutArea::Hidden => component.draw(f),
+                _ if fullscreen => continue,
+                _ => component.draw(f),
+            };
+            if let Err(e) = r {
+                self.action_tx
+                    .send(Action::Error(format!("component failed to draw: {:?}", e)))
+                    .unwrap();
+            }
+        }
+        if self.loading && !fullscreen {
+            self.draw_spinner(f);
+        }
+    }
+
+    /// Spinner with "Loading" label over the status bar
+    fn draw_spinner(&mut self, f: &mut Frame<'_>) {
+        let area = get_component_area(
+            get_terminal_area_max_height(f.size(), 30),
+            "Spinner",
+            LayoutArea::Title,
+            layout_settings(),
+        );
+        let spinner = Throbber::default()
+            .label(t!("messages.loading").to_string())
+            .throbber_set(BRAILLE_SIX_DOUBLE)
+            .use_type(WhichUse::Spin);
+        f.render_widget(Clear, area);
+        f.render_stateful_widget(spinner, area, &mut self.spinner_state);
+    }
+
+    /// Update app and components with `action`. Side effects (e.g. fetch) are skipped
+    /// while replaying, their resulting actions are replayed from the recording instead
+    pub async fn handle_action(&mut self, action: Action) -> Result<()> {
+        if action != Action::Tick && action != Action::Render {
+            debug!(target:"app_events", "received action: {}", action.to_string());
+        }
+        if let Some(recorder) = self.recorder.as_mut() {
+            if let Err(e) = recorder.record(&action) {
+                error!(target: "app", "failed to record action: {}", e);
+            }
+        }
+        let replaying = self.is_replaying();
+        self.mode = self.mode.next(&action);
+        match action.clone() {
+            Action::Tick => {
+                self.last_tick_key_events.drain(..);
+                if self.loading {
+                    self.spinner_state.calc_next();
+                }
+            }
+            Action::Quit => {
+                self.should_quit = true;
+            }
+            Action::SelectLocationByUid(selected) => {
+                self.state.selected = selected;
+            }
+            Action::SelectTab(index) => {
+                self.selected_tab = LayoutTab::from_repr(index).unwrap_or_default();
+            }
+            Action::NextTab => {
+                self.next_tab();
+                self.action_tx
+                    .send(Action::SelectTab(self.selected_tab as usize))?;
+            }
+            Action::PreviousTab => {
+                self.previous_tab();
+                self.action_tx
+                    .send(Action::SelectTab(self.selected_tab as usize))?;
+            }
+            Action::ToggleFullscreenMap => self.fullscreen_map = !self.fullscreen_map,
+            Action::Suspend => self.should_suspend = true,
+            Action::Resume => self.should_suspend = false,
+            Action::Locale => {
+                self.config.toggle_locale();
+                if replaying {
+                    // replayed session must not change saved settings
+                } else if let Err(e) = save_locale(&self.config, &self.config_path) {
+                    error!(target: "app", "failed to save locale: {}", e);
+                }
+                self.action_tx.send(Action::Refresh)?;
+            }
+            Action::CopySummary if !replaying => self.copy_summary()?,
+            Action::Reload if !replaying => self.reload_geo()?,
+            Action::FetchGeo if !replaying => {
+                let boundary = self.geo_client.boundary();
+                let locations = self.geo_client.locations();
+                debug!(target:"app", "fetch geo: total {} alerts", locations.len());
+                self.action_tx.send(Action::GetBoundaries(boundary))?;
+                self.action_tx
+                    .send(Action::GetLocations(Box::new(locations)))?;
+            }
+            Action::FetchActiveAlerts if !replaying => {
+                self.loading = true;
+                self.fetch_active_alerts().await?;
+                self.loading = false;
+            }
+            Action::FetchAirRaidAlertOblastStatuses if !replaying => {
+                self.loading = true;
+                self.fetch_air_raid_alert_oblast_statuses().await?;
+                self.loading = false;
+            }
+            _ => {}
+        }
+        for component in self.components.iter_mut() {
+            if let Some(action) = component.update(action.clone())? {
+                self.action_tx.send(action)?
+            };
+        }
+        if let Some(sink) = self.replay_sink.as_mut() {
+            while sink.try_recv().is_ok() {}
+        }
+        Ok(())
+    }
+
+    /// Stop background tasks, flush stored statuses and save state.
+    /// Failures are logged only, so that terminal is restored anyway
+    pub async fn shutdown(&mut self) {
+        self.cancellation_token.cancel();
+        if let Err(e) = self.data_repository.flush() {
+            error!(target: "app", "failed to flush statuses storage: {}", e);
+        }
+        if self.is_replaying() {
+            // replayed session must not replace saved state
+        } else if let Err(e) = self.state.save(&self.state_path) {
+            error!(target: "app", "failed to save state: {}", e);
+        }
+        debug!(target:"app", "shutdown complete");
+    }
+
     pub fn next_tab(&mut self) {
         self.selected_tab = self.selected_tab.next();
     }
@@ -89,26 +608,44 @@ impl App {
         let periodic_action_tx = self.action_tx.clone();
         let mut tui = tui::Tui::new()?
             .tick_rate(self.config.tick_rate())
-            .frame_rate(self.config.frame_rate());
-        // tui.mouse(true);
+            .frame_rate(self.config.frame_rate())
+            .mouse(true);
         tui.enter()?;
 
+        // ---------------------------------------------------------------------
+        // REPLAY
+        // ---------------------------------------------------------------------
+        // live events are still needed to draw and quit, other actions come from recording
+        let live_tx = self.action_tx.clone();
+        let replay = match self.replay.take() {
+            Some(recording) => {
+                debug!(target:"app", "replay {} recorded actions", recording.len());
+                self.start_replay();
+                Some(spawn_replay(
+                    recording,
+                    live_tx.clone(),
+                    self.cancellation_token.clone(),
+                ))
+            }
+            None => None,
+        };
+
         // ---------------------------------------------------------------------
         self.init().await?;
 
         // ---------------------------------------------------------------------
-        // EXAMPLE PERIODIC
+        // PERIODIC FETCH
         // ---------------------------------------------------------------------
-        // dispatch fetch action after 2 seconds
-        let interval = *self.config.polling_interval();
-        debug!(target:"app", "init periodic fetch action every {} seconds", interval);
-        tokio::spawn(async move {
-            loop {
-                sleep(Duration::from_secs(interval)).await;
-                let _ = periodic_action_tx.send(Action::FetchAirRaidAlertOblastStatuses);
-                let _ = periodic_action_tx.send(Action::FetchActiveAlerts);
-            }
-        });
+        // dispatch fetch actions every poll interval, first one after the interval
+        debug!(target:"app", "init periodic fetch action every {:?}", self.poll_interval);
+        let periodic_fetch = spawn_periodic_fetch(
+            periodic_action_tx,
+            self.poll_interval,
+            self.cancellation_token.clone(),
+        );
+
+        let signal_handler =
+            spawn_signal_handler(live_tx.clone(), self.cancellation_token.clone())?;
 
         for component in self.components.iter_mut() {
             component.register_action_handler(self.action_tx.clone())?;
@@ -124,157 +661,463 @@ impl App {
 
         loop {
             if let Some(e) = tui.next().await {
+                // in search and help modes keys are not passed to components
+                let is_modal_input = matches!(e, tui::Event::Key(_))
+                    && matches!(self.mode, Mode::Search | Mode::Help);
+                let replaying = self.is_replaying();
                 match e {
-                    tui::Event::Quit => self.action_tx.send(Action::Quit)?,
-                    tui::Event::Tick => self.action_tx.send(Action::Tick)?,
-                    tui::Event::Render => self.action_tx.send(Action::Render)?,
-                    tui::Event::Resize(x, y) => {
-                        self.action_tx.send(Action::Resize(x, y))?
-                    }
-                    tui::Event::Key(key_event) => match key_event.code {
-                        KeyCode::Char('q') => {
-                            self.action_tx.send(Action::Quit)?;
-                        }
-                        KeyCode::Char('c') | KeyCode::Char('C') => {
-                            if key_event.modifiers == KeyModifiers::CONTROL {
-                                self.action_tx.send(Action::Quit)?;
-                            }
-                        }
-                        KeyCode::Right => {
-                            self.next_tab();
-                            self.action_tx
-                                .send(Action::SelectTab(self.selected_tab as usize))?;
-                        }
-                        KeyCode::Left => {
-                            self.previous_tab();
-                            self.action_tx
-                                .send(Action::SelectTab(self.selected_tab as usize))?;
-                        }
-                        KeyCode::Char('u') => {
-                            // self.action_tx.send(Action::Fetch)?;
-                        }
-                        KeyCode::Char('l') => {
-                            self.action_tx.send(Action::Locale)?;
-                        }
-                        KeyCode::Char('r') => {
-                            self.action_tx.send(Action::Refresh)?;
+                    tui::Event::Quit => live_tx.send(Action::Quit)?,
+                    tui::Event::Tick => live_tx.send(Action::Tick)?,
+                    tui::Event::Render => live_tx.send(Action::Render)?,
+                    tui::Event::Resize(x, y) => live_tx.send(Action::Resize(x, y))?,
+                    // only quitting is possible while replaying
+                    tui::Event::Key(key_event) if replaying => {
+                        let action = handle_key(Mode::Map, key_event, &self.keybindings);
+                        if action == Some(Action::Quit) {
+                            live_tx.send(Action::Quit)?;
                         }
-                        KeyCode::Char('z') => {
-                            self.action_tx.send(Action::Suspend)?;
+                    }
+                    tui::Event::Key(key_event) => {
+                        if let Some(action) =
+                            handle_key(self.mode, key_event, &self.keybindings)
+                        {
+                            self.action_tx.send(action)?;
                         }
-                        _ => {}
-                    },
+                    }
                     _ => {}
                 }
-                for component in self.components.iter_mut() {
-                    if let Some(action) = component.handle_events(Some(e.clone()))? {
-                        self.action_tx.send(action)?;
+                if !is_modal_input && !replaying {
+                    for component in self.components.iter_mut() {
+                        if let Some(action) = component.handle_events(Some(e.clone()))? {
+                            self.action_tx.send(action)?;
+                        }
                     }
                 }
             }
 
             while let Ok(action) = self.action_rx.try_recv() {
-                if action != Action::Tick && action != Action::Render {
-                    debug!(target:"app_events", "received action: {}", action.to_string());
-                }
-                match action.clone() {
-                    Action::Tick => {
-                        self.last_tick_key_events.drain(..);
-                    }
-                    Action::Quit => self.should_quit = true,
-                    Action::Suspend => self.should_suspend = true,
-                    Action::Resume => self.should_suspend = false,
-                    Action::Locale => {
-                        self.config.toggle_locale();
-                        self.action_tx.send(Action::Refresh)?;
-                    }
+                match action {
                     Action::Resize(w, h) => {
                         tui.resize(Rect::new(0, 0, w, h))?;
-                        // FIXME
-                        /* tui.draw(|f| {
-                            for component in self.components.iter_mut() {
-                                let r = component.draw(f, &f.size());
-                                if let Err(e) = r {
-                                    self.action_tx
-                                        .send(Action::Error(format!("Failed to draw: {:?}", e)))
-                                        .unwrap();
-                                }
-                            }
-                        })?; */
+                        // redraw at once, e.g. to replace too small terminal message
+                        tui.draw(|f| self.draw(f))?;
                     }
                     Action::Render => {
-                        tui.draw(|f| {
-                            let selected_tab = *self.selected_tab();
-                            self.components
-                                .iter_mut()
-                                .filter(|c| c.is_visible(&selected_tab))
-                                .for_each(|component| {
-                                    let r = component.draw(f);
-                                    if let Err(e) = r {
-                                        self.action_tx
-                                            .send(Action::Error(format!(
-                                                "component failed to draw: {:?}",
-                                                e
-                                            )))
-                                            .unwrap();
-                                    }
-                                });
-                        })?;
-                    }
-                    Action::FetchGeo => {
-                        let boundary = self.geo_client.boundary();
-                        let locations = self.geo_client.locations();
-                        debug!(target:"app", "fetch geo: total {} alerts", locations.len());
-                        self.action_tx.send(Action::GetBoundaries(boundary))?;
-                        self.action_tx.send(Action::GetLocations(locations))?;
+                        tui.draw(|f| self.draw(f))?;
                     }
-                    Action::FetchActiveAlerts => {
-                        let response: Alerts = self.api_client.get_active_alerts().await?;
-                        debug!(target:"app", "get_active_alerts: total {} alerts", response.len());
-                        self.action_tx.send(Action::GetActiveAlerts(response))?;
+                    Action::GetAirRaidAlertOblastStatuses(ref statuses) => {
+                        let title = terminal_title(statuses.active_alerts_count());
+                        if let Err(e) = tui.set_title(&title) {
+                            error!(target: "app", "failed to set terminal title: {}", e);
+                        }
                     }
-                    Action::FetchAirRaidAlertOblastStatuses => {
-                        #[allow(clippy::bind_instead_of_map)]
-                        let response: AirRaidAlertOblastStatuses = self
-                            .api_client
-                            .get_air_raid_alert_statuses_by_location()
-                            .await
-                            .and_then(|r| {
-                                trace!(target: "app", "get_air_raid_alert_statuses_by_location: {}", r.raw_data());
-                                Ok(r)
-                            })
-                            .inspect_err(|e| {
-                                error!(target: "app", "error from API catched, possibly offline");
-                                let _ = self.action_tx.send( Action::Error(e.to_string()));
-                                let _ = self.action_tx.send( Action::Online(false));
-                            })
-                            .unwrap_or_default();
-                        debug!(target:"app", "get_air_raid_alert_statuses_by_location: total {} alerts", response.len());
-                        self.action_tx
-                            .send(Action::GetAirRaidAlertOblastStatuses(response))?;
+                    // fetch blocks the loop, show spinner before it starts
+                    Action::FetchActiveAlerts | Action::FetchAirRaidAlertOblastStatuses
+                        if !self.is_replaying() =>
+                    {
+                        self.loading = true;
+                        tui.draw(|f| self.draw(f))?;
                     }
                     _ => {}
                 }
-                for component in self.components.iter_mut() {
-                    if let Some(action) = component.update(action.clone())? {
-                        self.action_tx.send(action)?
-                    };
-                }
+                self.handle_action(action).await?;
             }
             if self.should_suspend {
                 tui.suspend()?;
                 self.action_tx.send(Action::Resume)?;
                 tui = tui::Tui::new()?
                     .tick_rate(self.config.tick_rate())
-                    .frame_rate(self.config.frame_rate());
-                // tui.mouse(true);
+                    .frame_rate(self.config.frame_rate())
+                    .mouse(true);
                 tui.enter()?;
             } else if self.should_quit {
                 tui.stop()?;
                 break;
             }
         }
+        self.shutdown().await;
         tui.exit()?;
+        let _ = periodic_fetch.await;
+        let _ = signal_handler.await;
+        if let Some(replay) = replay {
+            let _ = replay.await;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    #[derive(Debug)]
+    struct FailingApi;
+
+    #[async_trait::async_trait]
+    impl AlertsInUaApi for FailingApi {
+        async fn get_active_alerts(&self) -> miette::Result<Alerts, ApiError> {
+            Err(ApiError::InternalServerError)
+        }
+
+        async fn get_alerts_history(
+            &self,
+            _location_aid: &i8,
+            _period: &str,
+        ) -> miette::Result<Alerts, ApiError> {
+            Err(ApiError::InternalServerError)
+        }
+
+        async fn get_air_raid_alert_status(
+            &self,
+            _location_aid: &i8,
+        ) -> miette::Result<String, ApiError> {
+            Err(ApiError::InternalServerError)
+        }
+
+        async fn get_air_raid_alert_statuses_by_location(
+            &self,
+        ) -> miette::Result<AirRaidAlertOblastStatuses, ApiError> {
+            Err(ApiError::InternalServerError)
+        }
+    }
+
+    /// Config file that doesn't exist, so tests run with default settings
+    fn missing_config_path() -> PathBuf {
+        std::env::temp_dir().join("ralertsinua_test_app_missing_config.toml")
+    }
+
+    /// State file that is never saved, as these apps are not shut down
+    fn unused_state_path() -> PathBuf {
+        std::env::temp_dir().join("ralertsinua_test_app_state.json")
+    }
+
+    fn failing_app(data_source: MemoryDataSource) -> App {
+        let geo_client = Arc::new(AlertsInUaGeoClient::default());
+        App::with_data_source(
+            Config::default(),
+            Arc::new(FailingApi),
+            geo_client,
+            missing_config_path(),
+            unused_state_path(),
+            Box::new(data_source),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_with_data_source_loads_config_path() -> Result<()> {
+        let path = std::env::temp_dir().join("ralertsinua_test_app_config.toml");
+        std::fs::write(&path, "[keybindings]\nRefresh = \"u\"\n")?;
+        let app = App::with_data_source(
+            Config::default(),
+            Arc::new(FailingApi),
+            Arc::new(AlertsInUaGeoClient::default()),
+            path.clone(),
+            unused_state_path(),
+            Box::new(MemoryDataSource::default()),
+        )?;
+
+        let key = KeyEvent::new(KeyCode::Char('u'), KeyModifiers::NONE);
+        assert_eq!(app.keybindings.get(&key), Some(&Action::Refresh));
+        assert_eq!(app.config_path, path);
+        Ok(())
+    }
+
+    #[test]
+    fn test_save_locale_keeps_other_settings() -> miette::Result<()> {
+        let path = std::env::temp_dir().join("ralertsinua_test_save_locale.toml");
+        std::fs::write(&path, "base_url = \"http://localhost\"\n").unwrap();
+        let config = Config::default();
+
+        save_locale(&config, &path)?;
+
+        let saved = FileSettings::load(&path)?;
+        assert_eq!(saved.locale, Some(config.get_locale().to_string()));
+        assert_eq!(saved.base_url.as_deref(), Some("http://localhost"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_init_restores_last_known_statuses() -> Result<()> {
+        let last_known = "ANNNNNNNNNNNNNNNNNNNNNNNNNN".to_string();
+        let mut app = failing_app(MemoryDataSource::new(vec![last_known.clone()]));
+
+        app.init().await?;
+
+        let mut actions = Vec::new();
+        while let Ok(action) = app.action_rx.try_recv() {
+            actions.push(action);
+        }
+        assert_eq!(app.last_statuses, last_known);
+        assert!(actions.contains(&Action::GetAirRaidAlertOblastStatuses(
+            AirRaidAlertOblastStatuses::new(last_known, Some(false))
+        )));
+        assert!(actions.contains(&Action::FetchAirRaidAlertOblastStatuses));
+        Ok(())
+    }
+
+    /// Handle `actions` and everything they dispatch, as the loop does
+    async fn handle_all(app: &mut App, actions: Vec<Action>) -> Result<()> {
+        for action in actions {
+            app.handle_action(action).await?;
+            while let Ok(action) = app.action_rx.try_recv() {
+                app.handle_action(action).await?;
+            }
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_replay_restores_state() -> Result<()> {
+        let path = std::env::temp_dir().join("ralertsinua_test_replay.jsonl");
+        let _ = std::fs::remove_file(&path);
+        let mut app = failing_app(MemoryDataSource::default());
+        app.recorder = Some(Recorder::open(&path)?);
+        handle_all(
+            &mut app,
+            vec![
+                Action::FetchGeo,
+                Action::GetAirRaidAlertOblastStatuses(AirRaidAlertOblastStatuses::new(
+                    "ANNAANNANNNPANANANNNNAANNNN".to_string(),
+                    Some(false),
+                )),
+                Action::Tick,
+                Action::SelectIndex(3),
+                Action::SelectTab(1),
+                Action::FetchAirRaidAlertOblastStatuses,
+            ],
+        )
+        .await?;
+        assert!(app.state.selected.is_some());
+
+        let recording = load_recording(&path)?;
+        assert!(!recording.iter().any(|r| r.action == Action::Tick));
+        let mut replayed = failing_app(MemoryDataSource::default());
+        let live_tx = replayed.start_replay();
+        for recorded in recording {
+            live_tx.send(recorded.action)?;
+        }
+        while let Ok(action) = replayed.action_rx.try_recv() {
+            replayed.handle_action(action).await?;
+        }
+
+        assert!(replayed.is_replaying());
+        assert_eq!(replayed.state, app.state);
+        assert_eq!(replayed.selected_tab, app.selected_tab);
+        assert_eq!(replayed.mode, app.mode);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_saves_state() -> Result<()> {
+        let dir = std::env::temp_dir().join("ralertsinua_test_shutdown");
+        let _ = std::fs::remove_dir_all(&dir);
+        let repository = DataRepository::open(dir.join(STATUSES_FILE))?;
+        repository.insert_alerts_string("ANNNNNNNNNNNNNNNNNNNNNNNNNN")?;
+        let geo_client = Arc::new(AlertsInUaGeoClient::default());
+        let mut app = App::with_data_source(
+            Config::default(),
+            Arc::new(FailingApi),
+            geo_client,
+            missing_config_path(),
+            dir.join(STATE_FILE),
+            Box::new(repository.clone()),
+        )?;
+        app.state.selected = Some(31);
+
+        app.shutdown().await;
+
+        assert!(app.cancellation_token.is_cancelled());
+        assert_eq!(State::load(&app.state_path).selected, Some(31));
+        assert_eq!(repository.fetch_alerts_history(10)?.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_reload_geo_replaces_data() -> Result<()> {
+        let mut app = failing_app(MemoryDataSource::default());
+        assert_eq!(app.geo_client.boundary().coords_count(), 955);
+        let wkt_path = std::env::temp_dir().join("ralertsinua_test_reload.wkt");
+        std::fs::write(&wkt_path, "POLYGON((22 44, 41 44, 41 53, 22 53, 22 44))")?;
+        app.config
+            .set_boundary_path(wkt_path.to_string_lossy().to_string());
+
+        app.reload_geo()?;
+        assert_eq!(app.geo_client.boundary().coords_count(), 5);
+        assert_eq!(app.action_rx.try_recv().ok(), Some(Action::FetchGeo));
+
+        // broken file keeps current data
+        std::fs::write(&wkt_path, "POLYGON((22 44")?;
+        app.reload_geo()?;
+        assert_eq!(app.geo_client.boundary().coords_count(), 5);
+        assert!(matches!(app.action_rx.try_recv(), Ok(Action::Error(_))));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_fetch_failures_are_reported() -> Result<()> {
+        let mut app = failing_app(MemoryDataSource::default());
+
+        app.fetch_active_alerts().await?;
+        app.fetch_air_raid_alert_oblast_statuses().await?;
+
+        let mut actions = Vec::new();
+        while let Ok(action) = app.action_rx.try_recv() {
+            actions.push(action);
+        }
+        let errors = actions
+            .iter()
+            .filter(|a| matches!(a, Action::Error(_)))
+            .count();
+        assert_eq!(errors, 2);
+        assert!(actions.contains(&Action::Online(false)));
+        // nothing known yet, locations are shown as offline
+        assert!(actions.contains(&Action::GetAirRaidAlertOblastStatuses(
+            AirRaidAlertOblastStatuses::default()
+        )));
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_fetch_failure_keeps_last_known_statuses() -> Result<()> {
+        let mut app = failing_app(MemoryDataSource::default());
+        app.last_statuses = "A".repeat(27);
+
+        app.fetch_air_raid_alert_oblast_statuses().await?;
+
+        while let Ok(action) = app.action_rx.try_recv() {
+            assert!(!matches!(action, Action::GetAirRaidAlertOblastStatuses(_)));
+        }
+        assert_eq!(app.last_statuses, "A".repeat(27));
+        Ok(())
+    }
+
+    #[cfg(not(windows))]
+    #[tokio::test]
+    async fn test_signal_handler_sends_quit() -> Result<()> {
+        let (action_tx, mut action_rx) = mpsc::unbounded_channel();
+        let cancellation_token = CancellationToken::new();
+        let task = spawn_signal_handler(action_tx, cancellation_token.clone())?;
+
+        signal_hook::low_level::raise(signal_hook::consts::signal::SIGTERM)?;
+        let action = tokio::time::timeout(Duration::from_secs(1), action_rx.recv()).await;
+
+        assert_eq!(action.ok().flatten(), Some(Action::Quit));
+        cancellation_token.cancel();
+        task.await.unwrap();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_fullscreen_map_takes_whole_frame() -> Result<()> {
+        let render = |app: &mut App| -> String {
+            let mut terminal =
+                Terminal::new(ratatui::backend::TestBackend::new(100, 30)).unwrap();
+            terminal.draw(|f| app.draw(f)).unwrap();
+            let buffer = terminal.backend().buffer().clone();
+            // top right corner of the frame
+            buffer.get(99, 0).symbol().to_string()
+                + &buffer
+                    .content()
+                    .iter()
+                    .map(|c| c.symbol())
+                    .collect::<String>()
+        };
+        let mut app = failing_app(MemoryDataSource::default());
+        for component in app.components.iter_mut() {
+            component.register_config_handler(app.config.clone())?;
+        }
+        handle_all(
+            &mut app,
+            vec![Action::FetchGeo, Action::SelectLocationByUid(Some(31))],
+        )
+        .await?;
+
+        let rendered = render(&mut app);
+        assert!(!rendered.starts_with('┐'));
+        // highlight symbol of selected list item
+        assert!(rendered.contains(">>"));
+
+        handle_all(&mut app, vec![Action::ToggleFullscreenMap]).await?;
+        let rendered = render(&mut app);
+        // map block border is drawn at frame corner, list is hidden
+        assert!(rendered.starts_with('┐'));
+        assert!(!rendered.contains(">>"));
+        assert_eq!(app.state.selected, Some(31));
+
+        handle_all(&mut app, vec![Action::ToggleFullscreenMap]).await?;
+        assert!(!render(&mut app).starts_with('┐'));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_small_terminal_shows_message() -> Result<()> {
+        let mut app = failing_app(MemoryDataSource::default());
+        handle_all(&mut app, vec![Action::FetchGeo]).await?;
+        let render = |app: &mut App, width, height| -> String {
+            let mut terminal =
+                Terminal::new(ratatui::backend::TestBackend::new(width, height)).unwrap();
+            terminal.draw(|f| app.draw(f)).unwrap();
+            terminal
+                .backend()
+                .buffer()
+                .content()
+                .iter()
+                .map(|c| c.symbol())
+                .collect()
+        };
+
+        // message is localized, size is not
+        let rendered = render(&mut app, 50, 15);
+        assert!(rendered.contains("60x20"));
+
+        let rendered = render(&mut app, 100, 30);
+        assert!(!rendered.contains("60x20"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_loading_around_fetch() -> Result<()> {
+        let mut app = failing_app(MemoryDataSource::default());
+        assert!(!app.loading);
+
+        app.init().await?;
+        assert!(app.loading);
+
+        // fetch fails, loading ends anyway
+        handle_all(&mut app, vec![Action::FetchAirRaidAlertOblastStatuses]).await?;
+        assert!(!app.loading);
+        Ok(())
+    }
+
+    #[test]
+    fn test_terminal_title() {
+        // count goes first in every locale
+        assert!(terminal_title(5).starts_with("alertsinua — 5 "));
+        assert!(terminal_title(0).starts_with("alertsinua — 0 "));
+    }
+
+    #[tokio::test]
+    async fn test_spawn_periodic_fetch() {
+        let (action_tx, mut action_rx) = mpsc::unbounded_channel();
+        let cancellation_token = CancellationToken::new();
+        let task = spawn_periodic_fetch(
+            action_tx,
+            Duration::from_millis(10),
+            cancellation_token.clone(),
+        );
+
+        sleep(Duration::from_millis(55)).await;
+        cancellation_token.cancel();
+        task.await.unwrap();
+
+        let mut fetches = 0;
+        while let Ok(action) = action_rx.try_recv() {
+            if action == Action::FetchActiveAlerts {
+                fetches += 1;
+            }
+        }
+        assert!(fetches >= 2);
+    }
 }
@@ -1,5 +1,10 @@
-use crate::utils::version;
-use clap::Parser;
+use crate::{
+    config::Locale,
+    output::OutputFormat,
+    utils::{version, LogLevel},
+};
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
 #[command(author, version = version(), about)]
@@ -14,48 +19,300 @@ pub struct Cli {
     )]
     pub token: String,
 
-    #[arg(short, long, value_name = "LOCALE", help = "Locale", required = false, value_parser = get_available_locales(), default_value = get_default_locale())]
-    pub locale: String,
+    #[arg(
+        long,
+        value_name = "URL",
+        help = "API base URL",
+        default_value = "",
+        required = false
+    )]
+    pub base_url: String,
+
+    #[arg(short, long, value_name = "LOCALE", help = "Locale, defaults to system one", required = false, value_parser = get_available_locales())]
+    pub locale: Option<String>,
 
     #[arg(
         long,
         value_name = "LOG_FILE",
-        help = "Path to log file",
+        help = "Path to log file rotated daily, relative paths are placed in data dir",
         default_value = ""
     )]
     pub log_file: String,
 
+    #[arg(
+        long,
+        value_enum,
+        value_name = "LEVEL",
+        help = "Log level, overrides RUST_LOG env [default: info]",
+        required = false
+    )]
+    pub log_level: Option<LogLevel>,
+
+    #[arg(
+        long,
+        value_name = "DB_PATH",
+        help = "Path to file storing fetched alerts, defaults to platform data dir",
+        default_value = ""
+    )]
+    pub db_path: String,
+
+    #[arg(
+        long,
+        value_name = "WKT_PATH",
+        help = "Path to country boundary WKT file, defaults to bundled one",
+        default_value = ""
+    )]
+    pub boundary_path: String,
+
+    #[arg(
+        long,
+        value_name = "GEOJSON_PATH",
+        help = "Path to GeoJSON file with 27 administrative units, defaults to bundled one",
+        default_value = ""
+    )]
+    pub locations_path: String,
+
+    #[arg(
+        long = "poll-interval",
+        alias = "interval",
+        value_name = "SECONDS",
+        value_parser = parse_poll_interval,
+        help = "Polling interval, i.e. seconds between periodic fetches of alerts, at least 10",
+        required = false
+    )]
+    pub poll_interval: Option<u64>,
+
+    #[arg(
+        long,
+        help = "Show desktop notification when region enters active alert status",
+        default_value_t = false
+    )]
+    pub notifications: bool,
+
+    #[arg(
+        long,
+        help = "Ring terminal bell when region enters active alert status",
+        default_value_t = false
+    )]
+    pub bell: bool,
+
+    #[arg(
+        long,
+        help = "Disable colors, distinguish alert statuses by text markers",
+        default_value_t = false
+    )]
+    pub no_color: bool,
+
+    #[arg(
+        long,
+        help = "Run offline with bundled sample alerts, no token required",
+        default_value_t = false
+    )]
+    pub demo: bool,
+
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "Append every dispatched action with timestamp to JSONL file, for debugging",
+        required = false
+    )]
+    pub record: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "Replay actions recorded with '--record' instead of live events and fetches",
+        required = false
+    )]
+    pub replay: Option<PathBuf>,
+
+    #[arg(
+        short,
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        help = "Print current alerts once in given format and exit, without TUI",
+        required = false
+    )]
+    pub output: Option<OutputFormat>,
+
+    #[arg(
+        long,
+        help = "Check that API is reachable and token is valid, then exit with code 0 or 1, without TUI",
+        default_value_t = false
+    )]
+    pub check: bool,
+
+    #[arg(
+        long,
+        value_name = "NAME|UID",
+        help = "Watch a single region, e.g. 'Київ' or 31: print its status and alerts on change until Ctrl+C, without TUI",
+        required = false
+    )]
+    pub region: Option<String>,
+
     #[arg(
         long,
         value_name = "FLOAT",
-        help = "Tick rate, i.e. number of ticks per second",
-        default_value_t = 1.0
+        value_parser = parse_rate,
+        help = "Tick rate, i.e. number of ticks per second, must be positive [default: 1.0]",
+        required = false
     )]
-    pub tick_rate: f64,
+    pub tick_rate: Option<f64>,
 
     #[arg(
         long,
         value_name = "FLOAT",
-        help = "Frame rate, i.e. number of frames per second",
-        default_value_t = 1.0
+        value_parser = parse_frame_rate,
+        help = "Frame rate, i.e. number of frames per second, must be positive, values above 120 are capped [default: 1.0]",
+        required = false
     )]
-    pub frame_rate: f64,
+    pub frame_rate: Option<f64>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
 }
 
-#[inline]
-fn get_available_locales() -> Vec<&'static str> {
-    let locales = rust_i18n::available_locales!();
-    if locales.is_empty() {
-        return vec!["en", "uk"];
+/// Subcommands run instead of TUI
+#[derive(Subcommand, Debug, Clone, Copy, PartialEq)]
+pub enum Command {
+    /// Inspect configuration
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone, Copy, PartialEq)]
+pub enum ConfigCommand {
+    /// Print effective configuration merged from CLI args, env vars and config file as TOML,
+    /// with token redacted, and exit
+    Print,
+}
+
+/// Upper limit of frame rate, more frames are not noticeable in terminal
+pub const MAX_FRAME_RATE: f64 = 120.0;
+
+/// Parse positive number of events per second
+fn parse_rate(s: &str) -> Result<f64, String> {
+    let rate: f64 = s.parse().map_err(|_| format!("'{}' is not a number", s))?;
+    if !rate.is_finite() || rate <= 0.0 {
+        return Err(format!("must be a positive number, got {}", s));
     }
+    Ok(rate)
+}
 
-    locales
+/// Parse positive frame rate, capped at [`MAX_FRAME_RATE`]
+fn parse_frame_rate(s: &str) -> Result<f64, String> {
+    parse_rate(s).map(|rate| rate.min(MAX_FRAME_RATE))
+}
+
+/// Shortest polling interval in seconds, API is rate limited
+pub const MIN_POLL_INTERVAL: u64 = 10;
+
+/// Parse polling interval in seconds, at least [`MIN_POLL_INTERVAL`]
+pub fn parse_poll_interval(s: &str) -> Result<u64, String> {
+    let interval: u64 = s
+        .parse()
+        .map_err(|_| format!("'{}' is not a number of seconds", s))?;
+    if interval < MIN_POLL_INTERVAL {
+        return Err(format!(
+            "must be at least {} seconds, got {}",
+            MIN_POLL_INTERVAL, s
+        ));
+    }
+    Ok(interval)
 }
 
 #[inline]
-fn get_default_locale() -> String {
+fn get_available_locales() -> Vec<&'static str> {
+    Locale::all().iter().map(Locale::as_str).collect()
+}
+
+/// Language of system locale, `en` if unknown
+#[inline]
+pub fn get_default_locale() -> String {
     match sys_locale::get_locale() {
         Some(sl) => sl[..2].to_string(),
         None => "en".to_string(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rates_reject_non_positive() {
+        for args in [
+            ["ralertsinua", "--tick-rate=0"],
+            ["ralertsinua", "--tick-rate=-1"],
+            ["ralertsinua", "--frame-rate=0"],
+            ["ralertsinua", "--frame-rate=-2.5"],
+            ["ralertsinua", "--frame-rate=fast"],
+        ] {
+            assert!(Cli::try_parse_from(args).is_err(), "{:?}", args);
+        }
+    }
+
+    #[test]
+    fn test_region() {
+        let cli = Cli::try_parse_from(["ralertsinua", "--region", "Київ"]).unwrap();
+        assert_eq!(cli.region.as_deref(), Some("Київ"));
+
+        let cli = Cli::try_parse_from(["ralertsinua"]).unwrap();
+        assert_eq!(cli.region, None);
+    }
+
+    #[test]
+    fn test_rates() {
+        let cli = Cli::try_parse_from(["ralertsinua"]).unwrap();
+        assert_eq!(cli.tick_rate, None);
+        assert_eq!(cli.frame_rate, None);
+
+        let cli = Cli::try_parse_from([
+            "ralertsinua",
+            "--tick-rate",
+            "0.5",
+            "--frame-rate",
+            "500",
+        ])
+        .unwrap();
+        assert_eq!(cli.tick_rate, Some(0.5));
+        assert_eq!(cli.frame_rate, Some(MAX_FRAME_RATE));
+    }
+
+    #[test]
+    fn test_poll_interval() {
+        for args in [
+            ["ralertsinua", "--poll-interval=0"],
+            ["ralertsinua", "--poll-interval=9"],
+            ["ralertsinua", "--poll-interval=-30"],
+        ] {
+            assert!(Cli::try_parse_from(args).is_err(), "{:?}", args);
+        }
+        let cli = Cli::try_parse_from(["ralertsinua", "--poll-interval=10"]).unwrap();
+        assert_eq!(cli.poll_interval, Some(MIN_POLL_INTERVAL));
+    }
+
+    #[test]
+    fn test_config_print() {
+        let cli = Cli::try_parse_from([
+            "ralertsinua",
+            "--base-url=http://cli",
+            "config",
+            "print",
+        ])
+        .unwrap();
+        assert_eq!(
+            cli.command,
+            Some(Command::Config {
+                command: ConfigCommand::Print
+            })
+        );
+        assert_eq!(cli.base_url, "http://cli");
+
+        assert_eq!(Cli::try_parse_from(["ralertsinua"]).unwrap().command, None);
+        assert!(Cli::try_parse_from(["ralertsinua", "config"]).is_err());
+    }
+}
@@ -0,0 +1,88 @@
+use ralertsinua_models::AirRaidAlertOblastStatuses;
+use rust_i18n::t;
+use time::{macros::format_description, OffsetDateTime};
+
+/// Human-readable summary of regions with active alert,
+/// e.g. "Active: Луганська область, Київ; as of 12:34 UTC"
+pub fn alerts_summary(
+    statuses: &AirRaidAlertOblastStatuses,
+    locale: &str,
+    as_of: Option<OffsetDateTime>,
+) -> String {
+    let names: Vec<&str> = statuses
+        .iter()
+        .filter(|os| os.is_active_on_all_oblast())
+        .map(|os| {
+            if locale == "uk" {
+                os.location_title().as_str()
+            } else {
+                os.location_title_en().as_str()
+            }
+        })
+        .collect();
+    let active = if names.is_empty() {
+        t!("summary.none").to_string()
+    } else {
+        names.join(", ")
+    };
+    let mut summary = format!("{}: {}", t!("summary.active"), active);
+    if let Some(as_of) = as_of {
+        let time = as_of
+            .format(format_description!("[hour]:[minute] UTC"))
+            .unwrap_or_default();
+        summary.push_str(&format!("; {} {}", t!("summary.as_of"), time));
+    }
+    summary
+}
+
+/// Put `text` to system clipboard, fails e.g. in headless environment
+#[cfg(feature = "clipboard")]
+pub fn copy_to_clipboard(text: &str) -> Result<(), String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text.to_string()))
+        .map_err(|e| format!("failed to copy to clipboard: {}", e))
+}
+
+/// Clipboard requires `clipboard` feature
+#[cfg(not(feature = "clipboard"))]
+pub fn copy_to_clipboard(text: &str) -> Result<(), String> {
+    Err("failed to copy to clipboard: built without 'clipboard' feature".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    #[test]
+    fn test_alerts_summary() {
+        // "Київ" and "Луганська область" are active
+        let statuses = AirRaidAlertOblastStatuses::new(
+            "NNNNNNNNNANNANNNNNNNNNNNNNN".to_string(),
+            Some(false),
+        );
+
+        let summary =
+            alerts_summary(&statuses, "uk", Some(datetime!(2024-05-05 12:34 UTC)));
+        assert_eq!(
+            summary,
+            format!(
+                "{}: Київ, Луганська область; {} 12:34 UTC",
+                t!("summary.active"),
+                t!("summary.as_of")
+            )
+        );
+
+        let summary = alerts_summary(&statuses, "en", None);
+        assert!(summary.ends_with(": Kyiv, Luhansk Oblast"));
+    }
+
+    #[test]
+    fn test_alerts_summary_without_alerts() {
+        let summary = alerts_summary(&AirRaidAlertOblastStatuses::default(), "en", None);
+        assert_eq!(
+            summary,
+            format!("{}: {}", t!("summary.active"), t!("summary.none"))
+        );
+    }
+}
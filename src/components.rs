@@ -8,22 +8,37 @@ use crate::{
     config::Config,
     error::AppError,
     layout::*,
+    theme::Theme,
     tui::{Event, Frame},
     tui_helpers::*,
     utils::type_of,
 };
 
+pub mod alert_type_filter;
+pub mod details;
 pub mod fps;
 pub mod header;
+pub mod help;
+pub mod legend;
 pub mod list;
 pub mod logger;
 pub mod map;
+pub mod sparkline;
+pub mod status_bar;
+pub mod toast;
 
+pub use alert_type_filter::*;
+pub use details::*;
 pub use fps::*;
 pub use header::*;
+pub use help::*;
+pub use legend::*;
 pub use list::*;
 pub use logger::*;
 pub use map::*;
+pub use sparkline::*;
+pub use status_bar::*;
+pub use toast::*;
 
 pub type Result<T> = miette::Result<T, AppError>;
 
@@ -35,7 +50,12 @@ pub trait WithPlacement<'a> {
         let cmp_name = type_of(self);
         let LayoutPoint(area, _) = self.placement();
         let frame_wrapper = get_terminal_area_max_height(frame_size, 30);
-        Ok(get_component_area(frame_wrapper, cmp_name, *area))
+        Ok(get_component_area(
+            frame_wrapper,
+            cmp_name,
+            *area,
+            layout_settings(),
+        ))
     }
     /// Check if the component is visible based on current selected tab
     fn is_visible(&self, selected_tab: &LayoutTab) -> bool {
@@ -81,6 +101,20 @@ pub trait Component<'a>: WithPlacement<'a> {
     fn register_config_handler(&mut self, config: Config) -> Result<()> {
         Ok(())
     }
+    /// Register UI colors from `[theme]` table of config file, or monochrome ones in
+    /// no-color mode, if the component is styled.
+    ///
+    /// # Arguments
+    ///
+    /// * `theme` - UI colors.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<()>` - An Ok result or an error.
+    #[allow(unused_variables)]
+    fn register_theme_handler(&mut self, theme: Theme) -> Result<()> {
+        Ok(())
+    }
     /// Initialize the component with a specified area if necessary.
     ///
     /// # Arguments
@@ -105,7 +139,7 @@ pub trait Component<'a>: WithPlacement<'a> {
     fn handle_events(&mut self, event: Option<Event>) -> Result<Option<Action>> {
         let r = match event {
             Some(Event::Key(key_event)) => self.handle_key_events(key_event)?,
-            // Some(Event::Mouse(mvent)) => self.handle_mouse_events(mouse_event)?,
+            Some(Event::Mouse(mouse_event)) => self.handle_mouse_events(mouse_event)?,
             _ => None,
         };
         Ok(r)
@@ -160,4 +194,19 @@ pub trait Component<'a>: WithPlacement<'a> {
     ///
     /// * `Result<()>` - An Ok result or an error.
     fn draw(&mut self, f: &mut Frame<'_>) -> Result<()>;
+    /// Render the component in given `area` instead of its layout area, e.g. map over whole
+    /// terminal in fullscreen mode. Components not supporting it draw in their layout area
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - A frame used for rendering.
+    /// * `area` - The area in which the component should be drawn.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<()>` - An Ok result or an error.
+    #[allow(unused_variables)]
+    fn draw_in(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
+        self.draw(f)
+    }
 }
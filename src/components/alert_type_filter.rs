@@ -0,0 +1,60 @@
+use ralertsinua_models::{AirRaidAlertOblastStatuses, AlertType, Alerts};
+
+/// Alert types shown in UI, cycled by user to declutter the view
+#[derive(Debug, Default, Clone, Copy, strum_macros::Display, PartialEq)]
+pub enum AlertTypeFilter {
+    #[default]
+    #[strum(to_string = "all")]
+    All,
+    #[strum(to_string = "air_raid")]
+    AirRaid,
+    #[strum(to_string = "artillery_shelling")]
+    ArtilleryShelling,
+}
+
+impl AlertTypeFilter {
+    /// Next filter in cycle "all / air raid only / artillery only"
+    pub fn next(self) -> Self {
+        match self {
+            Self::All => Self::AirRaid,
+            Self::AirRaid => Self::ArtilleryShelling,
+            Self::ArtilleryShelling => Self::All,
+        }
+    }
+
+    /// Alert type to keep, `None` keeps all
+    pub fn alert_type(self) -> Option<AlertType> {
+        match self {
+            Self::All => None,
+            Self::AirRaid => Some(AlertType::AirRaid),
+            Self::ArtilleryShelling => Some(AlertType::ArtilleryShelling),
+        }
+    }
+
+    /// Fetched `statuses` as is for all alert types, otherwise statuses derived from
+    /// `alerts` of selected type only, so regions with only excluded alerts have no alert
+    pub fn apply(
+        self,
+        statuses: &AirRaidAlertOblastStatuses,
+        alerts: &Alerts,
+    ) -> AirRaidAlertOblastStatuses {
+        match self.alert_type() {
+            None => statuses.clone(),
+            Some(alert_type) => alerts.get_oblast_statuses_by_alert_type(alert_type),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alert_type_filter_cycle() {
+        let filter = AlertTypeFilter::default();
+        assert_eq!(filter, AlertTypeFilter::All);
+        assert_eq!(filter.next(), AlertTypeFilter::AirRaid);
+        assert_eq!(filter.next().next(), AlertTypeFilter::ArtilleryShelling);
+        assert_eq!(filter.next().next().next(), AlertTypeFilter::All);
+    }
+}
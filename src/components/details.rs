@@ -0,0 +1,306 @@
+use ralertsinua_models::*;
+use ratatui::{prelude::*, widgets::*};
+use rust_i18n::t;
+use std::str::FromStr;
+use strum::EnumProperty;
+use time::{macros::format_description, Duration};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::{Component, Result, WithPlacement};
+use crate::{
+    action::Action, config::*, layout::*, theme::*, tui::Frame, utils::alert_type_label,
+};
+
+/// Details of active alerts in the selected location
+#[derive(Debug)]
+pub struct LocationDetails {
+    command_tx: Option<UnboundedSender<Action>>,
+    placement: LayoutPoint,
+    #[allow(unused)]
+    config: Config,
+    alerts: Alerts,
+    selected_location_uid: Option<i32>,
+    theme: Theme,
+}
+
+impl LocationDetails {
+    pub fn new() -> Self {
+        Self {
+            command_tx: Option::default(),
+            placement: LayoutPoint(LayoutArea::RightBottom, Some(LayoutTab::Tab1)),
+            config: Config::default(),
+            alerts: Alerts::default(),
+            selected_location_uid: None,
+            theme: Theme::default(),
+        }
+    }
+
+    /// Draw in `area` instead of right bottom pane, see [`LayoutSettings::details_area`]
+    pub fn with_area(mut self, area: LayoutArea) -> Self {
+        self.placement.0 = area;
+        self
+    }
+
+    /// Active alerts in selected location, either for the oblast itself or within it,
+    /// ordered by [`Alert::rank`]: whole-oblast first, then most severe
+    pub fn selected_alerts(&self) -> Vec<Alert> {
+        let Some(uid) = self.selected_location_uid else {
+            return Vec::new();
+        };
+        let mut alerts: Vec<Alert> = self
+            .alerts
+            .iter()
+            .filter(|a| a.is_active())
+            .filter(|a| a.location_uid == uid || a.location_oblast_uid == Some(uid))
+            .cloned()
+            .collect();
+        alerts.sort_by_key(|a| std::cmp::Reverse(a.rank()));
+        alerts
+    }
+
+    /// Color of alert type, single alert color if colors are disabled
+    fn alert_type_color(&self, alert_type: &AlertType) -> Color {
+        if self.theme.no_color {
+            return self.theme.alert;
+        }
+        alert_type
+            .get_str("color")
+            .and_then(|color| Color::from_str(color).ok())
+            .unwrap_or(self.theme.alert)
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        if self.selected_location_uid.is_none() {
+            return vec![t!("views.Details.select_location").to_string().dim().into()];
+        }
+        let alerts = self.selected_alerts();
+        if alerts.is_empty() {
+            return vec![t!("views.Details.no_alerts").to_string().into()];
+        }
+        let started_at_format =
+            format_description!("[year]-[month]-[day] [hour]:[minute] UTC");
+        alerts
+            .iter()
+            .flat_map(|alert| {
+                let started_at = alert
+                    .started_at
+                    .format(started_at_format)
+                    .unwrap_or_default();
+                let icon = alert.alert_type.get_str("icon").unwrap_or_default();
+                let label = alert_type_label(&alert.alert_type, &rust_i18n::locale());
+                let mut lines = vec![
+                    Line::from(vec![
+                        format!("{} {}", icon, label)
+                            .fg(self.alert_type_color(&alert.alert_type))
+                            .bold(),
+                        format!(" {}", alert.location_title).into(),
+                    ]),
+                    Line::from(format!(
+                        "{} ({})",
+                        started_at,
+                        format_duration(alert.duration())
+                    )),
+                ];
+                let notes = alert.notes.as_deref().map(sanitize_notes);
+                if let Some(notes) = notes.filter(|n| !n.is_empty()) {
+                    lines.push(notes.italic().into());
+                }
+                lines
+            })
+            .collect()
+    }
+}
+
+/// Free-text alert notes with control characters (including line breaks) replaced by spaces,
+/// so they can't break the terminal; wrapping to panel width is left to the paragraph
+pub fn sanitize_notes(notes: &str) -> String {
+    notes
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+/// Human readable duration, e.g. "1h 05m"
+pub fn format_duration(duration: Duration) -> String {
+    let minutes = duration.whole_minutes();
+    format!("{}h {:02}m", minutes / 60, minutes % 60)
+}
+
+impl WithPlacement<'_> for LocationDetails {
+    fn placement(&self) -> &LayoutPoint {
+        &self.placement
+    }
+}
+
+impl Component<'_> for LocationDetails {
+    fn init(&mut self, size: Rect) -> Result<()> {
+        self.debug();
+        Ok(())
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn register_theme_handler(&mut self, theme: Theme) -> Result<()> {
+        self.theme = theme;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::GetActiveAlerts(alerts) => {
+                self.alerts = alerts;
+            }
+            Action::SelectLocationByUid(uid) => {
+                self.selected_location_uid = uid.map(|uid| uid as i32);
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>) -> Result<()> {
+        let area = self.get_area(f.size())?;
+        let widget = Paragraph::new(self.lines())
+            .wrap(Wrap { trim: true })
+            .block(Block::bordered().title(t!("views.Details.title").to_string()));
+        f.render_widget(widget, area);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ralertsinua_models::test_util::AlertBuilder;
+    use ratatui::backend::TestBackend;
+
+    fn alerts() -> Alerts {
+        Alerts::from(vec![AlertBuilder::new(31)
+            .with_location(LocationType::City, 31)
+            .with_title("м. Київ")
+            .with_notes("Загроза застосування БПЛА")
+            .build()])
+    }
+
+    fn render(details: &mut LocationDetails) -> String {
+        render_lines(details, 120, 30).concat()
+    }
+
+    fn render_lines(details: &mut LocationDetails, width: u16, height: u16) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|f| details.draw(f).unwrap()).unwrap();
+        let buffer = terminal.backend().buffer();
+        buffer
+            .content()
+            .chunks(buffer.area.width as usize)
+            .map(|row| row.iter().map(|c| c.symbol()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_draw_with_alerts() -> Result<()> {
+        let mut details = LocationDetails::new();
+        details.update(Action::GetActiveAlerts(alerts()))?;
+        details.update(Action::SelectLocationByUid(Some(31)))?;
+
+        let rendered = render(&mut details);
+
+        assert_eq!(details.selected_alerts().len(), 1);
+        let label = alert_type_label(&AlertType::AirRaid, &rust_i18n::locale());
+        assert!(rendered.contains(&label));
+        assert!(rendered.contains("2024-05-05 15:48 UTC"));
+        assert!(rendered.contains("Загроза"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_selected_alerts_skip_missing_oblast_uid() -> Result<()> {
+        let alerts = Alerts::from(vec![
+            AlertBuilder::new(31)
+                .with_id(1)
+                .with_location(LocationType::City, 31)
+                .build(),
+            AlertBuilder::new(14)
+                .with_id(2)
+                .with_location(LocationType::Hromada, 1234)
+                .with_oblast_uid(None)
+                .build(),
+        ]);
+        let mut details = LocationDetails::new();
+        details.update(Action::GetActiveAlerts(alerts))?;
+
+        details.update(Action::SelectLocationByUid(Some(31)))?;
+        let selected = details.selected_alerts();
+        assert_eq!(selected.len(), 1);
+        assert_eq!(selected[0].id, 1);
+
+        details.update(Action::SelectLocationByUid(Some(14)))?;
+        assert!(details.selected_alerts().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_draw_without_alerts() -> Result<()> {
+        let mut details = LocationDetails::new();
+        details.update(Action::GetActiveAlerts(alerts()))?;
+        details.update(Action::SelectLocationByUid(Some(14)))?;
+
+        let rendered = render(&mut details);
+
+        assert!(details.selected_alerts().is_empty());
+        assert!(rendered.contains(&t!("views.Details.no_alerts").to_string()));
+        let label = alert_type_label(&AlertType::AirRaid, &rust_i18n::locale());
+        assert!(!rendered.contains(&label));
+        Ok(())
+    }
+
+    #[test]
+    fn test_long_notes_wrap() -> Result<()> {
+        let mut alert = alerts().iter().next().unwrap().clone();
+        alert.notes =
+            Some("Загроза\x1b[31m застосування\tБПЛА\nу місті та області".to_string());
+        let alerts = Alerts::from(vec![alert]);
+        let mut details = LocationDetails::new();
+        details.update(Action::GetActiveAlerts(alerts))?;
+        details.update(Action::SelectLocationByUid(Some(31)))?;
+
+        // details pane is a quarter of the frame wide
+        let lines = render_lines(&mut details, 80, 30);
+
+        let notes_rows: Vec<&String> = lines
+            .iter()
+            .filter(|l| {
+                ["Загроза", "застосування", "БПЛА", "області"]
+                    .iter()
+                    .any(|w| l.contains(w))
+            })
+            .collect();
+        assert!(notes_rows.len() > 1, "{:#?}", lines);
+        assert!(lines
+            .iter()
+            .all(|l| !l.contains('\x1b') && !l.contains('\t')));
+        Ok(())
+    }
+
+    #[test]
+    fn test_sanitize_notes() {
+        assert_eq!(sanitize_notes("a\x07b\r\nc "), "a b  c");
+        assert_eq!(sanitize_notes("\n"), "");
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::minutes(65)), "1h 05m");
+        assert_eq!(format_duration(Duration::ZERO), "0h 00m");
+    }
+}
@@ -24,6 +24,8 @@ pub struct FpsCounter<'a> {
     throbber_state: ThrobberState,
     #[allow(unused)]
     config: Config,
+    /// Show ticks and frames per second readout, hidden by default
+    visible: bool,
 }
 
 impl<'a> FpsCounter<'a> {
@@ -41,9 +43,14 @@ impl<'a> FpsCounter<'a> {
             title: Line::default(),
             throbber_state: ThrobberState::default(),
             config: Config::default(),
+            visible: false,
         }
     }
 
+    pub fn visible(&self) -> bool {
+        self.visible
+    }
+
     fn app_tick(&mut self) -> Result<()> {
         self.app_frames += 1;
         let now = Instant::now();
@@ -107,6 +114,9 @@ impl<'a> Component<'a> for FpsCounter<'a> {
                 self.render_tick()?;
             }
             Action::Refresh => {}
+            Action::ToggleFps => {
+                self.visible = !self.visible;
+            }
             Action::Online(online) => {
                 self.title = get_title_with_online_status("Satus", self.config.online())
                     .alignment(Alignment::Left);
@@ -117,23 +127,21 @@ impl<'a> Component<'a> for FpsCounter<'a> {
     }
 
     fn draw(&mut self, f: &mut Frame) -> Result<()> {
-        let rects = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints(vec![
-                Constraint::Min(0),
-                Constraint::Length(2), // last row
-            ])
-            .split(f.size());
-        let left = rects[1].offset(Offset { x: 1, y: 0 });
-        let rect = rects[1].offset(Offset { x: 4, y: 0 });
-
-        let s = format!(
-            "{:.2} ticks per sec (app) {:.2} frames per sec (render)",
-            self.app_fps, self.render_fps
-        );
+        let area = self.get_area(f.size())?;
+        let left = area.offset(Offset { x: 1, y: 0 });
+        let rect = area.offset(Offset { x: 4, y: 0 });
+
         let title = self.title.clone();
         let block = Block::default().title(title);
         f.render_widget(block, rect);
+        if self.visible {
+            let s = format!(
+                "{:.2} ticks per sec (app) {:.2} frames per sec (render)",
+                self.app_fps, self.render_fps
+            );
+            let readout = Paragraph::new(s.dim()).alignment(Alignment::Right);
+            f.render_widget(readout, area);
+        }
         // Show "spinner"
         let throb = Throbber::default()
             .throbber_style(
@@ -147,3 +155,21 @@ impl<'a> Component<'a> for FpsCounter<'a> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toggle_fps() -> Result<()> {
+        let mut fps = FpsCounter::new();
+        assert!(!fps.visible());
+
+        fps.update(Action::ToggleFps)?;
+        assert!(fps.visible());
+
+        fps.update(Action::ToggleFps)?;
+        assert!(!fps.visible());
+        Ok(())
+    }
+}
@@ -1,13 +1,15 @@
 use crossterm::event::KeyEvent;
 use ratatui::{
-    style::{Modifier, Style},
-    text::Line,
-    widgets::Tabs,
+    layout::Alignment,
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Paragraph, Tabs},
 };
+use rust_i18n::t;
 use tokio::sync::mpsc::UnboundedSender;
 
 use super::{Component, Result, WithPlacement};
-use crate::{action::Action, config::*, constants::*, layout::*, tui::Frame};
+use crate::{action::Action, config::*, layout::*, theme::*, tui::Frame};
 
 #[derive(Debug)]
 pub struct Header<'a> {
@@ -18,6 +20,9 @@ pub struct Header<'a> {
     #[allow(unused)]
     tabs: Vec<Line<'a>>,
     selected_tab: LayoutTab,
+    theme: Theme,
+    /// Number of active and partly active oblasts, `None` until statuses are fetched
+    alerts_count: Option<(usize, usize)>,
 }
 
 impl<'a> Header<'a> {
@@ -28,8 +33,23 @@ impl<'a> Header<'a> {
             placement: LayoutPoint(LayoutArea::Header, None),
             tabs: Vec::new(),
             selected_tab: LayoutTab::default(),
+            theme: Theme::default(),
+            alerts_count: None,
         }
     }
+
+    /// Count badge, e.g. "Active alerts: 5/27, partial: 3"
+    pub fn badge(&self) -> Option<Line<'static>> {
+        let (active, partial) = self.alerts_count?;
+        let active = format!("{}: {}/27", t!("views.Header.active_alerts"), active);
+        let partial = format!(", {}: {}", t!("views.Header.partial"), partial);
+        let line = if self.theme.no_color {
+            Line::from(vec![Span::raw(active), Span::raw(partial)])
+        } else {
+            Line::from(vec![active.fg(self.theme.alert), partial.dim()])
+        };
+        Some(line)
+    }
 }
 
 impl WithPlacement<'_> for Header<'_> {
@@ -44,6 +64,11 @@ impl<'a> Component<'a> for Header<'a> {
         Ok(())
     }
 
+    fn register_theme_handler(&mut self, theme: Theme) -> Result<()> {
+        self.theme = theme;
+        Ok(())
+    }
+
     fn handle_key_events(&mut self, key_event: KeyEvent) -> Result<Option<Action>> {
         Ok(None)
     }
@@ -56,6 +81,12 @@ impl<'a> Component<'a> for Header<'a> {
                 self.selected_tab = tab;
                 // info!("List->update->Action::Fetch: {}", action);
             }
+            Action::GetAirRaidAlertOblastStatuses(statuses) => {
+                self.alerts_count = Some((
+                    statuses.active_alerts_count(),
+                    statuses.partly_active_alerts_count(),
+                ));
+            }
             _ => {}
         }
         Ok(None)
@@ -66,18 +97,45 @@ impl<'a> Component<'a> for Header<'a> {
         let titles = LayoutTab::into_iter().map(LayoutTab::title);
         let selected_tab_index = self.selected_tab as usize;
         let widget = Tabs::new(titles)
-            .style(Style::default().fg(*DEFAULT_COLOR))
+            .style(Style::default().fg(self.theme.default))
             .highlight_style(
                 Style::default()
                     .add_modifier(Modifier::BOLD)
                     .add_modifier(Modifier::UNDERLINED)
-                    .fg(*SELECTED_STYLE_FG),
+                    .fg(self.theme.selected),
             )
             .select(selected_tab_index)
             .padding("", "")
             .divider(" ");
 
         f.render_widget(widget, area);
+        if let Some(badge) = self.badge() {
+            f.render_widget(Paragraph::new(badge).alignment(Alignment::Center), area);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ralertsinua_models::AirRaidAlertOblastStatuses;
+
+    #[test]
+    fn test_badge() -> Result<()> {
+        let mut header = Header::new();
+        assert_eq!(header.badge(), None);
+
+        header.update(Action::GetAirRaidAlertOblastStatuses(
+            AirRaidAlertOblastStatuses::new(
+                "ANNAANNANNNPANANANNNNAANNNN".to_string(),
+                Some(false),
+            ),
+        ))?;
+
+        let badge = header.badge().unwrap().to_string();
+        assert!(badge.contains(": 9/27, "));
+        assert!(badge.ends_with(": 1"));
         Ok(())
     }
 }
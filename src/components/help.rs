@@ -0,0 +1,221 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+use rust_i18n::t;
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::{Component, Result, WithPlacement};
+use crate::{
+    action::Action, keybindings::*, layout::*, theme::*, tui::Frame, tui_helpers::*,
+};
+
+/// Rows of key bindings reference, as (actions, i18n description key). Keys are taken from
+/// active [`KeyBindings`], rows without bound keys are skipped
+const HELP_ROWS: [(&[Action], &str); 20] = [
+    (&[Action::ToggleHelp], "views.Help.toggle_help"),
+    (&[Action::Quit], "views.Help.quit"),
+    (
+        &[Action::PreviousTab, Action::NextTab],
+        "views.Help.switch_tab",
+    ),
+    (
+        &[Action::SelectPrevious, Action::SelectNext],
+        "views.Help.select_location",
+    ),
+    (
+        &[Action::SelectTop, Action::SelectBottom],
+        "views.Help.select_top_bottom",
+    ),
+    (
+        &[Action::NextActive, Action::PrevActive],
+        "views.Help.select_next_previous_active",
+    ),
+    (&[Action::Unselect], "views.Help.unselect_location"),
+    (&[Action::SearchStart], "views.Help.search"),
+    (&[Action::ZoomIn, Action::ZoomOut], "views.Help.zoom"),
+    (
+        &[
+            Action::PanLeft,
+            Action::PanRight,
+            Action::PanUp,
+            Action::PanDown,
+        ],
+        "views.Help.pan",
+    ),
+    (
+        &[Action::ZoomToSelected, Action::ZoomReset],
+        "views.Help.zoom_to_selected",
+    ),
+    (
+        &[Action::ToggleFullscreenMap],
+        "views.Help.toggle_fullscreen_map",
+    ),
+    (
+        &[Action::CycleAlertTypeFilter],
+        "views.Help.cycle_alert_type_filter",
+    ),
+    (&[Action::CycleSort], "views.Help.cycle_sort"),
+    (&[Action::CopySummary], "views.Help.copy_summary"),
+    (&[Action::Locale], "views.Help.toggle_locale"),
+    (&[Action::Refresh], "views.Help.refresh"),
+    (&[Action::Reload], "views.Help.reload"),
+    (&[Action::ToggleFps], "views.Help.toggle_fps"),
+    (&[Action::Suspend], "views.Help.suspend"),
+];
+
+/// Keys of help rows bound in `bindings`, as (keys label, i18n description key)
+pub fn help_rows(bindings: &KeyBindings) -> Vec<(String, &'static str)> {
+    HELP_ROWS
+        .iter()
+        .filter_map(|&(actions, description)| {
+            let mut keys: Vec<String> = actions
+                .iter()
+                .flat_map(|action| bindings.keys(action))
+                .map(|key_event| display_key(&key_event))
+                .collect();
+            // not configurable, see `crate::mode::handle_key`
+            if actions.contains(&Action::Quit) {
+                keys.push("Ctrl+c".to_string());
+            }
+            (!keys.is_empty()).then(|| (keys.join(", "), description))
+        })
+        .collect()
+}
+
+/// Popup with key bindings reference, drawn over the layout
+#[derive(Debug, Clone)]
+pub struct Help {
+    command_tx: Option<UnboundedSender<Action>>,
+    placement: LayoutPoint,
+    visible: bool,
+    theme: Theme,
+    keybindings: KeyBindings,
+}
+
+impl Help {
+    pub fn new() -> Self {
+        Self {
+            command_tx: Option::default(),
+            placement: LayoutPoint(LayoutArea::Hidden, None),
+            visible: false,
+            theme: Theme::default(),
+            keybindings: KeyBindings::default(),
+        }
+    }
+
+    /// Show keys of `keybindings` instead of default ones
+    pub fn with_keybindings(mut self, keybindings: KeyBindings) -> Self {
+        self.keybindings = keybindings;
+        self
+    }
+
+    pub fn visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+}
+
+impl WithPlacement<'_> for Help {
+    fn placement(&self) -> &LayoutPoint {
+        &self.placement
+    }
+}
+
+impl Component<'_> for Help {
+    fn init(&mut self, size: Rect) -> Result<()> {
+        self.debug();
+        Ok(())
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_theme_handler(&mut self, theme: Theme) -> Result<()> {
+        self.theme = theme;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        if action == Action::ToggleHelp {
+            self.toggle();
+        }
+        Ok(None)
+    }
+
+    fn handle_key_events(&mut self, key_event: KeyEvent) -> Result<Option<Action>> {
+        match key_event.code {
+            KeyCode::Esc if self.visible => Ok(Some(Action::ToggleHelp)),
+            _ => Ok(None),
+        }
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>) -> Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
+        let area = centered_rect(f.size(), 60, 60);
+        let help_rows = help_rows(&self.keybindings);
+        let keys_width = help_rows
+            .iter()
+            .map(|(keys, _)| keys.chars().count())
+            .max()
+            .unwrap_or_default();
+        let rows = help_rows.into_iter().map(|(keys, description)| {
+            Row::new(vec![
+                Cell::from(keys.fg(self.theme.marker)),
+                Cell::from(t!(description).to_string()),
+            ])
+        });
+        let widths = [Constraint::Length(keys_width as u16), Constraint::Min(0)];
+        let widget = Table::new(rows, widths).block(
+            Block::bordered()
+                .title(t!("views.Help.title").to_string())
+                .title_alignment(Alignment::Center)
+                .padding(Padding::horizontal(1)),
+        );
+        f.render_widget(Clear, area);
+        f.render_widget(widget, area);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toggle_help() -> Result<()> {
+        let mut help = Help::new();
+        assert!(!help.visible());
+
+        help.update(Action::ToggleHelp)?;
+        assert!(help.visible());
+
+        let action = help.handle_key_events(KeyEvent::from(KeyCode::Esc))?;
+        assert_eq!(action, Some(Action::ToggleHelp));
+
+        help.update(Action::ToggleHelp)?;
+        assert!(!help.visible());
+        assert_eq!(help.handle_key_events(KeyEvent::from(KeyCode::Esc))?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_help_rows_follow_keybindings() -> Result<()> {
+        let rows = help_rows(&KeyBindings::default());
+        assert_eq!(rows.len(), HELP_ROWS.len());
+        assert!(rows.contains(&("q, Ctrl+c".to_string(), "views.Help.quit")));
+        assert!(rows.contains(&("↑, k, ↓, j".to_string(), "views.Help.select_location")));
+        assert!(rows.contains(&("Esc".to_string(), "views.Help.unselect_location")));
+
+        let settings = toml::from_str("SelectNext = \"ctrl+n\"\nRefresh = \"j\"").unwrap();
+        let rows = help_rows(&KeyBindings::from_settings(&settings)?);
+        assert!(rows.contains(&("↑, k, Ctrl+n".to_string(), "views.Help.select_location")));
+        assert!(rows.contains(&("j".to_string(), "views.Help.refresh")));
+        Ok(())
+    }
+}
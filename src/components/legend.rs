@@ -0,0 +1,90 @@
+use ralertsinua_models::AlertStatus;
+use ratatui::{prelude::*, widgets::*};
+use strum::EnumProperty;
+
+use super::{Component, Result, WithPlacement};
+use crate::{layout::*, theme::*, tui::Frame, tui_helpers::*};
+
+/// Statuses explained in legend, in order of display
+const LEGEND_STATUSES: [AlertStatus; 3] = [AlertStatus::A, AlertStatus::P, AlertStatus::N];
+
+/// One-line footer explaining status icons and colors
+#[derive(Debug, Clone)]
+pub struct Legend {
+    placement: LayoutPoint,
+    theme: Theme,
+}
+
+impl Legend {
+    pub fn new() -> Self {
+        Self {
+            placement: LayoutPoint(LayoutArea::Legend, Some(LayoutTab::Tab1)),
+            theme: Theme::default(),
+        }
+    }
+
+    /// Icon and label of each status, styled with status color, or text marker and label
+    /// in no-color mode
+    pub fn line(&self) -> Line<'static> {
+        let spans = LEGEND_STATUSES.iter().flat_map(|status| {
+            let span = if self.theme.no_color {
+                Span::raw(format!("{} {}", get_marker_by_status(status), status))
+            } else {
+                let icon = status.get_str("icon").unwrap_or_default();
+                Span::styled(format!("{} {}", icon, status), get_color_by_status(status))
+            };
+            [span, Span::raw("  ")]
+        });
+        Line::from(spans.collect::<Vec<_>>())
+    }
+}
+
+impl WithPlacement<'_> for Legend {
+    fn placement(&self) -> &LayoutPoint {
+        &self.placement
+    }
+}
+
+impl Component<'_> for Legend {
+    fn init(&mut self, size: Rect) -> Result<()> {
+        self.debug();
+        Ok(())
+    }
+
+    fn register_theme_handler(&mut self, theme: Theme) -> Result<()> {
+        self.theme = theme;
+        Ok(())
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>) -> Result<()> {
+        let area = self.get_area(f.size())?;
+        f.render_widget(Paragraph::new(self.line()), area);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_legend_line_contains_all_statuses() {
+        let text = Legend::new().line().to_string();
+
+        assert!(text.contains("Active"));
+        assert!(text.contains("Partial"));
+        assert!(text.contains("No info"));
+    }
+
+    #[test]
+    fn test_legend_line_no_color() -> Result<()> {
+        let mut legend = Legend::new();
+        legend.register_theme_handler(Theme::no_color())?;
+
+        assert_eq!(
+            legend.line().to_string().trim_end(),
+            "[!] Active  [~] Partial  [ ] No info"
+        );
+        Ok(())
+    }
+}
@@ -1,5 +1,7 @@
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
 use getset::*;
+use icu_locid::subtags::Language;
+use ralertsinua_geo::SortByKeyIcu;
 use ralertsinua_models::*;
 use ratatui::{
     prelude::*,
@@ -10,8 +12,42 @@ use rust_i18n::t;
 use tokio::sync::mpsc::UnboundedSender;
 use tracing::info;
 
-use super::{Component, Frame, Result, WithPlacement};
-use crate::{action::Action, config::*, constants::*, layout::*, tui_helpers::*};
+use super::{AlertTypeFilter, Component, Frame, Result, WithPlacement};
+use crate::{action::Action, config::*, layout::*, theme::*, tui_helpers::*};
+
+/// Order of locations in list, cycled by user
+#[derive(Debug, Default, Clone, Copy, PartialEq, strum_macros::Display)]
+pub enum SortMode {
+    /// As fetched, i.e. by id
+    #[default]
+    #[strum(to_string = "default")]
+    Default,
+    /// By name in active locale
+    #[strum(to_string = "name")]
+    Name,
+    /// Active first, then partly active, then the rest
+    #[strum(to_string = "status")]
+    Status,
+}
+
+impl SortMode {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Default => Self::Name,
+            Self::Name => Self::Status,
+            Self::Status => Self::Default,
+        }
+    }
+}
+
+/// Rank of status when sorting by status, lower goes first
+fn status_rank(status: &AlertStatus) -> u8 {
+    match status {
+        AlertStatus::A => 0,
+        AlertStatus::P => 1,
+        _ => 2,
+    }
+}
 
 #[derive(Debug, Getters, MutGetters, Setters)]
 pub struct LocationsList<'a> {
@@ -20,8 +56,16 @@ pub struct LocationsList<'a> {
     #[allow(unused)]
     title: Line<'a>,
     config: Config,
+    /// Statuses as displayed, i.e. with alert type filter applied
     #[getset(get = "pub")]
     oblast_statuses: AirRaidAlertOblastStatuses,
+    /// Statuses as fetched
+    fetched_statuses: AirRaidAlertOblastStatuses,
+    alerts: Alerts,
+    #[getset(get = "pub")]
+    alert_type_filter: AlertTypeFilter,
+    #[getset(get = "pub")]
+    sort_mode: SortMode,
     #[getset(get = "pub with_prefix")]
     list: List<'a>,
     #[getset(get = "pub", get_mut)]
@@ -29,6 +73,12 @@ pub struct LocationsList<'a> {
     #[getset(get = "pub", get_mut)]
     last_selected: Option<usize>,
     selected_location_uid: i32,
+    /// Search query to filter locations by name
+    #[getset(get = "pub")]
+    query: String,
+    theme: Theme,
+    /// Area of last draw, to map mouse clicks to items
+    area: Rect,
 }
 
 impl<'a> LocationsList<'a> {
@@ -39,34 +89,106 @@ impl<'a> LocationsList<'a> {
             title: Line::default(),
             config: Config::default(),
             oblast_statuses: AirRaidAlertOblastStatuses::default(),
+            fetched_statuses: AirRaidAlertOblastStatuses::default(),
+            alerts: Alerts::default(),
+            alert_type_filter: AlertTypeFilter::default(),
+            sort_mode: SortMode::default(),
             list: List::default(),
             state: ListState::default(),
             last_selected: None,
             selected_location_uid: -1,
+            query: String::new(),
+            theme: Theme::default(),
+            area: Rect::default(),
+        }
+    }
+
+    /// Statuses of locations matching search query by name or English name, case-insensitive,
+    /// in order of current sort mode
+    pub fn filtered_statuses(&self) -> Vec<AirRaidAlertOblastStatus> {
+        let query = self.query.to_lowercase();
+        let mut statuses: Vec<AirRaidAlertOblastStatus> = self
+            .oblast_statuses
+            .iter()
+            .filter(|os| {
+                query.is_empty()
+                    || os.location_title().to_lowercase().contains(&query)
+                    || os.location_title_en().to_lowercase().contains(&query)
+            })
+            .cloned()
+            .collect();
+        match self.sort_mode {
+            SortMode::Default => {}
+            SortMode::Name => {
+                let locale = self.config.get_locale();
+                statuses.sort_by_key_icu(
+                    |os| location_title_by_locale(os, &locale).to_string(),
+                    locale.as_str(),
+                );
+            }
+            SortMode::Status => statuses.sort_by_key(|os| status_rank(os.status())),
         }
+        statuses
+    }
+
+    /// Select the same location by uid after items were rebuilt or reordered,
+    /// rather than keeping positional index
+    fn restore_selection(&mut self) {
+        let selected = (self.selected_location_uid >= 0)
+            .then_some(self.selected_location_uid as usize);
+        self.select_by_location_uid(selected);
+    }
+
+    /// Switch to next sort mode, keeping selection on the same location
+    fn cycle_sort(&mut self) {
+        self.sort_mode = self.sort_mode.next();
+        self.restore_selection();
     }
 
-    /// Generate List Widget with ListItems of locations
+    /// Update search query and keep selection if selected location still matches
+    fn set_query(&mut self, query: String) {
+        self.query = query;
+        self.restore_selection();
+    }
+
+    /// Apply alert type filter to fetched statuses, keeping selection
+    fn apply_alert_type_filter(&mut self) {
+        self.oblast_statuses = self
+            .alert_type_filter
+            .apply(&self.fetched_statuses, &self.alerts);
+        self.restore_selection();
+    }
+
+    /// Generate List Widget with ListItems of locations, named in active locale
     fn generate_list(&mut self, is_loading: bool) -> List<'a> {
         let locale = self.config.get_locale();
-        let oblast_statuses = self.oblast_statuses();
-        let items = oblast_statuses.iter().map(|item| {
-            let text: &str = if locale.as_str() == "uk" {
-                item.location_title()
-            } else {
-                item.location_title_en()
-            };
-            let is_selected = (item.location_uid) == self.selected_location_uid;
-            get_styled_line_by_status(text, item.status(), &is_selected)
-        });
+        List::new(self.list_lines(&locale))
+    }
 
-        List::new(items)
+    /// Lines of filtered locations, with text markers instead of colors in no-color mode
+    fn list_lines(&self, locale: &Language) -> Vec<Line<'a>> {
+        self.filtered_statuses()
+            .iter()
+            .map(|item| {
+                let text = location_title_by_locale(item, locale);
+                let is_selected = (item.location_uid) == self.selected_location_uid;
+                if self.theme.no_color {
+                    get_plain_line_by_status(text, item.status(), &is_selected)
+                } else {
+                    get_styled_line_by_status(text, item.status(), &is_selected)
+                }
+            })
+            .collect()
     }
 
     pub fn next(&mut self) {
+        let len = self.filtered_statuses().len();
+        if len == 0 {
+            return;
+        }
         let i = match self.state.selected() {
             Some(i) => {
-                if i >= self.oblast_statuses().len() - 1 {
+                if i >= len - 1 {
                     0
                 } else {
                     i + 1
@@ -78,10 +200,14 @@ impl<'a> LocationsList<'a> {
     }
 
     pub fn previous(&mut self) {
+        let len = self.filtered_statuses().len();
+        if len == 0 {
+            return;
+        }
         let i = match self.state.selected() {
             Some(i) => {
                 if i == 0 {
-                    self.oblast_statuses().len() - 1
+                    len - 1
                 } else {
                     i - 1
                 }
@@ -103,13 +229,58 @@ impl<'a> LocationsList<'a> {
     }
 
     pub fn go_bottom(&mut self) {
-        self.state.select(Some(self.oblast_statuses().len() - 1));
+        let len = self.filtered_statuses().len();
+        self.state.select(len.checked_sub(1));
         // drop(lock);
     }
 
+    /// Select next location with active alert after selected one, wrapping around.
+    /// Nothing changes if no location is active
+    pub fn next_active(&mut self) -> Option<AirRaidAlertOblastStatus> {
+        self.select_active(true)
+    }
+
+    /// Select previous location with active alert before selected one, wrapping around
+    pub fn previous_active(&mut self) -> Option<AirRaidAlertOblastStatus> {
+        self.select_active(false)
+    }
+
+    fn select_active(&mut self, forward: bool) -> Option<AirRaidAlertOblastStatus> {
+        let statuses = self.filtered_statuses();
+        let index = find_active_index(&statuses, self.state.selected(), forward)?;
+        self.select_index(index)
+    }
+
+    /// Select item by `location_uid`, e.g. when restored from state
+    pub fn select_by_location_uid(&mut self, location_uid: Option<usize>) {
+        let index = location_uid.and_then(|uid| {
+            self.filtered_statuses()
+                .iter()
+                .position(|os| os.location_uid as usize == uid)
+        });
+        match index {
+            Some(i) => {
+                self.state.select(Some(i));
+                self.selected_location_uid = location_uid.unwrap_or_default() as i32;
+            }
+            None => {
+                self.unselect();
+                self.selected_location_uid = -1;
+            }
+        }
+    }
+
+    /// Select item by index in filtered list, e.g. on mouse click
+    pub fn select_index(&mut self, index: usize) -> Option<AirRaidAlertOblastStatus> {
+        let selected = self.filtered_statuses().get(index).cloned()?;
+        self.state.select(Some(index));
+        self.selected_location_uid = selected.location_uid;
+        Some(selected)
+    }
+
     pub fn selected(&self) -> Option<AirRaidAlertOblastStatus> {
         match self.state.selected() {
-            Some(i) => self.oblast_statuses().get(i),
+            Some(i) => self.filtered_statuses().get(i).cloned(),
             None => None,
         }
     }
@@ -137,15 +308,95 @@ impl<'a> Component<'a> for LocationsList<'a> {
         Ok(())
     }
 
+    fn register_theme_handler(&mut self, theme: Theme) -> Result<()> {
+        self.theme = theme;
+        Ok(())
+    }
+
     #[tracing::instrument(skip(self))]
     fn update(&mut self, action: Action) -> Result<Option<Action>> {
         match action {
             Action::Tick => {}
             Action::GetAirRaidAlertOblastStatuses(data) => {
-                self.oblast_statuses = data;
+                self.fetched_statuses = data;
+                self.apply_alert_type_filter();
                 self.list = self.generate_list(true);
             }
+            Action::GetActiveAlerts(alerts) => {
+                self.alerts = alerts;
+                self.apply_alert_type_filter();
+                self.list = self.generate_list(false);
+            }
+            Action::CycleSort => {
+                self.cycle_sort();
+                self.list = self.generate_list(false);
+            }
+            Action::CycleAlertTypeFilter => {
+                self.alert_type_filter = self.alert_type_filter.next();
+                self.apply_alert_type_filter();
+                self.list = self.generate_list(false);
+            }
+            Action::SelectLocationByUid(selected) => {
+                self.select_by_location_uid(selected);
+                self.list = self.generate_list(false);
+            }
+            Action::SelectNext | Action::SelectPrevious => {
+                if action == Action::SelectNext {
+                    self.next();
+                } else {
+                    self.previous();
+                }
+                let Some(selected) = self.selected() else {
+                    return Ok(None);
+                };
+                self.selected_location_uid = selected.location_uid;
+                let uid = selected.location_uid as usize;
+                return Ok(Some(Action::SelectLocationByUid(Some(uid))));
+            }
+            Action::Unselect => {
+                self.unselect();
+                return Ok(Some(Action::SelectLocationByUid(None)));
+            }
+            Action::SelectTop | Action::SelectBottom => {
+                if action == Action::SelectTop {
+                    self.go_top();
+                } else {
+                    self.go_bottom();
+                }
+                let selected = self.selected().map(|s| s.location_uid as usize);
+                return Ok(selected.map(|uid| Action::SelectLocationByUid(Some(uid))));
+            }
+            Action::NextActive | Action::PrevActive => {
+                let selected = if action == Action::NextActive {
+                    self.next_active()
+                } else {
+                    self.previous_active()
+                };
+                self.list = self.generate_list(false);
+                return Ok(selected
+                    .map(|s| Action::SelectLocationByUid(Some(s.location_uid as usize))));
+            }
+            Action::SelectTab(_) => {
+                // not drawn on other tabs, ignore clicks until drawn again
+                self.area = Rect::default();
+            }
+            Action::SelectIndex(index) => {
+                let selected = self.select_index(index);
+                self.list = self.generate_list(false);
+                return Ok(selected
+                    .map(|s| Action::SelectLocationByUid(Some(s.location_uid as usize))));
+            }
+            Action::SearchInput(c) => {
+                let query = format!("{}{}", self.query, c);
+                self.set_query(query);
+                self.list = self.generate_list(false);
+            }
+            Action::SearchClear => {
+                self.set_query(String::new());
+                self.list = self.generate_list(false);
+            }
             Action::Refresh => {
+                self.restore_selection();
                 self.list = self.generate_list(false);
                 info!("List->update->Action::Refresh: {}", action);
             }
@@ -163,7 +414,27 @@ impl<'a> Component<'a> for LocationsList<'a> {
 
     fn draw(&mut self, f: &mut Frame) -> Result<()> {
         let area = self.get_area(f.size())?;
-        let title = self.title.clone();
+        self.area = area;
+        let mut title = self.title.clone();
+        if self.sort_mode != SortMode::Default {
+            let key = format!("sort.{}", self.sort_mode);
+            let sort = t!(&key);
+            title
+                .spans
+                .push(format!(" ↓{}", sort).fg(self.theme.marker));
+        }
+        if self.alert_type_filter != AlertTypeFilter::All {
+            let key = format!("filters.{}", self.alert_type_filter);
+            let filter = t!(&key);
+            title
+                .spans
+                .push(format!(" [{}]", filter).fg(self.theme.marker));
+        }
+        if !self.query.is_empty() {
+            title
+                .spans
+                .push(format!(" /{}", self.query).fg(self.theme.marker));
+        }
         let widget: List<'a> = self
             .list
             .clone()
@@ -172,7 +443,7 @@ impl<'a> Component<'a> for LocationsList<'a> {
                 Style::default()
                     .add_modifier(Modifier::BOLD)
                     .bg(Color::Reset)
-                    .fg(*SELECTED_STYLE_FG),
+                    .fg(self.theme.selected),
             )
             .highlight_symbol(">>")
             .repeat_highlight_symbol(true);
@@ -181,44 +452,360 @@ impl<'a> Component<'a> for LocationsList<'a> {
         Ok(())
     }
 
-    fn handle_key_events(&mut self, key_event: KeyEvent) -> Result<Option<Action>> {
-        match key_event.code {
-            KeyCode::Down => {
-                self.next();
-                let selected = self.selected().unwrap();
-                self.selected_location_uid = selected.location_uid;
-                let action =
-                    Action::SelectLocationByUid(Some(selected.location_uid as usize));
-                Ok(Some(action))
-            }
-            KeyCode::Up => {
-                self.previous();
-                let selected = self.selected().unwrap();
-                self.selected_location_uid = selected.location_uid;
-                let action =
-                    Action::SelectLocationByUid(Some(selected.location_uid as usize));
-                Ok(Some(action))
-            }
-            KeyCode::Esc => {
-                self.unselect();
-                let action = Action::SelectLocationByUid(None);
-                Ok(Some(action))
-            }
-            // Other handlers you could add here.
-            _ => Ok(None),
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) -> Result<Option<Action>> {
+        if mouse.kind != MouseEventKind::Down(MouseButton::Left) {
+            return Ok(None);
         }
+        let index =
+            get_list_index_at(self.area, self.state.offset(), mouse.column, mouse.row);
+        Ok(index
+            .filter(|&i| i < self.filtered_statuses().len())
+            .map(Action::SelectIndex))
+    }
+}
+
+/// Index of item at terminal position within bordered list `area`, scrolled by `offset`
+pub fn get_list_index_at(
+    area: Rect,
+    offset: usize,
+    column: u16,
+    row: u16,
+) -> Option<usize> {
+    get_inner_position(area, column, row).map(|(_, y)| offset + y as usize)
+}
+
+/// Index of the nearest item with active (`A`) status after `current` (before it if not
+/// `forward`), wrapping around, `None` if no item is active. Without `current` search starts
+/// from the first (last) item
+pub fn find_active_index(
+    statuses: &[AirRaidAlertOblastStatus],
+    current: Option<usize>,
+    forward: bool,
+) -> Option<usize> {
+    let len = statuses.len();
+    let start = match (current, forward) {
+        (Some(i), _) => i.min(len.checked_sub(1)?),
+        (None, true) => len.checked_sub(1)?,
+        (None, false) => 0,
+    };
+    (1..=len)
+        .map(|step| {
+            if forward {
+                (start + step) % len
+            } else {
+                (start + len - step) % len
+            }
+        })
+        .find(|&i| *statuses[i].status() == AlertStatus::A)
+}
+
+/// Ukrainian location title for "uk" locale, English otherwise
+pub fn location_title_by_locale<'s>(
+    item: &'s AirRaidAlertOblastStatus,
+    locale: &Language,
+) -> &'s str {
+    if locale.as_str() == "uk" {
+        item.location_title()
+    } else {
+        item.location_title_en()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    // use super::*;
-
-    /* #[test]
-    fn test_map_new() {
-        let list = LocationsList::new(Ukraine::new_arc(), );
-        assert!(list.command_tx.is_none());
-        assert_eq!(list.state, ListState::default());
-        assert!(list.ukraine.read().unwrap().locations().is_empty() == true);
-    } */
+    use super::*;
+    use icu_locid::subtags::language;
+    use ralertsinua_models::test_util::AlertBuilder;
+
+    #[test]
+    fn test_list_lines_no_color_snapshot() -> Result<()> {
+        let mut list = LocationsList::new();
+        list.register_theme_handler(Theme::no_color())?;
+        let statuses = format!("APN{}", "N".repeat(24));
+        list.update(Action::GetAirRaidAlertOblastStatuses(
+            AirRaidAlertOblastStatuses::new(statuses, None),
+        ))?;
+
+        let lines = list.list_lines(&language!("en"));
+        let rendered: Vec<String> = lines.iter().take(3).map(Line::to_string).collect();
+
+        assert_eq!(
+            rendered,
+            [
+                "[!] Autonomous Republic of Crimea",
+                "[~] Vinnytsia Oblast",
+                "[ ] Volyn Oblast",
+            ]
+        );
+        assert!(lines.iter().all(|line| line.style.fg.is_none()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_list_index_at() {
+        let area = Rect::new(90, 1, 30, 20);
+
+        // first row inside of border
+        assert_eq!(get_list_index_at(area, 0, 95, 2), Some(0));
+        assert_eq!(get_list_index_at(area, 0, 95, 5), Some(3));
+        // scrolled list
+        assert_eq!(get_list_index_at(area, 7, 95, 5), Some(10));
+        // border and outside of list
+        assert_eq!(get_list_index_at(area, 0, 95, 1), None);
+        assert_eq!(get_list_index_at(area, 0, 90, 5), None);
+        assert_eq!(get_list_index_at(area, 0, 10, 5), None);
+    }
+
+    #[test]
+    fn test_select_index() -> Result<()> {
+        let mut list = LocationsList::new();
+
+        let action = list.update(Action::SelectIndex(1))?;
+
+        assert_eq!(action, Some(Action::SelectLocationByUid(Some(8))));
+        assert_eq!(list.state().selected(), Some(1));
+        assert_eq!(list.update(Action::SelectIndex(100))?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_active_index() {
+        // "Автономна Республіка Крим" (0), "Київ" (9) and "Луганська область" (12) are active,
+        // "Вінницька область" (1) only partly
+        let statuses = AirRaidAlertOblastStatuses::new(
+            "APNNNNNNNANNANNNNNNNNNNNNNN".to_string(),
+            Some(false),
+        );
+        let statuses: Vec<_> = statuses.iter().cloned().collect();
+
+        assert_eq!(find_active_index(&statuses, None, true), Some(0));
+        assert_eq!(find_active_index(&statuses, Some(0), true), Some(9));
+        assert_eq!(find_active_index(&statuses, Some(9), true), Some(12));
+        assert_eq!(find_active_index(&statuses, Some(12), true), Some(0));
+        assert_eq!(find_active_index(&statuses, None, false), Some(12));
+        assert_eq!(find_active_index(&statuses, Some(9), false), Some(0));
+        assert_eq!(find_active_index(&statuses, Some(0), false), Some(12));
+
+        let statuses: Vec<_> = AirRaidAlertOblastStatuses::new("NP".repeat(13) + "N", None)
+            .iter()
+            .cloned()
+            .collect();
+        assert_eq!(find_active_index(&statuses, Some(3), true), None);
+        assert_eq!(find_active_index(&[], None, false), None);
+    }
+
+    #[test]
+    fn test_next_and_previous_active() -> Result<()> {
+        let mut list = LocationsList::new();
+        list.update(Action::GetAirRaidAlertOblastStatuses(
+            AirRaidAlertOblastStatuses::new(
+                "NNNNNNNNNANNANNNNNNNNNNNNNN".to_string(),
+                Some(false),
+            ),
+        ))?;
+
+        let action = list.update(Action::NextActive)?;
+        assert_eq!(action, Some(Action::SelectLocationByUid(Some(31))));
+        let action = list.update(Action::NextActive)?;
+        assert_eq!(action, Some(Action::SelectLocationByUid(Some(16))));
+        let action = list.update(Action::NextActive)?;
+        assert_eq!(action, Some(Action::SelectLocationByUid(Some(31))));
+        let action = list.update(Action::PrevActive)?;
+        assert_eq!(action, Some(Action::SelectLocationByUid(Some(16))));
+
+        // no active locations, selection is kept
+        list.update(Action::GetAirRaidAlertOblastStatuses(
+            AirRaidAlertOblastStatuses::new("N".repeat(27), Some(false)),
+        ))?;
+        assert_eq!(list.update(Action::NextActive)?, None);
+        assert_eq!(list.selected().map(|s| s.location_uid), Some(16));
+        Ok(())
+    }
+
+    #[test]
+    fn test_location_title_by_locale() {
+        let statuses = AirRaidAlertOblastStatuses::new("N".repeat(27), None);
+        let kyiv = statuses.get_by_location_uid(31).unwrap();
+
+        assert_eq!(location_title_by_locale(&kyiv, &language!("uk")), "Київ");
+        assert_eq!(location_title_by_locale(&kyiv, &language!("en")), "Kyiv");
+    }
+
+    #[test]
+    fn test_search_filters_by_name() -> Result<()> {
+        let mut list = LocationsList::new();
+        for c in "kyiv".chars() {
+            list.update(Action::SearchInput(c))?;
+        }
+        let names: Vec<String> = list
+            .filtered_statuses()
+            .iter()
+            .map(|os| os.location_title_en().to_string())
+            .collect();
+        assert_eq!(names, vec!["Kyiv", "Kyiv Oblast"]);
+
+        list.update(Action::SearchClear)?;
+        list.update(Action::SearchInput('Л'))?;
+        list.update(Action::SearchInput('ь'))?;
+        list.update(Action::SearchInput('в'))?;
+        let names: Vec<String> = list
+            .filtered_statuses()
+            .iter()
+            .map(|os| os.location_title_en().to_string())
+            .collect();
+        assert_eq!(names, vec!["Lviv Oblast"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_alert_type_filter_hides_excluded_alerts() -> Result<()> {
+        let alert = |uid: i32, alert_type: AlertType| -> Alert {
+            AlertBuilder::new(uid).with_alert_type(alert_type).build()
+        };
+        let mut list = LocationsList::new();
+        // "Київ" and "Луганська область" are active
+        list.update(Action::GetAirRaidAlertOblastStatuses(
+            AirRaidAlertOblastStatuses::new(
+                "NNNNNNNNNANNANNNNNNNNNNNNNN".to_string(),
+                Some(false),
+            ),
+        ))?;
+        list.update(Action::GetActiveAlerts(Alerts::from(vec![
+            alert(31, AlertType::AirRaid),
+            alert(16, AlertType::ArtilleryShelling),
+        ])))?;
+        list.update(Action::SelectLocationByUid(Some(16)))?;
+        let active_uids = |list: &LocationsList| -> Vec<i32> {
+            list.oblast_statuses()
+                .get_active_alert_oblasts()
+                .iter()
+                .map(|os| os.location_uid)
+                .collect()
+        };
+        assert_eq!(active_uids(&list), vec![31, 16]);
+
+        list.update(Action::CycleAlertTypeFilter)?;
+        assert_eq!(list.alert_type_filter(), &AlertTypeFilter::AirRaid);
+        assert_eq!(active_uids(&list), vec![31]);
+        // selection is kept
+        assert_eq!(list.selected().map(|s| s.location_uid), Some(16));
+
+        list.update(Action::CycleAlertTypeFilter)?;
+        assert_eq!(active_uids(&list), vec![16]);
+
+        list.update(Action::CycleAlertTypeFilter)?;
+        assert_eq!(active_uids(&list), vec![31, 16]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_sort_modes() -> Result<()> {
+        let mut list = LocationsList::new();
+        // "Вінницька область" partly active, "Київ" and "Луганська область" active
+        list.update(Action::GetAirRaidAlertOblastStatuses(
+            AirRaidAlertOblastStatuses::new(
+                "NPNNNNNNNANNANNNNNNNNNNNNNN".to_string(),
+                Some(false),
+            ),
+        ))?;
+        list.update(Action::SelectLocationByUid(Some(16)))?;
+        let first_uids = |list: &LocationsList| -> Vec<i32> {
+            list.filtered_statuses()
+                .iter()
+                .take(4)
+                .map(|os| os.location_uid)
+                .collect()
+        };
+        // by id, as fetched
+        assert_eq!(first_uids(&list), vec![29, 8, 4, 9]);
+
+        list.update(Action::CycleSort)?;
+        assert_eq!(list.sort_mode(), &SortMode::Name);
+        let names: Vec<String> = list
+            .filtered_statuses()
+            .iter()
+            .map(|os| location_title_by_locale(os, &list.config.get_locale()).to_string())
+            .collect();
+        let mut sorted = names.clone();
+        sorted.sort_by_key_icu(|n| n.clone(), list.config.get_locale().as_str());
+        assert_eq!(names, sorted);
+        assert_eq!(list.selected().map(|s| s.location_uid), Some(16));
+
+        list.update(Action::CycleSort)?;
+        assert_eq!(list.sort_mode(), &SortMode::Status);
+        // active first, stable within same status
+        assert_eq!(first_uids(&list), vec![31, 16, 8, 29]);
+        assert_eq!(list.selected().map(|s| s.location_uid), Some(16));
+        assert_eq!(list.state().selected(), Some(1));
+
+        list.update(Action::CycleSort)?;
+        assert_eq!(list.sort_mode(), &SortMode::Default);
+        assert_eq!(first_uids(&list), vec![29, 8, 4, 9]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_selection_survives_refresh() -> Result<()> {
+        let statuses = |data: &str| {
+            Action::GetAirRaidAlertOblastStatuses(AirRaidAlertOblastStatuses::new(
+                data.to_string(),
+                Some(false),
+            ))
+        };
+        let mut list = LocationsList::new();
+        list.update(statuses("NNNNNNNNNANNNNNNNNNNNNNNNNN"))?;
+        list.update(Action::SelectLocationByUid(Some(16)))?;
+        assert_eq!(list.state().selected(), Some(12));
+
+        // same order
+        list.update(Action::Refresh)?;
+        list.update(statuses("NNNNNNNNNANNNNNNNNNNNNNNNNN"))?;
+        assert_eq!(list.selected().map(|s| s.location_uid), Some(16));
+        assert_eq!(list.state().selected(), Some(12));
+
+        // sorted by status, new active location moves selected one
+        list.update(Action::CycleSort)?;
+        list.update(Action::CycleSort)?;
+        assert_eq!(list.state().selected(), Some(12));
+        list.update(Action::Refresh)?;
+        list.update(statuses("NNNNNNNNNANNNNNNNNNNANNNNNN"))?;
+        assert_eq!(list.selected().map(|s| s.location_uid), Some(16));
+        assert_eq!(list.state().selected(), Some(13));
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_clear_restores_list() -> Result<()> {
+        let mut list = LocationsList::new();
+        list.update(Action::SearchInput('x'))?;
+        list.update(Action::SearchInput('y'))?;
+        list.update(Action::SearchInput('z'))?;
+        assert!(list.filtered_statuses().is_empty());
+
+        list.update(Action::SearchClear)?;
+        assert_eq!(list.query(), "");
+        assert_eq!(list.filtered_statuses().len(), 27);
+        Ok(())
+    }
+
+    #[test]
+    fn test_select_navigation() -> Result<()> {
+        let mut list = LocationsList::new();
+
+        let action = list.update(Action::SelectNext)?;
+        assert_eq!(action, Some(Action::SelectLocationByUid(Some(29))));
+        let action = list.update(Action::SelectNext)?;
+        assert_eq!(action, Some(Action::SelectLocationByUid(Some(8))));
+        let action = list.update(Action::SelectPrevious)?;
+        assert_eq!(action, Some(Action::SelectLocationByUid(Some(29))));
+        let action = list.update(Action::Unselect)?;
+        assert_eq!(action, Some(Action::SelectLocationByUid(None)));
+        assert_eq!(list.selected(), None);
+
+        let action = list.update(Action::SelectBottom)?;
+        assert_eq!(action, Some(Action::SelectLocationByUid(Some(25))));
+        let action = list.update(Action::SelectTop)?;
+        assert_eq!(action, Some(Action::SelectLocationByUid(Some(29))));
+        Ok(())
+    }
 }
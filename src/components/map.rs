@@ -1,4 +1,5 @@
-use geo::Rect as GeoRect;
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use geo::{Contains, Coord, Rect as GeoRect};
 use ralertsinua_geo::*;
 use ralertsinua_models::*;
 use ratatui::widgets::canvas::Canvas;
@@ -10,8 +11,15 @@ use tokio::sync::mpsc::UnboundedSender;
 use tracing::debug;
 // use tui_popup::Popup;
 
-use super::{Component, Frame, Result, WithPlacement};
-use crate::{action::*, config::*, layout::*, tui_helpers::*};
+use super::{AlertTypeFilter, Component, Frame, Result, WithPlacement};
+use crate::{action::*, config::*, layout::*, theme::*, tui_helpers::*};
+
+/// Zoom factor per single zoom in/out
+const ZOOM_STEP: f64 = 2.0;
+/// Maximum zoom, relative to full country view
+const MAX_ZOOM: f64 = 16.0;
+/// Pan distance, as share of visible width/height
+const PAN_STEP: f64 = 0.1;
 
 #[derive(Debug)]
 pub struct Map<'a> {
@@ -23,14 +31,27 @@ pub struct Map<'a> {
     config: Config,
     bounding_rect: GeoRect,
     boundary: CountryBoundary,
+    /// Boundary simplified for current grid size, what is actually drawn
+    boundary_simplified: CountryBoundary,
     locations: [Location; 27],
     selected_location_uid: i32,
+    /// Statuses as displayed, i.e. with alert type filter applied
     oblast_statuses: AirRaidAlertOblastStatuses,
+    /// Statuses as fetched
+    fetched_statuses: AirRaidAlertOblastStatuses,
+    alert_type_filter: AlertTypeFilter,
     alerts: Alerts,
     //
     width: u16,
     height: u16,
     resolution: (f64, f64),
+    theme: Theme,
+    /// Area of last draw, to map mouse clicks to coordinates
+    area: Rect,
+    /// Zoom relative to full country view, 1.0 shows whole country
+    zoom: f64,
+    /// Center of visible part of the map
+    center: Coord,
 }
 
 impl<'a> Map<'a> {
@@ -42,15 +63,22 @@ impl<'a> Map<'a> {
             title: Line::default(),
             config: Config::default(),
             boundary: CountryBoundary::default(),
+            boundary_simplified: CountryBoundary::default(),
             bounding_rect: *UKRAINE_BBOX,
             locations: core::array::from_fn(|_| Location::default()),
             selected_location_uid: -1,
             oblast_statuses: AirRaidAlertOblastStatuses::default(),
+            fetched_statuses: AirRaidAlertOblastStatuses::default(),
+            alert_type_filter: AlertTypeFilter::default(),
             alerts: Alerts::default(),
             //
             width: 0,
             height: 0,
             resolution: (0.0, 0.0),
+            theme: Theme::default(),
+            area: Rect::default(),
+            zoom: 1.0,
+            center: UKRAINE_BBOX.center(),
         }
     }
 
@@ -62,6 +90,71 @@ impl<'a> Map<'a> {
         debug!(target:"app", "Map grid size: width: {}, height: {}, x_Y_bounds: {:?}, resolution: {:?}", width, height, self.get_x_y_bounds(), self.resolution);
     }
 
+    pub fn zoom(&self) -> f64 {
+        self.zoom
+    }
+
+    /// Change zoom by `factor` around current center, clamped to [1.0, MAX_ZOOM]
+    pub fn zoom_by(&mut self, factor: f64) {
+        self.zoom = (self.zoom * factor).clamp(1.0, MAX_ZOOM);
+        self.set_view();
+    }
+
+    /// Move center by `dx`/`dy` pan steps, east and north are positive
+    pub fn pan(&mut self, dx: i8, dy: i8) {
+        let (width, height) = (self.bounding_rect.width(), self.bounding_rect.height());
+        self.center.x += f64::from(dx) * PAN_STEP * width;
+        self.center.y += f64::from(dy) * PAN_STEP * height;
+        self.set_view();
+    }
+
+    /// Show bounding rect of selected location, zoom is kept in sync for further zooming
+    pub fn zoom_to_selected(&mut self) {
+        let Some(location) = self.get_selected_location() else {
+            return;
+        };
+        let rect = location.bounding_rect();
+        let zoom_x = UKRAINE_BBOX.width() / rect.width();
+        let zoom_y = UKRAINE_BBOX.height() / rect.height();
+        self.zoom = zoom_x.min(zoom_y).clamp(1.0, MAX_ZOOM);
+        self.center = rect.center();
+        self.bounding_rect = rect;
+        self.set_boundary_simplified();
+    }
+
+    /// Back to full country view
+    pub fn zoom_reset(&mut self) {
+        self.zoom = 1.0;
+        self.center = UKRAINE_BBOX.center();
+        self.set_view();
+    }
+
+    /// Bounds extended so that longitude degrees, scaled by cosine of center latitude,
+    /// and latitude degrees take equal number of dots at given canvas `resolution`
+    pub fn get_proportional_x_y_bounds(&self, resolution: (f64, f64)) -> XY_Bounds {
+        get_proportional_bounds(self.get_x_y_bounds(), resolution)
+    }
+
+    /// Update visible rect from zoom and center, keeping it within country bounds
+    fn set_view(&mut self) {
+        self.bounding_rect = get_view_rect(*UKRAINE_BBOX, self.center, self.zoom);
+        self.center = self.bounding_rect.center();
+        self.set_boundary_simplified();
+    }
+
+    /// Drop boundary points closer than one braille dot, they are not visible anyway
+    #[inline]
+    pub fn set_boundary_simplified(&mut self) {
+        let ([x_min, x_max], _) = self.get_x_y_bounds();
+        self.boundary_simplified = if self.resolution.0 > 0.0 {
+            let epsilon = (x_max - x_min) / self.resolution.0;
+            self.boundary.simplify(epsilon)
+        } else {
+            self.boundary.clone()
+        };
+        debug!(target:"app", "Map boundary simplified: {} of {} coords", self.boundary_simplified.coords_count(), self.boundary.coords_count());
+    }
+
     #[inline]
     pub fn get_location_by<P>(&self, mut predicate: P) -> Option<Location>
     where
@@ -70,6 +163,12 @@ impl<'a> Map<'a> {
         self.locations.iter().find(|r| predicate(r)).cloned()
     }
 
+    /// Location containing given geographic coordinate, if any
+    #[inline]
+    pub fn get_location_at(&self, coord: Coord) -> Option<Location> {
+        self.get_location_by(|l| l.geometry().contains(&coord))
+    }
+
     #[inline]
     pub fn get_selected_location(&self) -> Option<Location> {
         self.get_location_by(|l| l.location_uid == self.selected_location_uid)
@@ -81,13 +180,140 @@ impl<'a> Map<'a> {
             .get_by_location_uid(self.selected_location_uid)
     }
 
+    /// Markers at the centroid of each location with active alert
+    pub fn get_alert_markers(&self) -> Vec<PointMarker> {
+        self.locations
+            .iter()
+            .filter(|l| {
+                self.oblast_statuses
+                    .get_by_location_uid(l.location_uid)
+                    .is_some_and(|os| os.is_active_on_all_oblast())
+            })
+            .map(|l| PointMarker::new(l.centroid(), self.theme.alert))
+            .collect()
+    }
+
+    /// Full alerts as fetched, with details like `notes` and timestamps
+    #[inline]
+    pub fn alerts(&self) -> &Alerts {
+        &self.alerts
+    }
+
+    /// Store full alerts, used for details of selected location
+    #[inline]
+    pub fn set_alerts(&mut self, alerts: Alerts) {
+        self.alerts = alerts;
+        self.apply_alert_type_filter();
+    }
+
+    /// Store per-oblast statuses, used for status icons and alert markers
+    #[inline]
+    pub fn set_oblast_statuses(&mut self, oblast_statuses: AirRaidAlertOblastStatuses) {
+        self.fetched_statuses = oblast_statuses;
+        self.apply_alert_type_filter();
+    }
+
+    /// Show only alerts of type selected by `filter`
+    pub fn set_alert_type_filter(&mut self, filter: AlertTypeFilter) {
+        self.alert_type_filter = filter;
+        self.apply_alert_type_filter();
+    }
+
+    fn apply_alert_type_filter(&mut self) {
+        self.oblast_statuses = self
+            .alert_type_filter
+            .apply(&self.fetched_statuses, &self.alerts);
+    }
+
+    /// Alert shown for selected oblast, the one chosen among all alerts within it
+    /// by [`Alerts::get_most_severe_alerts`]
     #[inline]
     pub fn get_selected_alert(&self) -> Option<Alert> {
-        self.alerts
-            .get_alerts_by_location_uid(self.selected_location_uid)
-            .first()
-            .cloned()
+        Alerts::from(
+            self.alerts
+                .get_alerts_by_oblast_uid(self.selected_location_uid),
+        )
+        .get_most_severe_alerts()
+        .into_iter()
+        .next()
+    }
+}
+
+/// Braille dots of canvas drawn within bordered `area`
+#[inline]
+pub fn get_canvas_resolution(area: Rect) -> (f64, f64) {
+    let inner = area.inner(&Margin::new(1, 1));
+    (f64::from(inner.width) * 2.0, f64::from(inner.height) * 4.0)
+}
+
+/// Extend `bounds` around their center to make them geographically proportional at given
+/// `resolution`, i.e. one dot covers the same distance horizontally and vertically
+pub fn get_proportional_bounds(bounds: XY_Bounds, resolution: (f64, f64)) -> XY_Bounds {
+    let ([x_min, x_max], [y_min, y_max]) = bounds;
+    let (res_x, res_y) = resolution;
+    if res_x <= 0.0 || res_y <= 0.0 {
+        return bounds;
+    }
+    let (center_x, center_y) = ((x_min + x_max) / 2.0, (y_min + y_max) / 2.0);
+    // one degree of longitude is shorter than one of latitude, by cos(latitude)
+    let lon_scale = center_y.to_radians().cos();
+    let (width, height) = ((x_max - x_min) * lon_scale, y_max - y_min);
+    let (width, height) = if width / height < res_x / res_y {
+        (height * res_x / res_y, height)
+    } else {
+        (width, width * res_y / res_x)
+    };
+    let (half_x, half_y) = (width / lon_scale / 2.0, height / 2.0);
+    (
+        [center_x - half_x, center_x + half_x],
+        [center_y - half_y, center_y + half_y],
+    )
+}
+
+/// Part of `bbox` scaled down by `zoom` around `center`, shifted to stay inside of `bbox`.
+/// Width to height ratio of `bbox` is kept, so map is not stretched on zoom
+pub fn get_view_rect(bbox: GeoRect, center: Coord, zoom: f64) -> GeoRect {
+    if zoom <= 1.0 {
+        return bbox;
     }
+    let half_width = bbox.width() / zoom / 2.0;
+    let half_height = bbox.height() / zoom / 2.0;
+    let x = center
+        .x
+        .clamp(bbox.min().x + half_width, bbox.max().x - half_width);
+    let y = center
+        .y
+        .clamp(bbox.min().y + half_height, bbox.max().y - half_height);
+    GeoRect::new(
+        Coord {
+            x: x - half_width,
+            y: y - half_height,
+        },
+        Coord {
+            x: x + half_width,
+            y: y + half_height,
+        },
+    )
+}
+
+/// Geographic coordinate at the center of terminal cell within bordered canvas `area`,
+/// i.e. inverse of canvas transform for given bounds
+pub fn get_coord_at(
+    area: Rect,
+    x_bounds: [f64; 2],
+    y_bounds: [f64; 2],
+    column: u16,
+    row: u16,
+) -> Option<Coord> {
+    let (x, y) = get_inner_position(area, column, row)?;
+    let inner = area.inner(&Margin::new(1, 1));
+    let dx = (f64::from(x) + 0.5) / f64::from(inner.width);
+    let dy = (f64::from(y) + 0.5) / f64::from(inner.height);
+    Some(Coord {
+        x: x_bounds[0] + dx * (x_bounds[1] - x_bounds[0]),
+        // canvas y axis goes up, terminal rows go down
+        y: y_bounds[1] - dy * (y_bounds[1] - y_bounds[0]),
+    })
 }
 
 impl WithPlacement<'_> for Map<'_> {
@@ -107,6 +333,7 @@ impl WithBoundingRect for Map<'_> {
 impl<'a> Component<'a> for Map<'a> {
     fn init(&mut self, r: Rect) -> Result<()> {
         self.set_grid_size(r.width, r.height);
+        self.set_boundary_simplified();
         Ok(())
     }
 
@@ -120,21 +347,57 @@ impl<'a> Component<'a> for Map<'a> {
         Ok(())
     }
 
+    fn register_theme_handler(&mut self, theme: Theme) -> Result<()> {
+        self.theme = theme;
+        Ok(())
+    }
+
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) -> Result<Option<Action>> {
+        if mouse.kind != MouseEventKind::Down(MouseButton::Left) {
+            return Ok(None);
+        }
+        let resolution = get_canvas_resolution(self.area);
+        let (x_bounds, y_bounds) = self.get_proportional_x_y_bounds(resolution);
+        let location = get_coord_at(self.area, x_bounds, y_bounds, mouse.column, mouse.row)
+            .and_then(|coord| self.get_location_at(coord));
+        // clicks outside of borders keep current selection
+        Ok(location.map(|l| Action::SelectLocationByUid(Some(l.location_uid as usize))))
+    }
+
     fn update(&mut self, action: Action) -> Result<Option<Action>> {
         match action {
             Action::Tick => {}
-            Action::Resize(width, heith) => self.set_grid_size(width, heith),
+            Action::SelectTab(_) => {
+                // not drawn on other tabs, ignore clicks until drawn again
+                self.area = Rect::default();
+            }
+            Action::ZoomIn => self.zoom_by(ZOOM_STEP),
+            Action::ZoomOut => self.zoom_by(1.0 / ZOOM_STEP),
+            Action::CycleAlertTypeFilter => {
+                self.set_alert_type_filter(self.alert_type_filter.next());
+            }
+            Action::ZoomToSelected => self.zoom_to_selected(),
+            Action::ZoomReset => self.zoom_reset(),
+            Action::PanLeft => self.pan(-1, 0),
+            Action::PanRight => self.pan(1, 0),
+            Action::PanUp => self.pan(0, 1),
+            Action::PanDown => self.pan(0, -1),
+            Action::Resize(width, heith) => {
+                self.set_grid_size(width, heith);
+                self.set_boundary_simplified();
+            }
             Action::GetBoundaries(boundary) => {
                 self.boundary = boundary;
+                self.set_boundary_simplified();
             }
             Action::GetLocations(locations) => {
-                self.locations = locations;
+                self.locations = *locations;
             }
             Action::GetAirRaidAlertOblastStatuses(data) => {
-                self.oblast_statuses = data;
+                self.set_oblast_statuses(data);
             }
             Action::GetActiveAlerts(data) => {
-                self.alerts = data;
+                self.set_alerts(data);
             }
             Action::SelectLocationByUid(a) => match a {
                 Some(location_uid) => {
@@ -158,13 +421,22 @@ impl<'a> Component<'a> for Map<'a> {
     }
 
     fn draw(&mut self, f: &mut Frame) -> Result<()> {
-        let size: Rect = f.size();
-        let area: Rect = self.get_area(size)?;
-        let (x_bounds, y_bounds) = self.get_x_y_bounds();
+        let area: Rect = self.get_area(f.size())?;
+        self.draw_in(f, area)
+    }
+
+    fn draw_in(&mut self, f: &mut Frame, area: Rect) -> Result<()> {
+        self.area = area;
+        let resolution = get_canvas_resolution(area);
+        let (x_bounds, y_bounds) = self.get_proportional_x_y_bounds(resolution);
         let selected_location = self.get_selected_location();
         let selected_alert_status = self.get_selected_alert_status();
         let selected_alert = self.get_selected_alert();
+        let alert_markers = self.get_alert_markers();
         let title = self.title.clone();
+        let center_color = self.theme.center;
+        let no_color = self.theme.no_color;
+        let highlight_color = self.theme.selected;
         let widget = Canvas::default()
             .block(Block::bordered().title(title))
             .marker(Marker::Braille)
@@ -172,7 +444,11 @@ impl<'a> Component<'a> for Map<'a> {
             .y_bounds(y_bounds)
             .paint(move |ctx| {
                 //  Draw country borders with ctx
-                ctx.draw(&self.boundary);
+                ctx.draw(&self.boundary_simplified);
+                // Mark country center with ctx
+                ctx.draw(&PointMarker::new(*UKRAINE_CENTER, center_color));
+                // Mark locations with active alert with ctx
+                alert_markers.iter().for_each(|m| ctx.draw(m));
 
                 // Draw & Print selected location with ctx
                 self.locations.iter().for_each(|l| {
@@ -192,9 +468,17 @@ impl<'a> Component<'a> for Map<'a> {
                         .unwrap()
                         .status();
                     let is_selected = (l.location_uid) == self.selected_location_uid;
-                    let line = get_styled_line_icon_by_status(status, &is_selected);
+                    let line = if no_color {
+                        Line::from(get_marker_by_status(status)).bold()
+                    } else {
+                        get_styled_line_icon_by_status(status, &is_selected)
+                    };
                     ctx.print(x, y, line);
                 });
+                // Outline selected location on top of others
+                if let Some(l) = selected_location.as_ref() {
+                    ctx.draw(&LocationHighlight::new(l, highlight_color));
+                }
             })
             .background_color(Color::Reset);
         f.render_widget(widget, area);
@@ -210,7 +494,9 @@ impl<'a> Component<'a> for Map<'a> {
 
         // popup
         if let Some(sas) = selected_alert_status {
-            popup_bg = get_color_by_status(sas.status());
+            if !no_color {
+                popup_bg = get_color_by_status(sas.status());
+            }
             lines = vec![
                 sas.location_title_en().to_string().into(),
                 sas.status().to_string().into(),
@@ -236,17 +522,330 @@ impl<'a> Component<'a> for Map<'a> {
     }
 }
 
-/* #[cfg(test)]
+#[cfg(test)]
 mod tests {
     use super::*;
-    use geo::HasDimensions;
+    use crate::constants::ALERT_ROW_COLOR;
+    use ralertsinua_models::test_util::AlertBuilder;
+    use ratatui::backend::TestBackend;
+    use time::macros::datetime;
+
+    #[test]
+    fn test_get_alert_markers() {
+        let geo_client = AlertsInUaGeoClient::default();
+        let mut map = Map::new();
+        map.locations = geo_client.locations();
+        // only "Київ" (index 9, location_uid 31) is active
+        map.oblast_statuses = AirRaidAlertOblastStatuses::new(
+            "NNNNNNNNNANNNNNNNNNNNNNNNNN".to_string(),
+            Some(false),
+        );
+
+        let markers = map.get_alert_markers();
+
+        let kyiv = geo_client.get_location_by_uid(31).unwrap();
+        let centroid = kyiv.centroid();
+        assert_eq!(markers.len(), 1);
+        assert_eq!(markers[0].color, *ALERT_ROW_COLOR);
+        assert!((markers[0].point.x() - centroid.x()).abs() < f64::EPSILON);
+        assert!((markers[0].point.y() - centroid.y()).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_get_coord_at() {
+        let area = Rect::new(0, 1, 102, 22);
+        let (x_bounds, y_bounds) = ([20.0, 40.0], [40.0, 54.0]);
+
+        // top left cell of canvas is north-west
+        let coord = get_coord_at(area, x_bounds, y_bounds, 1, 2).unwrap();
+        assert!((coord.x - 20.1).abs() < 1e-9);
+        assert!((coord.y - 53.65).abs() < 1e-9);
+        // bottom right cell of canvas is south-east
+        let coord = get_coord_at(area, x_bounds, y_bounds, 100, 21).unwrap();
+        assert!((coord.x - 39.9).abs() < 1e-9);
+        assert!((coord.y - 40.35).abs() < 1e-9);
+        // border and outside of canvas
+        assert_eq!(get_coord_at(area, x_bounds, y_bounds, 0, 5), None);
+        assert_eq!(get_coord_at(area, x_bounds, y_bounds, 50, 30), None);
+    }
+
+    fn count_cells_with_fg(map: &mut Map, color: Color) -> usize {
+        let mut terminal = Terminal::new(TestBackend::new(120, 30)).unwrap();
+        terminal.draw(|f| map.draw(f).unwrap()).unwrap();
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .filter(|c| c.fg == color && c.symbol() != " ")
+            .count()
+    }
+
+    #[test]
+    fn test_selected_location_highlight() -> Result<()> {
+        let geo_client = AlertsInUaGeoClient::default();
+        let highlight = Color::Rgb(1, 2, 3);
+        let mut map = Map::new();
+        map.register_theme_handler(Theme {
+            selected: highlight,
+            ..Theme::default()
+        })?;
+        map.update(Action::GetLocations(Box::new(geo_client.locations())))?;
+        map.update(Action::GetAirRaidAlertOblastStatuses(
+            AirRaidAlertOblastStatuses::new("N".repeat(27), Some(false)),
+        ))?;
+
+        let unselected = count_cells_with_fg(&mut map, highlight);
+        map.update(Action::SelectLocationByUid(Some(31)))?;
+        let selected = count_cells_with_fg(&mut map, highlight);
+
+        assert_eq!(unselected, 0);
+        assert!(selected > 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_view_rect() {
+        let bbox = GeoRect::new(Coord { x: 0.0, y: 0.0 }, Coord { x: 20.0, y: 10.0 });
+
+        let full = get_view_rect(bbox, bbox.center(), 1.0);
+        let zoomed = get_view_rect(bbox, bbox.center(), 2.0);
+        let zoomed_at_corner = get_view_rect(bbox, Coord { x: 1.0, y: 1.0 }, 2.0);
+
+        assert_eq!(full, bbox);
+        assert_eq!(zoomed.min(), Coord { x: 5.0, y: 2.5 });
+        assert_eq!(zoomed.max(), Coord { x: 15.0, y: 7.5 });
+        // aspect ratio is kept
+        assert_eq!(
+            zoomed.width() / zoomed.height(),
+            bbox.width() / bbox.height()
+        );
+        // shifted to stay inside of bbox
+        assert_eq!(zoomed_at_corner.min(), Coord { x: 0.0, y: 0.0 });
+        assert_eq!(zoomed_at_corner.max(), Coord { x: 10.0, y: 5.0 });
+    }
+
+    #[test]
+    fn test_zoom_and_pan() -> Result<()> {
+        let mut map = Map::new();
+        let full_width = map.bounding_rect.width();
+
+        map.update(Action::ZoomOut)?;
+        assert_eq!(map.zoom(), 1.0);
+        map.update(Action::PanRight)?;
+        assert_eq!(map.bounding_rect, *UKRAINE_BBOX);
+
+        map.update(Action::ZoomIn)?;
+        assert_eq!(map.zoom(), ZOOM_STEP);
+        assert!((map.bounding_rect.width() - full_width / ZOOM_STEP).abs() < 1e-9);
+        let center = map.bounding_rect.center();
+        map.update(Action::PanRight)?;
+        map.update(Action::PanDown)?;
+        let panned = map.bounding_rect.center();
+        assert!(panned.x > center.x);
+        assert!(panned.y < center.y);
+
+        for _ in 0..10 {
+            map.update(Action::ZoomIn)?;
+        }
+        assert_eq!(map.zoom(), MAX_ZOOM);
+        Ok(())
+    }
+
+    #[test]
+    fn test_zoom_to_selected() -> Result<()> {
+        let geo_client = AlertsInUaGeoClient::default();
+        let mut map = Map::new();
+        map.update(Action::GetLocations(Box::new(geo_client.locations())))?;
+
+        // nothing selected
+        map.update(Action::ZoomToSelected)?;
+        assert_eq!(map.bounding_rect, *UKRAINE_BBOX);
+
+        map.update(Action::SelectLocationByUid(Some(14)))?;
+        map.update(Action::ZoomToSelected)?;
+        let rect = geo_client.get_location_by_uid(14).unwrap().bounding_rect();
+        let ([x_min, x_max], [y_min, y_max]) = map.get_x_y_bounds();
+        assert_eq!(x_min, rect.min().x - 0.5);
+        assert_eq!(x_max, rect.max().x + 0.5);
+        assert_eq!(y_min, rect.min().y - 0.5);
+        assert_eq!(y_max, rect.max().y + 0.5);
+        assert!(map.zoom() > 1.0);
+
+        map.update(Action::ZoomReset)?;
+        assert_eq!(map.zoom(), 1.0);
+        assert_eq!(map.bounding_rect, *UKRAINE_BBOX);
+        Ok(())
+    }
 
     #[test]
-    fn test_map_new() {
-        let map = Map::new(Ukraine::new_arc(), Arc::new(Config::init().unwrap()));
-        assert!(map.command_tx.is_none());
-        assert!(!map.map.boundary().is_empty());
-        assert!(map.ukraine.read().unwrap().locations().is_empty());
-        // match map.boundary.try_from()
-    }
-} */
+    fn test_get_proportional_bounds() {
+        let map = Map::new();
+        // square dots, twice as wide as high
+        let resolution = (200.0, 100.0);
+        let km_aspect_ratio = |([x0, x1], [y0, y1]): XY_Bounds| {
+            (x1 - x0) * ((y0 + y1) / 2.0_f64).to_radians().cos() / (y1 - y0)
+        };
+
+        let uncorrected = map.get_x_y_bounds();
+        let corrected = map.get_proportional_x_y_bounds(resolution);
+
+        assert!((km_aspect_ratio(uncorrected) - 2.0).abs() > 0.1);
+        assert!((km_aspect_ratio(corrected) - 2.0).abs() < 1e-9);
+        // bounds are only extended, whole country is still visible
+        assert!(corrected.0[0] <= uncorrected.0[0] && corrected.0[1] >= uncorrected.0[1]);
+        assert!(corrected.1[0] <= uncorrected.1[0] && corrected.1[1] >= uncorrected.1[1]);
+        // nothing to correct without resolution
+        assert_eq!(map.get_proportional_x_y_bounds((0.0, 0.0)), uncorrected);
+    }
+
+    #[test]
+    fn test_get_location_at() {
+        let mut map = Map::new();
+        map.locations = AlertsInUaGeoClient::default().locations();
+
+        let kyiv = map.get_location_at(Coord { x: 30.52, y: 50.45 });
+        let black_sea = map.get_location_at(Coord { x: 31.0, y: 44.5 });
+
+        assert_eq!(kyiv.map(|l| l.location_uid), Some(31));
+        assert!(black_sea.is_none());
+    }
+
+    #[test]
+    fn test_set_boundary_simplified() {
+        let geo_client = AlertsInUaGeoClient::default();
+        let mut map = Map::new();
+        map.boundary = geo_client.boundary();
+
+        map.set_grid_size(40, 10);
+        map.set_boundary_simplified();
+        let small = map.boundary_simplified.coords_count();
+        map.set_grid_size(400, 100);
+        map.set_boundary_simplified();
+        let large = map.boundary_simplified.coords_count();
+
+        assert!(small < large);
+        assert!(large <= map.boundary.coords_count());
+    }
+
+    #[test]
+    fn test_alerts_survive_statuses_update() -> Result<()> {
+        let alert = AlertBuilder::new(31)
+            .with_location(LocationType::City, 31)
+            .build();
+        let mut map = Map::new();
+
+        map.update(Action::GetActiveAlerts(Alerts::from(vec![alert.clone()])))?;
+        map.update(Action::GetAirRaidAlertOblastStatuses(
+            AirRaidAlertOblastStatuses::new(
+                "NNNNNNNNNANNNNNNNNNNNNNNNNN".to_string(),
+                Some(false),
+            ),
+        ))?;
+        map.update(Action::SelectLocationByUid(Some(31)))?;
+
+        assert_eq!(map.alerts().len(), 1);
+        assert_eq!(map.get_selected_alert(), Some(alert));
+        Ok(())
+    }
+
+    #[test]
+    fn test_selected_alert_prefers_whole_oblast() {
+        let hromada_alert = AlertBuilder::new(22)
+            .with_id(2)
+            .with_location(LocationType::Hromada, 1293)
+            .with_updated_at(datetime!(2024-05-05 16:48:31 UTC))
+            .build();
+        let mut alerts = test_alerts(&[(22, AlertType::ArtilleryShelling)]);
+        alerts.append_page(Alerts::from(vec![hromada_alert]));
+        let mut map = Map::new();
+        map.set_alerts(alerts);
+        map.selected_location_uid = 22;
+
+        // artillery shelling covers the whole oblast, air raid only a hromada
+        let selected = map.get_selected_alert().unwrap();
+        assert_eq!(selected.id, 22);
+        assert_eq!(selected.alert_type, AlertType::ArtilleryShelling);
+    }
+
+    #[test]
+    fn test_alert_type_filter_hides_markers() -> Result<()> {
+        let geo_client = AlertsInUaGeoClient::default();
+        let mut map = Map::new();
+        map.locations = geo_client.locations();
+        // "Київ" and "Луганська область" are active
+        map.set_oblast_statuses(AirRaidAlertOblastStatuses::new(
+            "NNNNNNNNNANNANNNNNNNNNNNNNN".to_string(),
+            Some(false),
+        ));
+        map.set_alerts(test_alerts(&[
+            (31, AlertType::AirRaid),
+            (16, AlertType::ArtilleryShelling),
+        ]));
+        assert_eq!(map.get_alert_markers().len(), 2);
+
+        // air raid only
+        map.update(Action::CycleAlertTypeFilter)?;
+        assert_eq!(map.get_alert_markers().len(), 1);
+        assert!(map
+            .oblast_statuses
+            .get_by_location_uid(16)
+            .unwrap()
+            .is_no_alert());
+
+        // artillery only
+        map.update(Action::CycleAlertTypeFilter)?;
+        assert_eq!(map.get_alert_markers().len(), 1);
+        assert!(map
+            .oblast_statuses
+            .get_by_location_uid(31)
+            .unwrap()
+            .is_no_alert());
+
+        map.update(Action::CycleAlertTypeFilter)?;
+        assert_eq!(map.get_alert_markers().len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_oblast_statuses_keeps_alerts() {
+        let mut map = Map::new();
+        map.set_oblast_statuses(AirRaidAlertOblastStatuses::new(
+            "NNNNNNNNNANNNNNNNNNNNNNNNNN".to_string(),
+            Some(false),
+        ));
+        map.selected_location_uid = 31;
+
+        assert!(map
+            .get_selected_alert_status()
+            .unwrap()
+            .is_active_on_all_oblast());
+        assert!(map.alerts().is_empty());
+    }
+
+    #[test]
+    fn test_set_alerts_keeps_oblast_statuses() {
+        let mut map = Map::new();
+        map.set_alerts(Alerts::default());
+        map.selected_location_uid = 31;
+
+        assert_eq!(map.get_selected_alert(), None);
+        assert_eq!(
+            map.get_selected_alert_status().unwrap().status(),
+            &AlertStatus::O
+        );
+    }
+
+    /// Active oblast-level alerts of given types, by location uid
+    fn test_alerts(alerts: &[(i32, AlertType)]) -> Alerts {
+        let alerts = alerts
+            .iter()
+            .map(|(uid, alert_type)| {
+                AlertBuilder::new(*uid)
+                    .with_alert_type(alert_type.clone())
+                    .build()
+            })
+            .collect::<Vec<Alert>>();
+        Alerts::from(alerts)
+    }
+}
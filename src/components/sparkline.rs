@@ -0,0 +1,90 @@
+use ratatui::{prelude::*, widgets::*};
+use rust_i18n::t;
+
+use super::{Component, Result, WithPlacement};
+use crate::{action::Action, layout::*, theme::*, tui::Frame};
+
+/// Number of last stored snapshots shown, wider than the area is ever expected to be
+pub const SPARKLINE_SNAPSHOTS: usize = 100;
+
+/// Active locations count over last stored statuses snapshots
+#[derive(Debug, Clone)]
+pub struct AlertsSparkline {
+    placement: LayoutPoint,
+    /// Oldest first, see [`crate::data::DataSource::fetch_active_counts`]
+    counts: Vec<u64>,
+    theme: Theme,
+}
+
+impl AlertsSparkline {
+    pub fn new() -> Self {
+        Self {
+            placement: LayoutPoint(LayoutArea::RightMiddle, Some(LayoutTab::Tab1)),
+            counts: Vec::new(),
+            theme: Theme::default(),
+        }
+    }
+
+    /// Newest counts fitting into `width`, as sparkline draws from the start of data
+    pub fn visible_counts(&self, width: u16) -> &[u64] {
+        let skip = self.counts.len().saturating_sub(width as usize);
+        &self.counts[skip..]
+    }
+}
+
+impl WithPlacement<'_> for AlertsSparkline {
+    fn placement(&self) -> &LayoutPoint {
+        &self.placement
+    }
+}
+
+impl Component<'_> for AlertsSparkline {
+    fn init(&mut self, size: Rect) -> Result<()> {
+        self.debug();
+        Ok(())
+    }
+
+    fn register_theme_handler(&mut self, theme: Theme) -> Result<()> {
+        self.theme = theme;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        if let Action::GetActiveCounts(counts) = action {
+            self.counts = counts;
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>) -> Result<()> {
+        let area = self.get_area(f.size())?;
+        let current = self.counts.last().copied().unwrap_or_default();
+        let block = Block::bordered().title(format!(
+            "{}: {}",
+            t!("views.Sparkline.title"),
+            current
+        ));
+        let width = block.inner(area).width;
+        let widget = Sparkline::default()
+            .block(block)
+            .data(self.visible_counts(width))
+            .style(Style::default().fg(self.theme.alert));
+        f.render_widget(widget, area);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visible_counts_are_newest() -> Result<()> {
+        let mut sparkline = AlertsSparkline::new();
+        sparkline.update(Action::GetActiveCounts(vec![0, 0, 1, 3, 2]))?;
+
+        assert_eq!(sparkline.visible_counts(3), &[1, 3, 2]);
+        assert_eq!(sparkline.visible_counts(10), &[0, 0, 1, 3, 2]);
+        Ok(())
+    }
+}
@@ -0,0 +1,136 @@
+use ratatui::{prelude::*, widgets::*};
+use rust_i18n::t;
+use time::{Duration, OffsetDateTime};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::{Component, Result, WithPlacement};
+use crate::{action::Action, config::*, layout::*, tui::Frame};
+
+/// Time since alerts were last successfully fetched
+#[derive(Debug)]
+pub struct StatusBar {
+    command_tx: Option<UnboundedSender<Action>>,
+    placement: LayoutPoint,
+    #[allow(unused)]
+    config: Config,
+    last_fetch: Option<OffsetDateTime>,
+    now: OffsetDateTime,
+}
+
+impl StatusBar {
+    pub fn new() -> Self {
+        Self {
+            command_tx: Option::default(),
+            placement: LayoutPoint(LayoutArea::Title, None),
+            config: Config::default(),
+            last_fetch: None,
+            now: OffsetDateTime::now_utc(),
+        }
+    }
+
+    fn text(&self) -> String {
+        let ago = match self.last_fetch {
+            Some(last_fetch) => format_relative(self.now - last_fetch),
+            None => t!("views.StatusBar.never").to_string(),
+        };
+        format!("{} {}", t!("views.StatusBar.updated"), ago)
+    }
+}
+
+/// Short localized relative time, e.g. "12s ago", "5m ago", "2h ago"
+pub fn format_relative(elapsed: Duration) -> String {
+    let seconds = elapsed.whole_seconds().max(0);
+    match seconds {
+        0..=59 => t!("views.StatusBar.seconds_ago", count = seconds),
+        60..=3599 => t!("views.StatusBar.minutes_ago", count = seconds / 60),
+        _ => t!("views.StatusBar.hours_ago", count = seconds / 3600),
+    }
+    .to_string()
+}
+
+impl WithPlacement<'_> for StatusBar {
+    fn placement(&self) -> &LayoutPoint {
+        &self.placement
+    }
+}
+
+impl Component<'_> for StatusBar {
+    fn init(&mut self, size: Rect) -> Result<()> {
+        self.debug();
+        Ok(())
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::Tick => {
+                self.now = OffsetDateTime::now_utc();
+            }
+            Action::LastFetch(last_fetch) => {
+                self.last_fetch = Some(last_fetch);
+                self.now = last_fetch;
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>) -> Result<()> {
+        let area = self.get_area(f.size())?;
+        let widget = Paragraph::new(self.text().dim()).alignment(Alignment::Right);
+        f.render_widget(widget, area);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_relative() {
+        let seconds = |count: i64| t!("views.StatusBar.seconds_ago", count = count);
+        assert_eq!(format_relative(Duration::ZERO), seconds(0));
+        assert_eq!(format_relative(Duration::seconds(12)), seconds(12));
+        assert_eq!(
+            format_relative(Duration::seconds(125)),
+            t!("views.StatusBar.minutes_ago", count = 2)
+        );
+        assert_eq!(
+            format_relative(Duration::hours(3)),
+            t!("views.StatusBar.hours_ago", count = 3)
+        );
+        // clock skew must not produce negative values
+        assert_eq!(format_relative(Duration::seconds(-5)), seconds(0));
+    }
+
+    #[test]
+    fn test_format_relative_locales() {
+        let en = t!("views.StatusBar.minutes_ago", locale = "en", count = 5);
+        let uk = t!("views.StatusBar.minutes_ago", locale = "uk", count = 5);
+        assert_eq!(en, "5m ago");
+        assert_eq!(uk, "5 хв тому");
+    }
+
+    #[test]
+    fn test_text_before_first_fetch() -> Result<()> {
+        let mut status_bar = StatusBar::new();
+        assert!(status_bar
+            .text()
+            .ends_with(&t!("views.StatusBar.never").to_string()));
+
+        status_bar.update(Action::LastFetch(OffsetDateTime::now_utc()))?;
+        let ago = t!("views.StatusBar.seconds_ago", count = 0);
+        assert!(status_bar.text().ends_with(&ago.to_string()));
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+use ratatui::{prelude::*, widgets::*};
+use time::OffsetDateTime;
+
+use super::{Component, Result, WithPlacement};
+use crate::{action::Action, layout::*, theme::*, tui::Frame};
+
+/// Number of ticks the banner stays visible
+const TOAST_TICKS: usize = 5;
+
+/// Temporary banner with the most recent error message
+#[derive(Debug)]
+pub struct Toast {
+    placement: LayoutPoint,
+    theme: Theme,
+    message: Option<(String, OffsetDateTime)>,
+    ticks_left: usize,
+}
+
+impl Toast {
+    pub fn new() -> Self {
+        Self {
+            placement: LayoutPoint(LayoutArea::Legend, None),
+            theme: Theme::default(),
+            message: None,
+            ticks_left: 0,
+        }
+    }
+
+    pub fn is_shown(&self) -> bool {
+        self.message.is_some()
+    }
+
+    /// Latest message with the time it was received
+    pub fn message(&self) -> Option<&(String, OffsetDateTime)> {
+        self.message.as_ref()
+    }
+}
+
+impl WithPlacement<'_> for Toast {
+    fn placement(&self) -> &LayoutPoint {
+        &self.placement
+    }
+}
+
+impl Component<'_> for Toast {
+    fn init(&mut self, size: Rect) -> Result<()> {
+        self.debug();
+        Ok(())
+    }
+
+    fn register_theme_handler(&mut self, theme: Theme) -> Result<()> {
+        self.theme = theme;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::Error(message) => {
+                // only the most recent message is shown
+                self.message = Some((message, OffsetDateTime::now_utc()));
+                self.ticks_left = TOAST_TICKS;
+            }
+            Action::Tick if self.message.is_some() => {
+                self.ticks_left = self.ticks_left.saturating_sub(1);
+                if self.ticks_left == 0 {
+                    self.message = None;
+                }
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>) -> Result<()> {
+        let Some((message, _)) = &self.message else {
+            return Ok(());
+        };
+        let area = self.get_area(f.size())?;
+        let style = if self.theme.no_color {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default().fg(Color::White).bg(self.theme.alert)
+        };
+        let widget = Paragraph::new(format!(" ! {}", message)).style(style);
+        f.render_widget(Clear, area);
+        f.render_widget(widget, area);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toast_shows_latest_error_and_clears() -> Result<()> {
+        let mut toast = Toast::new();
+        assert!(!toast.is_shown());
+
+        toast.update(Action::Error("first".to_string()))?;
+        toast.update(Action::Error("second".to_string()))?;
+        assert!(toast.is_shown());
+        assert_eq!(toast.message().unwrap().0, "second");
+
+        for _ in 0..TOAST_TICKS - 1 {
+            toast.update(Action::Tick)?;
+        }
+        assert!(toast.is_shown());
+        toast.update(Action::Tick)?;
+        assert!(!toast.is_shown());
+        Ok(())
+    }
+}
@@ -5,15 +5,30 @@ use icu_locid::subtags::{language, Language};
 #[allow(unused_imports)]
 use miette::{miette, Error, IntoDiagnostic, Result};
 use serde::{Deserialize, Serialize};
-use std::{str::FromStr, string::ToString};
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+    string::ToString,
+};
 use tracing::warn;
 
 #[allow(unused_imports)]
 use crate::error::*;
+use crate::{
+    cli::{get_default_locale, parse_poll_interval, Cli},
+    keybindings::KeyBindingsSettings,
+    layout::LayoutSettings,
+    theme::ThemeSettings,
+    utils::{get_config_dir, LogLevel},
+};
+
+pub const CONFIG_FILE: &str = "config.toml";
+/// Placeholder of secrets in printed config, see [`Config::to_redacted_toml`]
+pub const REDACTED: &str = "<redacted>";
 
 #[derive(Debug, Clone, EnvConfig, Getters, Setters)]
 pub struct Config {
-    // pub keybindings: HashMap<String, String>, // FIXME: fails with new EnvConfig derive
     #[getset(get = "pub")]
     settings: Settings,
     #[env_config(default = false)]
@@ -30,8 +45,26 @@ pub struct Settings {
     #[getset(get = "pub")]
     pub token: String,
     #[env_config(name = "ALERTSINUA_POLLING_INTERVAL_SEC", default = 30)]
-    #[getset(get = "pub")]
+    #[getset(get = "pub", set = "pub")]
     pub polling_interval: u64,
+    #[env_config(name = "ALERTSINUA_DB_PATH", default = "")]
+    #[getset(get = "pub", set = "pub")]
+    pub db_path: String,
+    #[env_config(name = "ALERTSINUA_BOUNDARY_PATH", default = "")]
+    #[getset(get = "pub", set = "pub")]
+    pub boundary_path: String,
+    #[env_config(name = "ALERTSINUA_LOCATIONS_PATH", default = "")]
+    #[getset(get = "pub", set = "pub")]
+    pub locations_path: String,
+    #[env_config(name = "ALERTSINUA_NOTIFICATIONS", default = false)]
+    #[getset(get = "pub", set = "pub")]
+    pub notifications: bool,
+    #[env_config(name = "ALERTSINUA_BELL", default = false)]
+    #[getset(get = "pub", set = "pub")]
+    pub bell: bool,
+    #[env_config(name = "ALERTSINUA_NO_COLOR", default = false)]
+    #[getset(get = "pub", set = "pub")]
+    pub no_color: bool,
     #[env_config(name = "LOG_FILE", default = "")]
     #[getset(get = "pub", set = "pub")]
     pub log_file: String,
@@ -41,13 +74,173 @@ pub struct Settings {
     #[getset(get = "pub with_prefix")]
     pub locale: String, // Language, // FIXME: fails with new EnvConfig derive
     #[env_config(name = "TICK_RATE", default = 1.0)]
-    #[getset(get = "pub")]
+    #[getset(get = "pub", set = "pub")]
     pub tick_rate: f64,
     #[env_config(name = "FRAME_RATE", default = 1.0)]
-    #[getset(get = "pub")]
+    #[getset(get = "pub", set = "pub")]
     pub frame_rate: f64,
 }
 
+/// Supported UI locale, one per file in `locales` dir
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Locale {
+    Ukrainian,
+    #[default]
+    English,
+}
+
+impl Locale {
+    /// All supported locales, in the order they are toggled
+    pub fn all() -> &'static [Locale] {
+        &[Locale::English, Locale::Ukrainian]
+    }
+
+    /// Language code, e.g. `uk`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Locale::Ukrainian => "uk",
+            Locale::English => "en",
+        }
+    }
+
+    /// Locale toggled to from this one, see [`Config::toggle_locale`]
+    pub fn next(&self) -> Locale {
+        let all = Self::all();
+        let index = all.iter().position(|l| l == self).unwrap_or_default();
+        all[(index + 1) % all.len()]
+    }
+
+    /// Comma-separated codes of all locales, for help and error messages
+    pub fn codes() -> String {
+        Self::all()
+            .iter()
+            .map(Locale::as_str)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Locale {
+    type Err = AppError;
+
+    /// Parse language code, case-insensitive
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Self::all()
+            .iter()
+            .find(|l| l.as_str().eq_ignore_ascii_case(s.trim()))
+            .copied()
+            .ok_or_else(|| AppError::InvalidLocale(s.to_string(), Self::codes()))
+    }
+}
+
+impl From<Locale> for Language {
+    fn from(locale: Locale) -> Self {
+        match locale {
+            Locale::Ukrainian => language!("uk"),
+            Locale::English => language!("en"),
+        }
+    }
+}
+
+/// Settings from optional TOML config file, overridden by env vars and CLI args.
+/// Unknown keys are rejected instead of ignored, so typos don't go unnoticed
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FileSettings {
+    pub token: Option<String>,
+    pub base_url: Option<String>,
+    /// Last selected UI locale, saved when toggled
+    pub locale: Option<String>,
+    pub db_path: Option<String>,
+    /// Seconds between periodic fetches of alerts
+    pub poll_interval: Option<u64>,
+    pub log_level: Option<LogLevel>,
+    #[serde(skip_serializing_if = "ThemeSettings::is_empty")]
+    pub theme: ThemeSettings,
+    #[serde(skip_serializing_if = "KeyBindingsSettings::is_empty")]
+    pub keybindings: KeyBindingsSettings,
+    #[serde(skip_serializing_if = "LayoutSettings::is_empty")]
+    pub layout: LayoutSettings,
+}
+
+impl FileSettings {
+    pub fn path() -> PathBuf {
+        get_config_dir().join(CONFIG_FILE)
+    }
+
+    /// Load settings from file, missing file results in empty settings
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(miette!("failed to read config file {:?}: {}", path, e)),
+        };
+        Ok(Self::parse(path, &contents)?)
+    }
+
+    /// Parse and validate config file `contents`, error names the first invalid or
+    /// unknown key with its line and column
+    pub fn parse(path: &Path, contents: &str) -> std::result::Result<Self, AppError> {
+        toml::from_str(contents)
+            .map_err(|e| AppError::InvalidConfig(path.to_path_buf(), e.to_string()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).into_diagnostic()?;
+        }
+        let contents = toml::to_string(self).into_diagnostic()?;
+        fs::write(path, contents).into_diagnostic()
+    }
+
+    /// Stored locale, fails on unsupported one
+    pub fn locale(&self) -> std::result::Result<Option<Locale>, AppError> {
+        self.locale.as_deref().map(Locale::from_str).transpose()
+    }
+}
+
+/// Env var lookup, [`std::env::var`] outside of tests
+pub fn env_var(name: &str) -> Option<String> {
+    std::env::var(name).ok()
+}
+
+/// First non-empty value by precedence: CLI args, env vars, config file
+pub fn resolve_setting(
+    cli: Option<&str>,
+    env: Option<&str>,
+    file: Option<&str>,
+) -> Option<String> {
+    [cli, env, file]
+        .into_iter()
+        .flatten()
+        .find(|v| !v.is_empty())
+        .map(String::from)
+}
+
+/// Log level by precedence: `--log-level`, `ALERTSINUA_LOG_LEVEL` env, config file.
+/// `None` leaves it to `RUST_LOG` env, see [`crate::utils::log_filter`]
+pub fn resolve_log_level(
+    cli: &Cli,
+    env: &impl Fn(&str) -> Option<String>,
+    file: &FileSettings,
+) -> Result<Option<LogLevel>> {
+    if cli.log_level.is_some() {
+        return Ok(cli.log_level);
+    }
+    match env("ALERTSINUA_LOG_LEVEL").filter(|v| !v.is_empty()) {
+        Some(level) => <LogLevel as clap::ValueEnum>::from_str(&level, true)
+            .map(Some)
+            .map_err(|e| miette!("invalid ALERTSINUA_LOG_LEVEL '{}': {}", level, e)),
+        None => Ok(file.log_level),
+    }
+}
+
 impl Default for Config {
     fn default() -> Self {
         Config::init().map_err(|e| miette!(e)).unwrap()
@@ -60,11 +253,26 @@ impl Config {
             pub fn base_url(&self) -> &str;
             pub fn set_base_url(&mut self, val: String) -> &mut Settings;
             pub fn token(&self) -> &str;
+            pub fn db_path(&self) -> &str;
+            pub fn set_db_path(&mut self, val: String) -> &mut Settings;
+            pub fn boundary_path(&self) -> &str;
+            pub fn set_boundary_path(&mut self, val: String) -> &mut Settings;
+            pub fn locations_path(&self) -> &str;
+            pub fn set_locations_path(&mut self, val: String) -> &mut Settings;
+            pub fn notifications(&self) -> &bool;
+            pub fn set_notifications(&mut self, val: bool) -> &mut Settings;
+            pub fn bell(&self) -> &bool;
+            pub fn set_bell(&mut self, val: bool) -> &mut Settings;
+            pub fn no_color(&self) -> &bool;
+            pub fn set_no_color(&mut self, val: bool) -> &mut Settings;
             pub fn log_file(&self) -> &str;
             pub fn set_log_file(&mut self, v: String) -> &mut Settings;
             pub fn polling_interval(&self) -> &u64;
+            pub fn set_polling_interval(&mut self, val: u64) -> &mut Settings;
             pub fn tick_rate(&self) -> &f64;
+            pub fn set_tick_rate(&mut self, val: f64) -> &mut Settings;
             pub fn frame_rate(&self) -> &f64;
+            pub fn set_frame_rate(&mut self, val: f64) -> &mut Settings;
         }
     }
 
@@ -78,6 +286,85 @@ impl Config {
         }
     }
 
+    /// Merge CLI args, `ALERTSINUA_*` env vars (looked up with `env`) and config file
+    /// settings, first set value wins: CLI > env > file > default.
+    /// Locale is stored only, see [`Config::apply_locale`]
+    pub fn merge(
+        &mut self,
+        file: &FileSettings,
+        cli: &Cli,
+        env: &impl Fn(&str) -> Option<String>,
+    ) -> Result<&mut Settings> {
+        let resolve = |cli: Option<&str>, name: &str, file: Option<&str>| {
+            resolve_setting(cli, env(name).as_deref(), file)
+        };
+        if let Some(token) =
+            resolve(Some(&cli.token), "ALERTSINUA_TOKEN", file.token.as_deref())
+        {
+            self.set_token(token)?;
+        }
+        if let Some(base_url) = resolve(
+            Some(&cli.base_url),
+            "ALERTSINUA_BASE_URL",
+            file.base_url.as_deref(),
+        ) {
+            self.set_base_url(base_url);
+        }
+        if let Some(db_path) = resolve(
+            Some(&cli.db_path),
+            "ALERTSINUA_DB_PATH",
+            file.db_path.as_deref(),
+        ) {
+            self.set_db_path(db_path);
+        }
+        let cli_interval = cli.poll_interval.map(|i| i.to_string());
+        let file_interval = file.poll_interval.map(|i| i.to_string());
+        if let Some(interval) = resolve(
+            cli_interval.as_deref(),
+            "ALERTSINUA_POLLING_INTERVAL_SEC",
+            file_interval.as_deref(),
+        ) {
+            let interval = parse_poll_interval(&interval)
+                .map_err(|e| miette!("invalid polling interval '{}': {}", interval, e))?;
+            self.set_polling_interval(interval);
+        }
+        let locale = match resolve(
+            cli.locale.as_deref(),
+            "ALERTSINUA_LOCALE",
+            file.locale.as_deref(),
+        ) {
+            Some(locale) => locale.parse::<Locale>()?,
+            // unsupported system locale is not an error
+            None => get_default_locale().parse().unwrap_or_default(),
+        };
+        self.settings.locale = locale.to_string();
+        Ok(&mut self.settings)
+    }
+
+    /// Effective settings with resolved `log_level` as TOML, for `config print` subcommand.
+    /// Token is replaced by [`REDACTED`] unless empty
+    pub fn to_redacted_toml(&self, log_level: Option<LogLevel>) -> Result<String> {
+        let mut table = toml::Table::try_from(&self.settings).into_diagnostic()?;
+        if !self.token().is_empty() {
+            table.insert("token".to_string(), REDACTED.into());
+        }
+        if let Some(level) = log_level {
+            table.insert("log_level".to_string(), level.to_string().into());
+        }
+        toml::to_string(&table).into_diagnostic()
+    }
+
+    /// Switch UI to merged locale setting, unsupported one is ignored
+    pub fn apply_locale(&mut self) -> &mut Settings {
+        match self.settings.locale.parse::<Locale>() {
+            Ok(locale) => self.set_locale(locale),
+            Err(e) => {
+                warn!("{}", e);
+                &mut self.settings
+            }
+        }
+    }
+
     /// For example, check if the token is 46 characters long and contains only alphanumeric characters
     #[inline]
     fn validate_token(token: &str) -> bool {
@@ -93,13 +380,12 @@ impl Config {
 
     #[inline]
     pub fn toggle_locale(&mut self) -> &mut Settings {
-        let curr_lang: Language = self.get_locale();
-        let lang = if curr_lang == language!("en") {
-            language!("uk")
-        } else {
-            language!("en")
-        };
-        self.set_locale(lang);
+        let locale = self
+            .get_locale()
+            .as_str()
+            .parse::<Locale>()
+            .unwrap_or_default();
+        self.set_locale(locale.next());
         &mut self.settings
     }
 
@@ -119,3 +405,307 @@ impl Config {
         &mut self.settings
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[test]
+    fn test_resolve_setting_precedence() {
+        let resolve = |cli, env, file| resolve_setting(cli, env, file);
+
+        assert_eq!(
+            resolve(Some("cli"), Some("env"), Some("file")),
+            Some("cli".into())
+        );
+        assert_eq!(
+            resolve(Some(""), Some("env"), Some("file")),
+            Some("env".into())
+        );
+        assert_eq!(resolve(None, None, Some("file")), Some("file".into()));
+        assert_eq!(resolve(Some(""), Some(""), Some("")), None);
+        assert_eq!(resolve(None, None, None), None);
+    }
+
+    #[test]
+    fn test_file_settings_load() -> Result<()> {
+        let dir = std::env::temp_dir().join("ralertsinua_test_file_settings");
+        fs::create_dir_all(&dir).into_diagnostic()?;
+        let path = dir.join(CONFIG_FILE);
+        fs::write(&path, "base_url = \"http://localhost\"\n").into_diagnostic()?;
+
+        let settings = FileSettings::load(&path)?;
+        let missing = FileSettings::load(&dir.join("missing.toml"))?;
+        fs::write(&path, "token = ").into_diagnostic()?;
+        let invalid = FileSettings::load(&path);
+        fs::remove_dir_all(&dir).into_diagnostic()?;
+
+        assert_eq!(settings.base_url.as_deref(), Some("http://localhost"));
+        assert_eq!(settings.token, None);
+        assert_eq!(missing, FileSettings::default());
+        assert!(invalid.is_err());
+        Ok(())
+    }
+
+    fn token(c: char) -> String {
+        c.to_string().repeat(46)
+    }
+
+    fn file_settings() -> FileSettings {
+        FileSettings {
+            token: Some(token('f')),
+            base_url: Some("http://file".to_string()),
+            locale: Some("uk".to_string()),
+            db_path: Some("file.db".to_string()),
+            poll_interval: Some(60),
+            log_level: Some(LogLevel::Warn),
+            ..FileSettings::default()
+        }
+    }
+
+    fn merged(
+        file: &FileSettings,
+        cli: &[&str],
+        env: &[(&str, String)],
+    ) -> Result<Settings> {
+        let cli = Cli::parse_from([&["ralertsinua"], cli].concat());
+        let env = |name: &str| env.iter().find(|(n, _)| *n == name).map(|(_, v)| v.clone());
+        let mut config = Config::default();
+        config.merge(file, &cli, &env)?;
+        Ok(config.settings().clone())
+    }
+
+    #[test]
+    fn test_merge_file_overrides_default() -> Result<()> {
+        let settings = merged(&file_settings(), &[], &[])?;
+
+        assert_eq!(settings.token, token('f'));
+        assert_eq!(settings.base_url, "http://file");
+        assert_eq!(settings.locale, "uk");
+        assert_eq!(settings.db_path, "file.db");
+        assert_eq!(settings.polling_interval, 60);
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_env_overrides_file() -> Result<()> {
+        let file = file_settings();
+        let cases = [
+            ("ALERTSINUA_TOKEN", token('e')),
+            ("ALERTSINUA_BASE_URL", "http://env".to_string()),
+            ("ALERTSINUA_LOCALE", "en".to_string()),
+            ("ALERTSINUA_DB_PATH", "env.db".to_string()),
+            ("ALERTSINUA_POLLING_INTERVAL_SEC", "20".to_string()),
+        ];
+        for (name, value) in cases {
+            let settings = merged(&file, &[], &[(name, value.clone())])?;
+            let merged_value = match name {
+                "ALERTSINUA_TOKEN" => settings.token,
+                "ALERTSINUA_BASE_URL" => settings.base_url,
+                "ALERTSINUA_LOCALE" => settings.locale,
+                "ALERTSINUA_DB_PATH" => settings.db_path,
+                _ => settings.polling_interval.to_string(),
+            };
+            assert_eq!(merged_value, value, "{}", name);
+        }
+
+        let cli = Cli::parse_from(["ralertsinua"]);
+        let env =
+            |name: &str| (name == "ALERTSINUA_LOG_LEVEL").then(|| "DEBUG".to_string());
+        assert_eq!(resolve_log_level(&cli, &env, &file)?, Some(LogLevel::Debug));
+        assert_eq!(
+            resolve_log_level(&cli, &|_: &str| None, &file)?,
+            Some(LogLevel::Warn)
+        );
+        let invalid = |_: &str| Some("loud".to_string());
+        assert!(resolve_log_level(&cli, &invalid, &file).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_cli_overrides_env() -> Result<()> {
+        let env = [
+            ("ALERTSINUA_BASE_URL", "http://env".to_string()),
+            ("ALERTSINUA_POLLING_INTERVAL_SEC", "20".to_string()),
+            ("ALERTSINUA_LOG_LEVEL", "debug".to_string()),
+        ];
+        let args = ["--base-url=http://cli", "--poll-interval=15", "--locale=en"];
+        let settings = merged(&file_settings(), &args, &env)?;
+
+        assert_eq!(settings.base_url, "http://cli");
+        assert_eq!(settings.polling_interval, 15);
+        assert_eq!(settings.locale, "en");
+
+        let cli = Cli::parse_from(["ralertsinua", "--log-level=error"]);
+        let env = |_: &str| Some("debug".to_string());
+        assert_eq!(
+            resolve_log_level(&cli, &env, &file_settings())?,
+            Some(LogLevel::Error)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_rejects_short_poll_interval() {
+        let file = FileSettings {
+            poll_interval: Some(0),
+            ..FileSettings::default()
+        };
+        assert!(merged(&file, &[], &[]).is_err());
+        let env = [("ALERTSINUA_POLLING_INTERVAL_SEC", "0".to_string())];
+        assert!(merged(&FileSettings::default(), &[], &env).is_err());
+    }
+
+    #[test]
+    fn test_to_redacted_toml() -> Result<()> {
+        let cli = Cli::parse_from(["ralertsinua", "--poll-interval=15"]);
+        let env =
+            |name: &str| (name == "ALERTSINUA_BASE_URL").then(|| "http://env".to_string());
+        let mut config = Config::default();
+        config.merge(&file_settings(), &cli, &env)?;
+
+        let printed = config.to_redacted_toml(Some(LogLevel::Debug))?;
+        let table: toml::Table = toml::from_str(&printed).into_diagnostic()?;
+
+        assert_eq!(table["base_url"].as_str(), Some("http://env"));
+        assert_eq!(table["polling_interval"].as_integer(), Some(15));
+        assert_eq!(table["db_path"].as_str(), Some("file.db"));
+        assert_eq!(table["log_level"].as_str(), Some("debug"));
+        assert_eq!(table["token"].as_str(), Some(REDACTED));
+        assert!(!printed.contains(&token('f')));
+        Ok(())
+    }
+
+    #[test]
+    fn test_file_settings_parse_valid() -> Result<()> {
+        let path = Path::new(CONFIG_FILE);
+        let settings = FileSettings::parse(
+            path,
+            r#"
+            base_url = "http://localhost"
+            locale = "uk"
+
+            [theme]
+            alert = "red"
+
+            [keybindings]
+            Refresh = "u"
+
+            [layout]
+            left_percent = 60
+            "#,
+        )?;
+
+        assert_eq!(settings.base_url.as_deref(), Some("http://localhost"));
+        assert_eq!(settings.theme.alert.as_deref(), Some("red"));
+        assert_eq!(settings.layout.left_percent, 60);
+        assert_eq!(settings.keybindings.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_file_settings_parse_unknown_key() {
+        let path = Path::new(CONFIG_FILE);
+        for (contents, key, line) in [
+            (
+                "base_url = \"http://localhost\"\ntokn = \"x\"\n",
+                "tokn",
+                "line 2",
+            ),
+            ("[theme]\nalrt = \"red\"\n", "alrt", "line 2"),
+            (
+                "[layout]\nleft_percent = 60\nbottom = 3\n",
+                "bottom",
+                "line 3",
+            ),
+        ] {
+            let Err(AppError::InvalidConfig(error_path, message)) =
+                FileSettings::parse(path, contents)
+            else {
+                panic!("{} is accepted", key);
+            };
+            assert_eq!(error_path, path);
+            assert!(
+                message.contains(&format!("unknown field `{}`", key)),
+                "{}",
+                message
+            );
+            assert!(message.contains(line), "{}", message);
+        }
+    }
+
+    #[test]
+    fn test_file_settings_locale_round_trip() -> Result<()> {
+        let path = std::env::temp_dir()
+            .join("ralertsinua_test_locale")
+            .join(CONFIG_FILE);
+        let settings = FileSettings {
+            locale: Some(Locale::Ukrainian.to_string()),
+            ..FileSettings::default()
+        };
+
+        settings.save(&path)?;
+        let loaded = FileSettings::load(&path)?;
+        fs::remove_dir_all(path.parent().unwrap()).into_diagnostic()?;
+
+        assert_eq!(loaded, settings);
+        assert_eq!(loaded.locale()?, Some(Locale::Ukrainian));
+        Ok(())
+    }
+
+    #[test]
+    fn test_file_settings_invalid_locale() -> Result<()> {
+        let settings = FileSettings {
+            locale: Some("not a locale".to_string()),
+            ..FileSettings::default()
+        };
+
+        assert!(matches!(
+            settings.locale(),
+            Err(AppError::InvalidLocale(locale, _)) if locale == "not a locale"
+        ));
+        assert_eq!(FileSettings::default().locale()?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_locale_parse() {
+        assert_eq!("uk".parse::<Locale>().ok(), Some(Locale::Ukrainian));
+        assert_eq!("EN".parse::<Locale>().ok(), Some(Locale::English));
+        for locale in Locale::all() {
+            assert_eq!(locale.to_string().parse::<Locale>().ok(), Some(*locale));
+            assert_eq!(Language::from(*locale).as_str(), locale.as_str());
+        }
+
+        for invalid in ["", "de", "ukr", "en-US"] {
+            let error = invalid.parse::<Locale>().unwrap_err();
+            assert!(error.to_string().contains("en, uk"), "{}", error);
+        }
+    }
+
+    #[test]
+    fn test_locales_have_translations() {
+        let available = rust_i18n::available_locales!();
+        for locale in Locale::all() {
+            assert!(available.contains(&locale.as_str()), "{}", locale);
+        }
+    }
+
+    #[test]
+    fn test_locale_next_cycles_all() {
+        assert_eq!(Locale::English.next(), Locale::Ukrainian);
+        assert_eq!(Locale::Ukrainian.next(), Locale::English);
+    }
+
+    #[test]
+    fn test_merge_rejects_invalid_locale() {
+        let file = FileSettings {
+            locale: Some("de".to_string()),
+            ..FileSettings::default()
+        };
+        assert!(merged(&file, &[], &[]).is_err());
+        let env = [("ALERTSINUA_LOCALE", "fr".to_string())];
+        assert!(merged(&FileSettings::default(), &[], &env).is_err());
+    }
+}
@@ -2,11 +2,15 @@
 use lazy_static::lazy_static;
 use ratatui::style::{palette::material, Color};
 
+/// Smallest terminal (width, height) the layout is drawn in, a message is shown instead below it
+pub const MIN_TERMINAL_SIZE: (u16, u16) = (60, 20);
+
 lazy_static! {
     // Colors
     pub static ref DEFAULT_COLOR: Color = Color::Reset;
     pub static ref TEXT_COLOR: Color = Color::LightBlue;
     pub static ref MARKER_COLOR: Color = Color::LightYellow;
+    pub static ref CENTER_COLOR: Color = Color::LightMagenta;
     pub static ref NORMAL_ROW_COLOR: Color = Color::Reset;
     pub static ref ALERT_ROW_COLOR: Color = Color::Red;
     pub static ref SELECTED_STYLE_FG: Color = Color::Blue;
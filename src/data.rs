@@ -0,0 +1,613 @@
+use async_trait::async_trait;
+use geojson::{Feature, FeatureCollection, JsonObject};
+use ralertsinua_geo::Location;
+use ralertsinua_http::AlertsInUaApi;
+use ralertsinua_models::{AirRaidAlertOblastStatuses, AlertStatus, REGIONS_DATA};
+use std::{
+    fmt,
+    fs::OpenOptions,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard},
+};
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+use tracing::warn;
+
+use crate::{error::*, utils::get_data_dir};
+
+type Result<T> = miette::Result<T, AppError>;
+
+pub const STATUSES_FILE: &str = "statuses.txt";
+/// Size of statuses file after which it is rotated, about 20k snapshots
+pub const STATUSES_MAX_SIZE: u64 = 1024 * 1024;
+
+/// Raw air raid alert statuses string, one char per oblast
+pub type AlertsResponseString = String;
+
+/// Separates `created_at` timestamp and statuses string in a line
+const FIELD_SEPARATOR: char = '\t';
+
+/// Storage of fetched statuses used by `App`, implemented by [`DataRepository`]
+/// and by [`MemoryDataSource`] which keeps nothing on disk
+#[async_trait]
+pub trait DataSource: fmt::Debug + Send + Sync {
+    /// Prepare stored data written by older versions
+    fn migrate(&self) -> Result<()>;
+
+    /// Most recent statuses snapshot, `None` if storage is empty
+    fn fetch_last_known_alerts_string(&self) -> Result<Option<AlertsResponseString>>;
+
+    /// Store statuses snapshot, skipped if equal to the last known one
+    fn insert_alerts_string(&self, data: &str) -> Result<()>;
+
+    /// Make sure stored data reaches the disk, called on shutdown
+    fn flush(&self) -> Result<()>;
+
+    /// Active locations count of up to `limit` last snapshots, oldest first,
+    /// padded with zeros at the start to `limit` if fewer were stored
+    fn fetch_active_counts(&self, limit: usize) -> Result<Vec<u64>>;
+
+    /// Current status of a single oblast requested from `api`. If the request fails,
+    /// status from the last known snapshot is returned, or the error if nothing is stored
+    async fn fetch_region_alert(
+        &self,
+        api: &dyn AlertsInUaApi,
+        uid: i32,
+    ) -> Result<AlertStatus> {
+        if !REGIONS_DATA
+            .iter()
+            .any(|(_, location_uid, _, _)| *location_uid == uid)
+        {
+            return Err(AppError::InvalidLocationUid(uid));
+        }
+        // oblast uids are at most 31
+        let location_aid = uid as i8;
+        match api.get_air_raid_alert_status(&location_aid).await {
+            // single-char status, e.g. "A"
+            Ok(status) => Ok(status
+                .chars()
+                .next()
+                .map(AlertStatus::from)
+                .unwrap_or_default()),
+            Err(e) => {
+                warn!(target: "app", "failed to fetch status of location {}: {}", uid, e);
+                self.fetch_last_known_alerts_string()?
+                    .map(|data| AirRaidAlertOblastStatuses::new(data, Some(false)))
+                    .and_then(|statuses| statuses.get_by_location_uid(uid))
+                    .map(|os| os.status().clone())
+                    .ok_or_else(|| e.into())
+            }
+        }
+    }
+}
+
+/// Count of locations with active alert in statuses snapshot
+pub fn active_count(data: &str) -> u64 {
+    data.chars().filter(|&c| c == 'A').count() as u64
+}
+
+/// Active counts of `snapshots` in given order, zero-padded at the start to `limit`
+fn padded_active_counts<'s>(
+    snapshots: impl DoubleEndedIterator<Item = &'s str>,
+    limit: usize,
+) -> Vec<u64> {
+    let mut counts: Vec<u64> = snapshots.rev().take(limit).map(active_count).collect();
+    counts.resize(limit, 0);
+    counts.reverse();
+    counts
+}
+
+/// File-based storage of fetched alert statuses, one `created_at<TAB>statuses` line per snapshot.
+///
+/// Stands in for a SQLite `statuses` table: only the latest snapshots are ever read back,
+/// so a plain text file does the job without a native dependency. Writes are append-only
+/// and the last snapshot is kept in memory, so inserting doesn't re-read the file.
+/// Once the file grows past `max_size` it is moved to `<path>.1`, replacing the previous one,
+/// and a new file is started with the last snapshot, so at most twice `max_size` is kept
+#[derive(Debug, Clone)]
+pub struct DataRepository {
+    path: PathBuf,
+    max_size: u64,
+    /// Last stored snapshot, `None` until read from file on first use
+    last: Arc<Mutex<Option<Option<AlertsResponseString>>>>,
+}
+
+impl DataRepository {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            max_size: STATUSES_MAX_SIZE,
+            last: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// File size in bytes after which storage is rotated, default is [`STATUSES_MAX_SIZE`]
+    pub fn with_max_size(mut self, max_size: u64) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Default storage path in data directory, respects `$RALERTSINUA_DATA` and platform data dir
+    pub fn default_path() -> PathBuf {
+        get_data_dir().join(STATUSES_FILE)
+    }
+
+    /// Open storage at `path`, creating parent directories and empty file if missing
+    pub fn open(path: PathBuf) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        OpenOptions::new().create(true).append(true).open(&path)?;
+        Ok(Self::new(path))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Previous file, moved aside on rotation
+    pub fn rotated_path(&self) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(".1");
+        path.into()
+    }
+
+    fn lock_last(&self) -> MutexGuard<'_, Option<Option<AlertsResponseString>>> {
+        // cached snapshot stays consistent even if a holder panicked
+        self.last.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Last stored snapshot, read from file only on first call
+    fn last_known(
+        &self,
+        last: &mut Option<Option<AlertsResponseString>>,
+    ) -> Result<Option<AlertsResponseString>> {
+        if let Some(known) = last {
+            return Ok(known.clone());
+        }
+        let known = self.read_entries()?.pop().map(|(_, data)| data);
+        *last = Some(known.clone());
+        Ok(known)
+    }
+
+    fn insert_alerts_string_at(
+        &self,
+        data: &str,
+        created_at: OffsetDateTime,
+    ) -> Result<()> {
+        let data = data.trim_matches('"');
+        let mut last = self.lock_last();
+        if self.last_known(&mut last)?.as_deref() == Some(data) {
+            return Ok(());
+        }
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        let line = Self::format_line(data, created_at);
+        writeln!(file, "{}", line)?;
+        if file.metadata()?.len() > self.max_size {
+            drop(file);
+            std::fs::rename(&self.path, self.rotated_path())?;
+            std::fs::write(&self.path, line + "\n")?;
+        }
+        *last = Some(Some(data.to_string()));
+        Ok(())
+    }
+
+    /// Up to `limit` statuses snapshots, newest first
+    pub fn fetch_alerts_history(
+        &self,
+        limit: usize,
+    ) -> Result<Vec<(OffsetDateTime, AlertsResponseString)>> {
+        let mut entries = self.read_entries()?;
+        entries.reverse();
+        entries.sort_by_key(|(created_at, _)| std::cmp::Reverse(*created_at));
+        entries.truncate(limit);
+        Ok(entries)
+    }
+
+    /// GeoJSON FeatureCollection of `locations` with last known alert status per feature
+    pub fn export_geojson(&self, locations: &[Location]) -> Result<String> {
+        let statuses = self
+            .fetch_last_known_alerts_string()?
+            .map(|data| AirRaidAlertOblastStatuses::new(data, Some(false)))
+            .unwrap_or_default();
+        let features = locations
+            .iter()
+            .map(|location| {
+                let status = statuses
+                    .get_by_location_uid(location.location_uid)
+                    .map(|s| s.status().to_string())
+                    .unwrap_or_default();
+                let mut properties = JsonObject::new();
+                properties.insert("location_uid".into(), location.location_uid.into());
+                properties.insert("name".into(), location.name.clone().into());
+                properties.insert("name_en".into(), location.name_en.clone().into());
+                properties.insert("status".into(), status.into());
+                Feature {
+                    bbox: None,
+                    geometry: Some(geojson::Geometry::new((&location.geometry).into())),
+                    id: Some(geojson::feature::Id::String(location.relation_id.clone())),
+                    properties: Some(properties),
+                    foreign_members: None,
+                }
+            })
+            .collect();
+        let collection = FeatureCollection {
+            bbox: None,
+            features,
+            foreign_members: None,
+        };
+        Ok(collection.to_string())
+    }
+
+    fn read_contents(&self) -> Result<Option<String>> {
+        Self::read_file(&self.path)
+    }
+
+    fn read_file(path: &Path) -> Result<Option<String>> {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => Ok(Some(contents)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Valid snapshots of rotated and current file in insertion order
+    fn read_entries(&self) -> Result<Vec<(OffsetDateTime, AlertsResponseString)>> {
+        let rotated = Self::read_file(&self.rotated_path())?.unwrap_or_default();
+        let current = self.read_contents()?.unwrap_or_default();
+        let mut entries: Vec<_> = rotated
+            .lines()
+            .chain(current.lines())
+            .filter_map(Self::parse_line)
+            .collect();
+        // current file starts with the last snapshot of rotated one
+        entries.dedup();
+        Ok(entries)
+    }
+
+    fn format_line(data: &str, created_at: OffsetDateTime) -> String {
+        let created_at = created_at.format(&Rfc3339).unwrap_or_default();
+        format!(
+            "{}{}{}",
+            created_at,
+            FIELD_SEPARATOR,
+            data.trim_matches('"')
+        )
+    }
+
+    /// Parse a line, lines without `created_at` get UNIX epoch
+    fn parse_line(line: &str) -> Option<(OffsetDateTime, AlertsResponseString)> {
+        let (created_at, data) = match line.trim().split_once(FIELD_SEPARATOR) {
+            Some((created_at, data)) => {
+                (OffsetDateTime::parse(created_at, &Rfc3339).ok()?, data)
+            }
+            None => (OffsetDateTime::UNIX_EPOCH, line.trim()),
+        };
+        (data.chars().count() == REGIONS_DATA.len()).then(|| (created_at, data.to_string()))
+    }
+}
+
+impl DataSource for DataRepository {
+    /// Add `created_at` to snapshots stored without it, using UNIX epoch as unknown time
+    fn migrate(&self) -> Result<()> {
+        let Some(contents) = self.read_contents()? else {
+            return Ok(());
+        };
+        if contents.lines().all(|line| line.contains(FIELD_SEPARATOR)) {
+            return Ok(());
+        }
+        let migrated: String = contents
+            .lines()
+            .filter_map(Self::parse_line)
+            .map(|(created_at, data)| Self::format_line(&data, created_at) + "\n")
+            .collect();
+        std::fs::write(&self.path, migrated)?;
+        Ok(())
+    }
+
+    /// Most recent statuses snapshot, `None` if storage is empty or missing
+    fn fetch_last_known_alerts_string(&self) -> Result<Option<AlertsResponseString>> {
+        let mut last = self.lock_last();
+        self.last_known(&mut last)
+    }
+
+    /// Append statuses snapshot to storage, timestamped with current time, rotating
+    /// storage past its max size. Snapshot equal to the last known one is skipped,
+    /// so storage only grows on changes
+    fn insert_alerts_string(&self, data: &str) -> Result<()> {
+        self.insert_alerts_string_at(data, OffsetDateTime::now_utc())
+    }
+
+    fn flush(&self) -> Result<()> {
+        match std::fs::File::open(&self.path) {
+            Ok(file) => Ok(file.sync_all()?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn fetch_active_counts(&self, limit: usize) -> Result<Vec<u64>> {
+        let history = self.fetch_alerts_history(limit)?;
+        // history is newest first
+        Ok(padded_active_counts(
+            history.iter().rev().map(|(_, data)| data.as_str()),
+            limit,
+        ))
+    }
+}
+
+/// In-memory storage, e.g. for demo mode and tests
+#[derive(Debug, Default)]
+pub struct MemoryDataSource {
+    entries: Mutex<Vec<AlertsResponseString>>,
+}
+
+impl MemoryDataSource {
+    pub fn new(entries: Vec<AlertsResponseString>) -> Self {
+        Self {
+            entries: Mutex::new(entries),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<AlertsResponseString>> {
+        // entries stay consistent even if a holder panicked
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Stored snapshots in insertion order
+    pub fn entries(&self) -> Vec<AlertsResponseString> {
+        self.lock().clone()
+    }
+}
+
+impl DataSource for MemoryDataSource {
+    fn migrate(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn fetch_last_known_alerts_string(&self) -> Result<Option<AlertsResponseString>> {
+        Ok(self.entries().pop())
+    }
+
+    fn insert_alerts_string(&self, data: &str) -> Result<()> {
+        let data = data.trim_matches('"');
+        let mut entries = self.lock();
+        if entries.last().map(String::as_str) != Some(data) {
+            entries.push(data.to_string());
+        }
+        Ok(())
+    }
+
+    fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn fetch_active_counts(&self, limit: usize) -> Result<Vec<u64>> {
+        let entries = self.lock();
+        Ok(padded_active_counts(
+            entries.iter().map(String::as_str),
+            limit,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::demo::DemoApi;
+    use ralertsinua_http::{AlertsInUaClient, ApiError};
+
+    fn temp_repository(name: &str) -> DataRepository {
+        let path = std::env::temp_dir().join(name);
+        let _ = std::fs::remove_file(&path);
+        DataRepository::new(path)
+    }
+
+    #[test]
+    fn test_fetch_last_known_alerts_string() -> Result<()> {
+        let repo = temp_repository("ralertsinua_test_statuses_last.txt");
+        assert_eq!(repo.fetch_last_known_alerts_string()?, None);
+
+        repo.insert_alerts_string("NNNNNNNNNNNNNNNNNNNNNNNNNNN")?;
+        repo.insert_alerts_string("\"ANNAANNANNNPANANANNNNAANNNN\"")?;
+        let last = repo.fetch_last_known_alerts_string()?.unwrap();
+
+        assert_eq!(last.len(), 27);
+        assert_eq!(last, "ANNAANNANNNPANANANNNNAANNNN");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_fetch_region_alert() -> Result<()> {
+        let repo = temp_repository("ralertsinua_test_statuses_region.txt");
+        let statuses = DemoApi.get_air_raid_alert_statuses_by_location().await?;
+
+        for uid in [16, 29, 31] {
+            let expected = statuses.get_by_location_uid(uid).unwrap().status().clone();
+            assert_eq!(repo.fetch_region_alert(&DemoApi, uid).await?, expected);
+        }
+        assert!(matches!(
+            repo.fetch_region_alert(&DemoApi, 0).await,
+            Err(AppError::InvalidLocationUid(0))
+        ));
+        assert!(repo.fetch_region_alert(&DemoApi, 32).await.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_fetch_region_alert_falls_back_to_snapshot() -> Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", mockito::Matcher::Any)
+            .with_status(500)
+            .create_async()
+            .await;
+        let api = AlertsInUaClient::new(&server.url(), "token")?
+            .with_retry(0, std::time::Duration::ZERO);
+        let repo = temp_repository("ralertsinua_test_statuses_region_fallback.txt");
+        assert!(matches!(
+            repo.fetch_region_alert(&api, 31).await,
+            Err(AppError::ApiError(ApiError::InternalServerError))
+        ));
+
+        // "Київ" (31) is active, "Луганська область" (16) is partly active
+        repo.insert_alerts_string("NNNNNNNNNANNPNNNNNNNNNNNNNN")?;
+
+        assert_eq!(repo.fetch_region_alert(&api, 31).await?, AlertStatus::A);
+        assert_eq!(repo.fetch_region_alert(&api, 16).await?, AlertStatus::P);
+        Ok(())
+    }
+
+    #[test]
+    fn test_fetch_alerts_history() -> Result<()> {
+        let repo = temp_repository("ralertsinua_test_statuses_history.txt");
+        let now = OffsetDateTime::now_utc();
+        let statuses = [
+            "NNNNNNNNNNNNNNNNNNNNNNNNNNN",
+            "ANNNNNNNNNNNNNNNNNNNNNNNNNN",
+            "AANNNNNNNNNNNNNNNNNNNNNNNNN",
+        ];
+        for (i, data) in statuses.iter().enumerate() {
+            repo.insert_alerts_string_at(data, now + time::Duration::minutes(i as i64))?;
+        }
+
+        let history = repo.fetch_alerts_history(2)?;
+
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].1, statuses[2]);
+        assert_eq!(history[1].1, statuses[1]);
+        assert!(history[0].0 > history[1].0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_fetch_active_counts() -> Result<()> {
+        assert_eq!(active_count("NNNNNNNNNANNANNNNNNNNNNNNNN"), 2);
+        assert_eq!(active_count("PPNNNNNNNNNNNNNNNNNNNNNNNNN"), 0);
+
+        let repo = temp_repository("ralertsinua_test_statuses_active_counts.txt");
+        let now = OffsetDateTime::now_utc();
+        for (i, data) in ["ANNNNNNNNNNNNNNNNNNNNNNNNNN", "AAANNNNNNNNNNNNNNNNNNNNNNNN"]
+            .iter()
+            .enumerate()
+        {
+            repo.insert_alerts_string_at(data, now + time::Duration::minutes(i as i64))?;
+        }
+
+        // fewer snapshots than requested, padded with zeros before oldest one
+        assert_eq!(repo.fetch_active_counts(4)?, vec![0, 0, 1, 3]);
+        assert_eq!(repo.fetch_active_counts(1)?, vec![3]);
+
+        let memory = MemoryDataSource::new(vec!["A".repeat(27), "P".repeat(27)]);
+        assert_eq!(memory.fetch_active_counts(3)?, vec![0, 27, 0]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_migrate_adds_created_at() -> Result<()> {
+        let repo = temp_repository("ralertsinua_test_statuses_migrate.txt");
+        std::fs::write(repo.path(), "NNNNNNNNNNNNNNNNNNNNNNNNNNN\n")?;
+
+        repo.migrate()?;
+
+        let contents = std::fs::read_to_string(repo.path())?;
+        assert!(contents.starts_with("1970-01-01T00:00:00Z\t"));
+        assert_eq!(repo.fetch_alerts_history(10)?.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_open_creates_storage() -> Result<()> {
+        let dir = std::env::temp_dir().join("ralertsinua_test_open");
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = dir.join("nested").join(STATUSES_FILE);
+
+        let repo = DataRepository::open(path.clone())?;
+
+        assert!(path.exists());
+        assert_eq!(repo.fetch_last_known_alerts_string()?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_same_alerts_string_is_idempotent() -> Result<()> {
+        let repo = temp_repository("ralertsinua_test_statuses_idempotent.txt");
+
+        repo.insert_alerts_string("ANNNNNNNNNNNNNNNNNNNNNNNNNN")?;
+        repo.insert_alerts_string("ANNNNNNNNNNNNNNNNNNNNNNNNNN")?;
+        assert_eq!(repo.fetch_alerts_history(10)?.len(), 1);
+
+        repo.insert_alerts_string("NNNNNNNNNNNNNNNNNNNNNNNNNNN")?;
+        assert_eq!(repo.fetch_alerts_history(10)?.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_appends_and_rotates() -> Result<()> {
+        let repo =
+            temp_repository("ralertsinua_test_statuses_rotate.txt").with_max_size(100);
+        let _ = std::fs::remove_file(repo.rotated_path());
+        // whole seconds keep lines 49 bytes long
+        let now = OffsetDateTime::UNIX_EPOCH;
+        let statuses = [
+            "NNNNNNNNNNNNNNNNNNNNNNNNNNN",
+            "ANNNNNNNNNNNNNNNNNNNNNNNNNN",
+            "AANNNNNNNNNNNNNNNNNNNNNNNNN",
+        ];
+
+        repo.insert_alerts_string_at(statuses[0], now)?;
+        let before = std::fs::read_to_string(repo.path())?;
+        repo.insert_alerts_string_at(statuses[1], now + time::Duration::minutes(1))?;
+        // appended, previous contents untouched
+        assert!(std::fs::read_to_string(repo.path())?.starts_with(&before));
+        assert!(!repo.rotated_path().exists());
+
+        repo.insert_alerts_string_at(statuses[2], now + time::Duration::minutes(2))?;
+
+        // 3 lines of 49 bytes exceed the limit, new file starts with the last snapshot
+        let current = std::fs::read_to_string(repo.path())?;
+        assert_eq!(current.lines().count(), 1);
+        assert!(current.ends_with(&format!("{}\n", statuses[2])));
+        assert_eq!(
+            std::fs::read_to_string(repo.rotated_path())?
+                .lines()
+                .count(),
+            3
+        );
+        let reopened = DataRepository::new(repo.path().to_path_buf());
+        assert_eq!(
+            reopened.fetch_last_known_alerts_string()?.as_deref(),
+            Some(statuses[2])
+        );
+        assert_eq!(reopened.fetch_alerts_history(10)?.len(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_export_geojson() -> Result<()> {
+        let repo = temp_repository("ralertsinua_test_statuses_geojson.txt");
+        repo.insert_alerts_string("ANNAANNANNNPANANANNNNAANNNN")?;
+        let locations = ralertsinua_geo::AlertsInUaGeoClient::default().locations;
+
+        let geojson: geojson::GeoJson = repo.export_geojson(&locations)?.parse().unwrap();
+
+        let geojson::GeoJson::FeatureCollection(collection) = geojson else {
+            panic!("expected FeatureCollection");
+        };
+        assert_eq!(collection.features.len(), 27);
+        let kyiv = collection
+            .features
+            .iter()
+            .find(|f| f.property("location_uid") == Some(&31.into()))
+            .unwrap();
+        assert_eq!(kyiv.property("name_en"), Some(&"Kyiv".into()));
+        assert!(kyiv.property("status").is_some());
+        Ok(())
+    }
+}
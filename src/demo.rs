@@ -0,0 +1,153 @@
+use async_trait::async_trait;
+use ralertsinua_http::{AlertsInUaApi, ApiError};
+use ralertsinua_models::*;
+
+type Result<T> = miette::Result<T, ApiError>;
+
+/// Sample response of `/alerts/active.json`, served in demo mode
+pub const DEMO_ALERTS_RESPONSE: &str = r#"{
+  "alerts": [
+    {
+      "id": 8757,
+      "location_title": "Луганська область",
+      "location_type": "oblast",
+      "started_at": "2022-04-04T16:45:39.000Z",
+      "finished_at": null,
+      "updated_at": "2023-10-29T18:22:37.357Z",
+      "alert_type": "air_raid",
+      "location_uid": "16",
+      "location_oblast": "Луганська область",
+      "location_oblast_uid": 16,
+      "notes": null,
+      "calculated": null
+    },
+    {
+      "id": 28288,
+      "location_title": "Автономна Республіка Крим",
+      "location_type": "oblast",
+      "started_at": "2022-12-10T22:22:00.000Z",
+      "finished_at": null,
+      "updated_at": "2023-10-29T16:56:12.340Z",
+      "alert_type": "air_raid",
+      "location_uid": "29",
+      "location_oblast": "Автономна Республіка Крим",
+      "location_oblast_uid": 29,
+      "notes": "Згідно інформації з Офіційних карт тривог",
+      "calculated": null
+    },
+    {
+      "id": 71235,
+      "location_title": "м. Київ",
+      "location_type": "city",
+      "started_at": "2024-05-05T15:48:31.000Z",
+      "finished_at": null,
+      "updated_at": "2024-05-05T15:48:31.818Z",
+      "alert_type": "air_raid",
+      "location_uid": "31",
+      "location_oblast": "м. Київ",
+      "location_oblast_uid": 31,
+      "notes": "Загроза застосування БПЛА",
+      "calculated": null
+    },
+    {
+      "id": 71240,
+      "location_title": "Куп'янська територіальна громада",
+      "location_type": "hromada",
+      "started_at": "2024-05-05T16:02:11.000Z",
+      "finished_at": null,
+      "updated_at": "2024-05-05T16:02:11.418Z",
+      "alert_type": "air_raid",
+      "location_uid": "1293",
+      "location_oblast": "Харківська область",
+      "location_oblast_uid": 22,
+      "notes": null,
+      "calculated": null
+    },
+    {
+      "id": 71244,
+      "location_title": "Донецька область",
+      "location_type": "oblast",
+      "started_at": "2024-05-05T16:10:00.000Z",
+      "finished_at": null,
+      "updated_at": "2024-05-05T16:10:00.102Z",
+      "alert_type": "artillery_shelling",
+      "location_uid": "28",
+      "location_oblast": "Донецька область",
+      "location_oblast_uid": 28,
+      "notes": null,
+      "calculated": null
+    }
+  ],
+  "meta": {
+    "last_updated_at": "2024/05/05 16:10:45 +0000",
+    "type": "full"
+  },
+  "disclaimer": "Demo data, not real alerts"
+}"#;
+
+/// API client serving bundled sample data without network, for `--demo` mode
+#[derive(Debug, Default)]
+pub struct DemoApi;
+
+impl DemoApi {
+    fn alerts() -> Result<Alerts> {
+        serde_json::from_str(DEMO_ALERTS_RESPONSE).map_err(ApiError::from)
+    }
+
+    /// Air raid statuses synthesized from demo alerts
+    fn statuses() -> Result<AirRaidAlertOblastStatuses> {
+        Ok(Self::alerts()?.get_oblast_statuses_by_alert_type(AlertType::AirRaid))
+    }
+}
+
+#[async_trait]
+impl AlertsInUaApi for DemoApi {
+    async fn get_active_alerts(&self) -> Result<Alerts> {
+        Self::alerts()
+    }
+
+    async fn get_alerts_history(&self, location_aid: &i8, period: &str) -> Result<Alerts> {
+        let alerts = Self::alerts()?.get_alerts_by_location_uid(i32::from(*location_aid));
+        Ok(Alerts::from(alerts))
+    }
+
+    async fn get_air_raid_alert_status(&self, location_aid: &i8) -> Result<String> {
+        let status = Self::statuses()?
+            .get_by_location_uid(i32::from(*location_aid))
+            // variant name is the single-char status, e.g. "A"
+            .map(|os| format!("{:?}", os.status()))
+            .unwrap_or_default();
+        Ok(status)
+    }
+
+    async fn get_air_raid_alert_statuses_by_location(
+        &self,
+    ) -> Result<AirRaidAlertOblastStatuses> {
+        Self::statuses()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_demo_api() -> Result<()> {
+        let api = DemoApi;
+
+        let alerts = api.get_active_alerts().await?;
+        assert_eq!(alerts.len(), 5);
+
+        let statuses = api.get_air_raid_alert_statuses_by_location().await?;
+        assert_eq!(statuses.raw_data().len(), 27);
+        assert_eq!(statuses.active_alerts_count(), 3);
+        // hromada-level alert in "Харківська область"
+        assert_eq!(statuses.partly_active_alerts_count(), 1);
+        // artillery shelling is not an air raid
+        assert!(statuses.get_by_location_uid(28).unwrap().is_no_alert());
+
+        assert_eq!(api.get_air_raid_alert_status(&16).await?, "A");
+        assert_eq!(api.get_alerts_history(&31, "week_ago").await?.len(), 1);
+        Ok(())
+    }
+}
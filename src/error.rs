@@ -26,6 +26,28 @@ pub enum AppError {
     #[error("API error")]
     #[diagnostic(transparent)]
     ApiError(#[from] ralertsinua_http::ApiError),
+    #[error("unknown location uid: {0}")]
+    #[diagnostic(help("expected uid of one of 27 oblasts, e.g. 31 for Kyiv"))]
+    InvalidLocationUid(i32),
+    #[error("unknown region '{0}', expected location uid or one of: {1}")]
+    UnknownRegion(String, String),
+    #[error("ambiguous region '{0}', did you mean one of: {1}")]
+    AmbiguousRegion(String, String),
+    #[error("invalid key binding: {0}")]
+    #[diagnostic(help(
+        "expected action name mapped to key(s), e.g. Refresh = \"ctrl+r\""
+    ))]
+    InvalidKeyBinding(String),
+    #[error("key '{0}' is bound to both {1} and {2}")]
+    #[diagnostic(help("bind one of the actions to another key in [keybindings]"))]
+    KeyBindingConflict(String, Box<Action>, Box<Action>),
+    #[error("unknown locale '{0}', expected one of: {1}")]
+    InvalidLocale(String, String),
+    #[error("invalid config file {0:?}: {1}")]
+    #[diagnostic(help(
+        "fix or remove the reported key, see README for supported settings"
+    ))]
+    InvalidConfig(std::path::PathBuf, String),
     #[error("unknown error")]
     Unknown,
 }
@@ -0,0 +1,392 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{action::Action, error::*};
+
+type Result<T> = miette::Result<T, AppError>;
+
+/// Key or keys bound to action in `[keybindings]` table of config file
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum KeySettings {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeySettings {
+    fn keys(&self) -> &[String] {
+        match self {
+            KeySettings::One(key) => std::slice::from_ref(key),
+            KeySettings::Many(keys) => keys,
+        }
+    }
+}
+
+/// `[keybindings]` table of config file, action name to key(s), e.g. `Refresh = "u"`
+pub type KeyBindingsSettings = BTreeMap<String, KeySettings>;
+
+/// Keys bound to actions in map and list modes, see [`crate::mode::handle_key`].
+/// Components get the actions, not the keys, so every key listed in help can be rebound
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyBindings(HashMap<KeyEvent, Action>);
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        let bindings = [
+            ("q", Action::Quit),
+            ("/", Action::SearchStart),
+            ("g", Action::SelectTop),
+            ("G", Action::SelectBottom),
+            ("n", Action::NextActive),
+            ("N", Action::PrevActive),
+            ("down", Action::SelectNext),
+            ("j", Action::SelectNext),
+            ("up", Action::SelectPrevious),
+            ("k", Action::SelectPrevious),
+            ("esc", Action::Unselect),
+            ("right", Action::NextTab),
+            ("left", Action::PreviousTab),
+            ("+", Action::ZoomIn),
+            ("=", Action::ZoomIn),
+            ("-", Action::ZoomOut),
+            ("enter", Action::ZoomToSelected),
+            ("backspace", Action::ZoomReset),
+            ("shift+left", Action::PanLeft),
+            ("shift+right", Action::PanRight),
+            ("shift+up", Action::PanUp),
+            ("shift+down", Action::PanDown),
+            ("m", Action::ToggleFullscreenMap),
+            ("l", Action::Locale),
+            ("r", Action::Refresh),
+            ("R", Action::Reload),
+            ("F", Action::ToggleFps),
+            ("t", Action::CycleAlertTypeFilter),
+            ("s", Action::CycleSort),
+            ("y", Action::CopySummary),
+            ("?", Action::ToggleHelp),
+            ("z", Action::Suspend),
+        ]
+        .into_iter()
+        .map(|(key, action)| (parse_key(key).expect("valid default key"), action))
+        .collect();
+        Self(bindings)
+    }
+}
+
+impl KeyBindings {
+    /// Default bindings with actions from `settings` rebound to given keys.
+    /// Fails on unknown action or key, and on key bound to two actions
+    pub fn from_settings(settings: &KeyBindingsSettings) -> Result<Self> {
+        let mut overrides: HashMap<KeyEvent, Action> = HashMap::new();
+        for (name, keys) in settings {
+            let action = parse_action(name)?;
+            for key in keys.keys() {
+                let key_event = parse_key(key)?;
+                if let Some(other) = overrides.insert(key_event, action.clone()) {
+                    if other != action {
+                        return Err(AppError::KeyBindingConflict(
+                            key.clone(),
+                            Box::new(other),
+                            Box::new(action),
+                        ));
+                    }
+                }
+            }
+        }
+
+        let Self(mut bindings) = Self::default();
+        // rebound actions lose their default keys
+        bindings.retain(|_, action| !overrides.values().any(|a| a == action));
+        for (key_event, action) in overrides {
+            if let Some(other) = bindings.get(&key_event) {
+                return Err(AppError::KeyBindingConflict(
+                    format_key(&key_event),
+                    Box::new(other.clone()),
+                    Box::new(action),
+                ));
+            }
+            bindings.insert(key_event, action);
+        }
+        Ok(Self(bindings))
+    }
+
+    /// Action bound to `key_event`, kind and state of the event are ignored
+    pub fn get(&self, key_event: &KeyEvent) -> Option<&Action> {
+        self.0
+            .get(&KeyEvent::new(key_event.code, key_event.modifiers))
+    }
+
+    /// Keys bound to `action`, special keys first, e.g. `↑, k`
+    pub fn keys(&self, action: &Action) -> Vec<KeyEvent> {
+        let mut keys: Vec<KeyEvent> = self
+            .0
+            .iter()
+            .filter(|(_, a)| *a == action)
+            .map(|(key_event, _)| *key_event)
+            .collect();
+        keys.sort_by_key(|k| {
+            let is_char = matches!(k.code, KeyCode::Char(_));
+            (k.modifiers.bits(), is_char, display_key(k))
+        });
+        keys
+    }
+}
+
+/// Action without payload by its name, e.g. `Refresh`
+fn parse_action(name: &str) -> Result<Action> {
+    serde_json::from_value(serde_json::Value::String(name.to_string()))
+        .map_err(|_| AppError::InvalidKeyBinding(format!("unknown action '{}'", name)))
+}
+
+/// Parse key like `q`, `G`, `ctrl+r`, `shift+left` or `f5`, modifiers are case-insensitive
+pub fn parse_key(value: &str) -> Result<KeyEvent> {
+    let invalid = || AppError::InvalidKeyBinding(format!("unknown key '{}'", value));
+    let (modifiers, key) = match value.rsplit_once('+') {
+        // `+` itself and e.g. `ctrl++`
+        Some((prefix, "")) if !prefix.is_empty() => (prefix.trim_end_matches('+'), "+"),
+        Some((prefix, key)) if !prefix.is_empty() => (prefix, key),
+        _ => ("", value),
+    };
+
+    let mut key_modifiers = KeyModifiers::NONE;
+    for modifier in modifiers.split('+').filter(|m| !m.is_empty()) {
+        key_modifiers |= match modifier.to_lowercase().as_str() {
+            "ctrl" | "control" => KeyModifiers::CONTROL,
+            "alt" => KeyModifiers::ALT,
+            "shift" => KeyModifiers::SHIFT,
+            _ => return Err(invalid()),
+        };
+    }
+
+    let mut chars = key.chars();
+    let code = match (chars.next(), chars.next()) {
+        (Some(c), None) => KeyCode::Char(c),
+        _ => match key.to_lowercase().as_str() {
+            "esc" => KeyCode::Esc,
+            "enter" => KeyCode::Enter,
+            "tab" => KeyCode::Tab,
+            "backspace" => KeyCode::Backspace,
+            "space" => KeyCode::Char(' '),
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            f => f
+                .strip_prefix('f')
+                .and_then(|n| n.parse().ok())
+                .map(KeyCode::F)
+                .ok_or_else(invalid)?,
+        },
+    };
+    Ok(KeyEvent::new(code, key_modifiers))
+}
+
+/// Key in the format accepted by [`parse_key`], for error messages
+fn format_key(key_event: &KeyEvent) -> String {
+    let mut key = String::new();
+    for (modifier, name) in [
+        (KeyModifiers::CONTROL, "ctrl+"),
+        (KeyModifiers::ALT, "alt+"),
+        (KeyModifiers::SHIFT, "shift+"),
+    ] {
+        if key_event.modifiers.contains(modifier) {
+            key.push_str(name);
+        }
+    }
+    match key_event.code {
+        KeyCode::Char(c) => key.push(c),
+        KeyCode::F(n) => key.push_str(&format!("f{}", n)),
+        code => key.push_str(&format!("{:?}", code).to_lowercase()),
+    }
+    key
+}
+
+/// Key as shown in help, e.g. `Ctrl+r`, `Shift+←` or `Esc`
+pub fn display_key(key_event: &KeyEvent) -> String {
+    let mut key = String::new();
+    for (modifier, name) in [
+        (KeyModifiers::CONTROL, "Ctrl+"),
+        (KeyModifiers::ALT, "Alt+"),
+        (KeyModifiers::SHIFT, "Shift+"),
+    ] {
+        if key_event.modifiers.contains(modifier) {
+            key.push_str(name);
+        }
+    }
+    match key_event.code {
+        KeyCode::Char(' ') => key.push_str("Space"),
+        KeyCode::Char(c) => key.push(c),
+        KeyCode::Left => key.push('←'),
+        KeyCode::Right => key.push('→'),
+        KeyCode::Up => key.push('↑'),
+        KeyCode::Down => key.push('↓'),
+        KeyCode::F(n) => key.push_str(&format!("F{}", n)),
+        code => key.push_str(&format!("{:?}", code)),
+    }
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(toml: &str) -> KeyBindingsSettings {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn test_parse_key() -> Result<()> {
+        assert_eq!(
+            parse_key("q")?,
+            KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            parse_key("Ctrl+r")?,
+            KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(
+            parse_key("shift+left")?,
+            KeyEvent::new(KeyCode::Left, KeyModifiers::SHIFT)
+        );
+        assert_eq!(
+            parse_key("+")?,
+            KeyEvent::new(KeyCode::Char('+'), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            parse_key("f5")?,
+            KeyEvent::new(KeyCode::F(5), KeyModifiers::NONE)
+        );
+        assert!(parse_key("hyper+q").is_err());
+        assert!(parse_key("qq").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_custom_binding() -> Result<()> {
+        let bindings = KeyBindings::from_settings(&settings(
+            "Refresh = \"u\"\nQuit = [\"x\", \"ctrl+q\"]",
+        ))?;
+        let key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+
+        assert_eq!(bindings.get(&key('u')), Some(&Action::Refresh));
+        assert_eq!(bindings.get(&key('x')), Some(&Action::Quit));
+        assert_eq!(
+            bindings.get(&KeyEvent::new(KeyCode::Char('q'), KeyModifiers::CONTROL)),
+            Some(&Action::Quit)
+        );
+        // default keys of rebound actions are freed, others are kept
+        assert_eq!(bindings.get(&key('r')), None);
+        assert_eq!(bindings.get(&key('q')), None);
+        assert_eq!(bindings.get(&key('g')), Some(&Action::SelectTop));
+        // uppercase letter is matched with or without shift reported
+        assert_eq!(
+            bindings.get(&KeyEvent::new(KeyCode::Char('G'), KeyModifiers::SHIFT)),
+            Some(&Action::SelectBottom)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_conflicting_bindings() {
+        let result =
+            KeyBindings::from_settings(&settings("Refresh = \"x\"\nReload = \"x\""));
+        assert!(matches!(
+            result,
+            Err(AppError::KeyBindingConflict(_, a, b))
+                if *a == Action::Refresh && *b == Action::Reload
+        ));
+
+        // `s` is still bound to CycleSort
+        let result = KeyBindings::from_settings(&settings("Refresh = \"s\""));
+        assert!(matches!(
+            result,
+            Err(AppError::KeyBindingConflict(key, a, b))
+                if key == "s" && *a == Action::CycleSort && *b == Action::Refresh
+        ));
+
+        // rebinding both is fine
+        assert!(KeyBindings::from_settings(&settings(
+            "Refresh = \"s\"\nCycleSort = \"o\""
+        ))
+        .is_ok());
+    }
+
+    #[test]
+    fn test_binding_list_navigation_key() -> Result<()> {
+        let j = KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE);
+
+        // `j` is still bound to SelectNext
+        let result = KeyBindings::from_settings(&settings("Refresh = \"j\""));
+        assert!(matches!(
+            result,
+            Err(AppError::KeyBindingConflict(key, a, b))
+                if key == "j" && *a == Action::SelectNext && *b == Action::Refresh
+        ));
+
+        // rebound list navigation frees `j`
+        let bindings = KeyBindings::from_settings(&settings(
+            "Refresh = \"j\"\nSelectNext = [\"down\", \"ctrl+n\"]",
+        ))?;
+        assert_eq!(bindings.get(&j), Some(&Action::Refresh));
+        assert_eq!(
+            bindings.get(&KeyEvent::new(KeyCode::Down, KeyModifiers::NONE)),
+            Some(&Action::SelectNext)
+        );
+        assert_eq!(
+            bindings.get(&KeyEvent::new(KeyCode::Char('k'), KeyModifiers::NONE)),
+            Some(&Action::SelectPrevious)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_keys_of_action() -> Result<()> {
+        let bindings = KeyBindings::default();
+        let labels = |action| {
+            bindings
+                .keys(&action)
+                .iter()
+                .map(display_key)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(labels(Action::SelectPrevious), ["↑", "k"]);
+        assert_eq!(labels(Action::ZoomIn), ["+", "="]);
+        assert_eq!(labels(Action::PanLeft), ["Shift+←"]);
+        assert_eq!(labels(Action::Unselect), ["Esc"]);
+        assert!(labels(Action::Tick).is_empty());
+
+        let bindings = KeyBindings::from_settings(&settings("ZoomReset = \"ctrl+z\""))?;
+        assert_eq!(
+            bindings
+                .keys(&Action::ZoomReset)
+                .iter()
+                .map(display_key)
+                .collect::<Vec<_>>(),
+            ["Ctrl+z"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_bindings() {
+        for toml in [
+            "Unknown = \"x\"",
+            "SelectTab = \"x\"",
+            "Refresh = \"nokey\"",
+        ] {
+            assert!(
+                matches!(
+                    KeyBindings::from_settings(&settings(toml)),
+                    Err(AppError::InvalidKeyBinding(_))
+                ),
+                "{}",
+                toml
+            );
+        }
+    }
+}
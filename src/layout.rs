@@ -1,6 +1,9 @@
 use ratatui::prelude::*;
 use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
 use strum::{Display, EnumIter, FromRepr, IntoEnumIterator};
+use tracing::warn;
 
 use crate::constants::*;
 
@@ -48,6 +51,11 @@ pub enum LayoutArea {
     Inner,
     Left,
     Right,
+    RightMiddle,
+    RightBottom,
+    /// Full-width strip below left and right panes, only shown if its height is configured
+    Bottom,
+    Legend,
     Footer,
     #[default]
     Hidden,
@@ -55,3 +63,87 @@ pub enum LayoutArea {
 
 #[derive(Debug, Clone)]
 pub struct LayoutPoint(pub LayoutArea, pub Option<LayoutTab>);
+
+/// Split ratios of main layout, from `[layout]` table of config file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LayoutSettings {
+    /// Width of left (map) pane in percent, the rest goes to right pane
+    pub left_percent: u16,
+    /// Height of bottom strip in rows, `0` hides it. When shown, it holds location details
+    /// instead of right bottom pane
+    pub bottom_height: u16,
+}
+
+impl LayoutSettings {
+    pub const DEFAULT: Self = Self {
+        left_percent: 75,
+        bottom_height: 0,
+    };
+    const LEFT_PERCENT_RANGE: std::ops::RangeInclusive<u16> = 20..=90;
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Settings with out of range values replaced by defaults
+    pub fn validated(self) -> Self {
+        let mut settings = self;
+        if !Self::LEFT_PERCENT_RANGE.contains(&self.left_percent) {
+            warn!(
+                "Invalid layout left_percent {}, expected {:?}, using default",
+                self.left_percent,
+                Self::LEFT_PERCENT_RANGE
+            );
+            settings.left_percent = Self::DEFAULT.left_percent;
+        }
+        settings
+    }
+
+    /// Area of location details pane
+    pub fn details_area(&self) -> LayoutArea {
+        if self.bottom_height > 0 {
+            LayoutArea::Bottom
+        } else {
+            LayoutArea::RightBottom
+        }
+    }
+}
+
+impl Default for LayoutSettings {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+static LAYOUT_SETTINGS: RwLock<LayoutSettings> = RwLock::new(LayoutSettings::DEFAULT);
+
+/// Layout settings used to place components, see [`set_layout_settings`]
+pub fn layout_settings() -> LayoutSettings {
+    *LAYOUT_SETTINGS.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Use `settings` for layout of all components, set once on start
+pub fn set_layout_settings(settings: LayoutSettings) {
+    *LAYOUT_SETTINGS.write().unwrap_or_else(|e| e.into_inner()) = settings.validated();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout_settings_validated() {
+        let settings = LayoutSettings {
+            left_percent: 100,
+            bottom_height: 4,
+        };
+        assert_eq!(
+            settings.validated(),
+            LayoutSettings {
+                left_percent: 75,
+                bottom_height: 4
+            }
+        );
+    }
+}
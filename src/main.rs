@@ -3,13 +3,22 @@
 pub mod action;
 pub mod app;
 pub mod cli;
+pub mod clipboard;
 pub mod components;
 pub mod config;
 pub mod constants;
+pub mod data;
+pub mod demo;
 pub mod error;
 pub mod fs;
+pub mod keybindings;
 pub mod layout;
 pub mod mode;
+pub mod notifications;
+pub mod output;
+pub mod recording;
+pub mod state;
+pub mod theme;
 pub mod tui;
 pub mod tui_helpers;
 pub mod utils;
@@ -17,21 +26,26 @@ pub mod utils;
 rust_i18n::i18n!();
 
 use clap::Parser;
-use cli::Cli;
+use cli::{Cli, Command, ConfigCommand};
 #[allow(unused_imports)]
 use miette::{miette, IntoDiagnostic, Result};
 use ralertsinua_geo::*;
 use ralertsinua_http::*;
 #[allow(unused_imports)]
 use std::{
-    io::{stdin, stdout, Write},
+    io::{stdin, stdout, IsTerminal, Write},
     time::Duration,
     {path::PathBuf, sync::Arc},
 };
 use tracing::{debug, error, warn};
 use tui_logger::set_level_for_target;
 
-use crate::{app::App, config::Config, utils::*};
+use crate::{
+    app::App,
+    config::{env_var, resolve_log_level, Config, FileSettings},
+    state::State,
+    utils::*,
+};
 
 async fn tokio_main() -> Result<()> {
     dotenvy::dotenv().ok();
@@ -43,18 +57,39 @@ async fn tokio_main() -> Result<()> {
     if config.log_file().is_empty() {
         if !args.log_file.is_empty() {
             config.set_log_file(args.log_file.to_string());
-            log_file = Some(args.log_file);
+            log_file = Some(args.log_file.clone());
         }
     } else {
         log_file = Some(config.log_file().to_string());
     }
 
-    initialize_logging(log_file)?;
+    // file settings go first, log level may come from there
+    let file_settings_path = FileSettings::path();
+    let file_settings = FileSettings::load(&file_settings_path)?;
+    let log_level = resolve_log_level(&args, &env_var, &file_settings)?;
+
+    initialize_logging(log_file, log_filter(log_level))?;
     set_level_for_target("app", log::LevelFilter::Debug);
-    debug!(target:"app", "initialized logging");
+    debug!(target:"app", "initialized logging, level={:?}", log_level);
     initialize_panic_handler()?;
 
-    if config.token().is_empty() {
+    config.merge(&file_settings, &args, &env_var)?;
+    config.apply_locale();
+
+    let recording = match &args.replay {
+        Some(path) => Some(recording::load_recording(path)?),
+        None => None,
+    };
+    // recorded actions already contain fetched alerts, nothing is fetched live
+    let offline = args.demo || recording.is_some();
+
+    if args.demo {
+        debug!(target: "app", "demo mode enabled by parameters, token is not required");
+    } else if recording.is_some() {
+        debug!(target: "app", "replay enabled by parameters, token is not required");
+    } else if args.command.is_some() {
+        debug!(target: "app", "subcommand given, token is not required");
+    } else if config.token().is_empty() {
         warn!(target: "app", "token is empty, asking user for token");
         print!("enter your 'alerts.in.ua' token: ");
         stdout().flush().into_diagnostic()?;
@@ -65,14 +100,57 @@ async fn tokio_main() -> Result<()> {
 
         if token.is_empty() {
             error!(target: "app", "token cannot be empty, exiting");
-            return Err(miette!("token cannot be empty"));
+            return Err(miette!(
+                "token is missing: pass '--token', set 'ALERTSINUA_TOKEN' env or add 'token' to {:?}",
+                file_settings_path
+            ));
         } else {
             debug!(target: "app", "token from user input accepted");
             config.set_token(token)?;
         }
-    } else if !args.token.is_empty() {
-        debug!(target: "app", "token from parameters accepted, ignore env");
-        config.set_token(args.token)?;
+    }
+
+    if let Some(tick_rate) = args.tick_rate {
+        debug!(target: "app", "tick rate from parameters accepted, ignore env");
+        config.set_tick_rate(tick_rate);
+    }
+
+    if let Some(frame_rate) = args.frame_rate {
+        debug!(target: "app", "frame rate from parameters accepted, ignore env");
+        config.set_frame_rate(frame_rate);
+    }
+
+    if args.notifications {
+        debug!(target: "app", "desktop notifications enabled by parameters");
+        config.set_notifications(true);
+    }
+
+    if args.bell {
+        debug!(target: "app", "terminal bell enabled by parameters");
+        config.set_bell(true);
+    }
+
+    if args.no_color {
+        debug!(target: "app", "no-color mode enabled by parameters");
+        config.set_no_color(true);
+    }
+
+    if !args.boundary_path.is_empty() {
+        debug!(target: "app", "boundary path from parameters accepted, ignore env");
+        config.set_boundary_path(args.boundary_path);
+    }
+
+    if !args.locations_path.is_empty() {
+        debug!(target: "app", "locations path from parameters accepted, ignore env");
+        config.set_locations_path(args.locations_path);
+    }
+
+    if let Some(Command::Config {
+        command: ConfigCommand::Print,
+    }) = args.command
+    {
+        print!("{}", config.to_redacted_toml(log_level)?);
+        return Ok(());
     }
 
     // Replace with a reliable public server (e.g., 8.8.8.8:53)
@@ -87,13 +165,80 @@ async fn tokio_main() -> Result<()> {
         }
     };
 
+    if offline {
+        // sample or recorded data is always available
+        config.set_online(true);
+    }
+
     debug!(target: "app", "\n{:?} \n\n-----------", config.settings());
 
-    let api_client: Arc<dyn AlertsInUaApi> =
-        Arc::new(AlertsInUaClient::new(config.base_url(), config.token()));
-    let geo_client: Arc<dyn AlertsInUaGeo> = Arc::new(AlertsInUaGeoClient::default());
+    let api_client: Arc<dyn AlertsInUaApi> = if offline {
+        Arc::new(demo::DemoApi)
+    } else {
+        Arc::new(AlertsInUaClient::new(config.base_url(), config.token())?)
+    };
+    let geo_client: Arc<dyn AlertsInUaGeo> =
+        Arc::new(app::load_geo_client(&config).into_diagnostic()?);
+
+    if args.check {
+        let ok = output::check_connectivity(api_client.as_ref(), &mut stdout()).await?;
+        std::process::exit(if ok { 0 } else { 1 });
+    }
+
+    if let Some(format) = args.output {
+        let locale = config.get_locale();
+        let color = stdout().is_terminal() && !*config.no_color();
+        output::fetch_and_print(
+            api_client.as_ref(),
+            format,
+            locale.as_str(),
+            color,
+            &mut stdout(),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    if let Some(region) = args.region {
+        let location = output::resolve_region(geo_client.as_ref(), &region)?;
+        debug!(target: "app", "watching single region {}", location.location_uid);
+        let interval = Duration::from_secs(*config.polling_interval());
+        output::watch_region(
+            api_client.as_ref(),
+            &location,
+            config.get_locale().as_str(),
+            interval,
+            &mut stdout(),
+        )
+        .await?;
+        return Ok(());
+    }
 
-    let mut app = App::new(config, api_client.clone(), geo_client.clone())?;
+    let mut app = if offline {
+        // sample or recorded data must not replace stored real alerts
+        let data_source = Box::new(data::MemoryDataSource::default());
+        App::with_data_source(
+            config,
+            api_client.clone(),
+            geo_client.clone(),
+            file_settings_path,
+            State::path(),
+            data_source,
+        )?
+    } else {
+        App::new(
+            config,
+            api_client.clone(),
+            geo_client.clone(),
+            file_settings_path,
+            State::path(),
+        )?
+    };
+    if let Some(path) = &args.record {
+        debug!(target: "app", "recording actions to {:?}", path);
+        app.recorder = Some(recording::Recorder::open(path)?);
+    }
+    app.replay = recording;
     app.run().await?;
 
     Ok(())
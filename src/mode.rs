@@ -1,7 +1,258 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use serde::{Deserialize, Serialize};
 
+use crate::{action::Action, keybindings::KeyBindings};
+
+/// Input mode, decides how keys are handled. Changed by dispatched actions only, see [`Mode::next`]
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Mode {
+    /// Map is navigated, e.g. zoomed or panned
     #[default]
     Map,
+    /// Locations list is navigated
+    List,
+    /// Typed characters go to search query instead of key bindings
+    Search,
+    /// Help popup is shown, other key bindings are disabled
+    Help,
+}
+
+impl Mode {
+    /// Mode after `action` is dispatched in this mode
+    pub fn next(self, action: &Action) -> Mode {
+        match (self, action) {
+            (Mode::Search, Action::SearchClear | Action::SearchSubmit) => Mode::Map,
+            (Mode::Search, _) => Mode::Search,
+            (Mode::Help, Action::ToggleHelp) => Mode::Map,
+            (Mode::Help, _) => Mode::Help,
+            (_, Action::ToggleHelp) => Mode::Help,
+            (_, Action::SearchStart) => Mode::Search,
+            (
+                _,
+                Action::SelectIndex(_)
+                | Action::SelectNext
+                | Action::SelectPrevious
+                | Action::Unselect
+                | Action::SelectTop
+                | Action::SelectBottom
+                | Action::NextActive
+                | Action::PrevActive,
+            ) => Mode::List,
+            (
+                _,
+                Action::ZoomIn
+                | Action::ZoomOut
+                | Action::ZoomToSelected
+                | Action::ZoomReset
+                | Action::ToggleFullscreenMap
+                | Action::PanLeft
+                | Action::PanRight
+                | Action::PanUp
+                | Action::PanDown,
+            ) => Mode::Map,
+            (mode, _) => mode,
+        }
+    }
+}
+
+/// Action bound to `key_event` in `mode`, `None` if the key is not bound in it.
+/// Map and list modes use configurable `bindings`, in help mode only help toggle and quit
+/// keys are bound, Esc closes help too. `Ctrl+c` always quits.
+/// Keys not bound here may still be handled by components, except in search and help modes
+pub fn handle_key(
+    mode: Mode,
+    key_event: KeyEvent,
+    bindings: &KeyBindings,
+) -> Option<Action> {
+    let is_ctrl_c = matches!(key_event.code, KeyCode::Char('c') | KeyCode::Char('C'))
+        && key_event.modifiers == KeyModifiers::CONTROL;
+    match mode {
+        Mode::Search => search_key_action(key_event),
+        Mode::Help => match bindings.get(&key_event) {
+            Some(action @ (Action::ToggleHelp | Action::Quit)) => Some(action.clone()),
+            _ if key_event.code == KeyCode::Esc => Some(Action::ToggleHelp),
+            _ if is_ctrl_c => Some(Action::Quit),
+            _ => None,
+        },
+        Mode::Map | Mode::List if is_ctrl_c => Some(Action::Quit),
+        Mode::Map | Mode::List => bindings.get(&key_event).cloned(),
+    }
+}
+
+/// Action for key typed in [`Mode::Search`]: characters (including `q`) go to the query,
+/// only `Ctrl+c` quits
+pub fn search_key_action(key_event: KeyEvent) -> Option<Action> {
+    match key_event.code {
+        KeyCode::Char('c') | KeyCode::Char('C')
+            if key_event.modifiers == KeyModifiers::CONTROL =>
+        {
+            Some(Action::Quit)
+        }
+        KeyCode::Esc => Some(Action::SearchClear),
+        KeyCode::Enter => Some(Action::SearchSubmit),
+        KeyCode::Char(c) => Some(Action::SearchInput(c)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mode_transitions() {
+        let mode = Mode::default();
+        assert_eq!(mode, Mode::Map);
+
+        let mode = mode.next(&Action::SelectIndex(2));
+        assert_eq!(mode, Mode::List);
+        assert_eq!(mode.next(&Action::ZoomIn), Mode::Map);
+        assert_eq!(mode.next(&Action::Tick), Mode::List);
+
+        let mode = mode.next(&Action::SearchStart);
+        assert_eq!(mode, Mode::Search);
+        // list selection follows the query, search goes on
+        assert_eq!(mode.next(&Action::SelectIndex(0)), Mode::Search);
+        assert_eq!(mode.next(&Action::SearchInput('q')), Mode::Search);
+        assert_eq!(mode.next(&Action::SearchSubmit), Mode::Map);
+        assert_eq!(mode.next(&Action::SearchClear), Mode::Map);
+
+        let mode = Mode::Map.next(&Action::ToggleHelp);
+        assert_eq!(mode, Mode::Help);
+        assert_eq!(mode.next(&Action::SelectTop), Mode::Help);
+        assert_eq!(mode.next(&Action::ToggleHelp), Mode::Map);
+    }
+
+    #[test]
+    fn test_handle_key_depends_on_mode() {
+        let bindings = KeyBindings::default();
+        let key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+
+        for mode in [Mode::Map, Mode::List, Mode::Help] {
+            assert_eq!(
+                handle_key(mode, key('q'), &bindings),
+                Some(Action::Quit),
+                "{:?}",
+                mode
+            );
+        }
+        assert_eq!(
+            handle_key(Mode::Search, key('q'), &bindings),
+            Some(Action::SearchInput('q'))
+        );
+
+        assert_eq!(
+            handle_key(Mode::Map, key('g'), &bindings),
+            Some(Action::SelectTop)
+        );
+        assert_eq!(
+            handle_key(Mode::List, key('g'), &bindings),
+            Some(Action::SelectTop)
+        );
+        assert_eq!(handle_key(Mode::Help, key('g'), &bindings), None);
+        assert_eq!(
+            handle_key(Mode::Search, key('g'), &bindings),
+            Some(Action::SearchInput('g'))
+        );
+
+        assert_eq!(
+            handle_key(Mode::Map, key('?'), &bindings),
+            Some(Action::ToggleHelp)
+        );
+        assert_eq!(
+            handle_key(Mode::Help, key('?'), &bindings),
+            Some(Action::ToggleHelp)
+        );
+        assert_eq!(
+            handle_key(Mode::Search, key('?'), &bindings),
+            Some(Action::SearchInput('?'))
+        );
+
+        let esc = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(
+            handle_key(Mode::Map, esc, &bindings),
+            Some(Action::Unselect)
+        );
+        assert_eq!(
+            handle_key(Mode::Help, esc, &bindings),
+            Some(Action::ToggleHelp)
+        );
+        assert_eq!(
+            handle_key(Mode::Search, esc, &bindings),
+            Some(Action::SearchClear)
+        );
+    }
+
+    #[test]
+    fn test_handle_key_tabs_and_pan() {
+        let bindings = KeyBindings::default();
+        let right = KeyEvent::new(KeyCode::Right, KeyModifiers::NONE);
+        let shift_right = KeyEvent::new(KeyCode::Right, KeyModifiers::SHIFT);
+
+        assert_eq!(
+            handle_key(Mode::Map, right, &bindings),
+            Some(Action::NextTab)
+        );
+        assert_eq!(
+            handle_key(Mode::Map, shift_right, &bindings),
+            Some(Action::PanRight)
+        );
+        assert_eq!(
+            handle_key(Mode::List, shift_right, &bindings),
+            Some(Action::PanRight)
+        );
+        assert_eq!(handle_key(Mode::Search, right, &bindings), None);
+        assert_eq!(
+            handle_key(
+                Mode::List,
+                KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL),
+                &bindings
+            ),
+            Some(Action::Quit)
+        );
+    }
+
+    #[test]
+    fn test_handle_key_in_help_mode_uses_bindings() -> miette::Result<()> {
+        let settings = toml::from_str("ToggleHelp = \"h\"\nQuit = \"x\"").unwrap();
+        let bindings = KeyBindings::from_settings(&settings)?;
+        let key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+
+        assert_eq!(
+            handle_key(Mode::Help, key('h'), &bindings),
+            Some(Action::ToggleHelp)
+        );
+        assert_eq!(
+            handle_key(Mode::Help, key('x'), &bindings),
+            Some(Action::Quit)
+        );
+        assert_eq!(handle_key(Mode::Help, key('?'), &bindings), None);
+        assert_eq!(handle_key(Mode::Help, key('q'), &bindings), None);
+        // other bound actions are disabled
+        assert_eq!(handle_key(Mode::Help, key('j'), &bindings), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_key_action() {
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+        assert_eq!(
+            search_key_action(key(KeyCode::Char('q'))),
+            Some(Action::SearchInput('q'))
+        );
+        assert_eq!(
+            search_key_action(key(KeyCode::Esc)),
+            Some(Action::SearchClear)
+        );
+        assert_eq!(
+            search_key_action(key(KeyCode::Enter)),
+            Some(Action::SearchSubmit)
+        );
+        assert_eq!(search_key_action(key(KeyCode::Left)), None);
+        assert_eq!(
+            search_key_action(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            Some(Action::Quit)
+        );
+    }
 }
@@ -0,0 +1,189 @@
+use ralertsinua_models::REGIONS_DATA;
+use std::{
+    io::{stdout, Write},
+    time::{Duration, Instant},
+};
+#[allow(unused_imports)]
+use tracing::{debug, error};
+
+/// Location uids of regions, which entered active alert status between `prev` and `next`
+/// status strings. Regions missing in `prev` (e.g. on first fetch) are not reported.
+pub fn newly_activated(prev: &str, next: &str) -> Vec<usize> {
+    let prev = prev.trim_matches('"');
+    let next = next.trim_matches('"');
+    REGIONS_DATA
+        .iter()
+        .zip(prev.chars().zip(next.chars()))
+        .filter(|(_, (p, n))| *p != 'A' && *n == 'A')
+        .map(|((_, location_uid, _, _), _)| *location_uid as usize)
+        .collect()
+}
+
+/// Localized region name by location uid
+fn region_name(location_uid: usize, locale: &str) -> Option<&'static str> {
+    REGIONS_DATA
+        .iter()
+        .find(|(_, uid, _, _)| *uid as usize == location_uid)
+        .map(|(_, _, name, name_en)| if locale == "uk" { *name } else { *name_en })
+}
+
+/// Show OS desktop notification for each of newly alerted regions
+#[cfg(feature = "notify")]
+pub fn notify_desktop(location_uids: &[usize], locale: &str) {
+    use rust_i18n::t;
+
+    for location_uid in location_uids {
+        let Some(name) = region_name(*location_uid, locale) else {
+            continue;
+        };
+        debug!(target: "app", "desktop notification for location_uid={}", location_uid);
+        if let Err(e) = notify_rust::Notification::new()
+            .summary(&t!("notifications.air_raid"))
+            .body(name)
+            .show()
+        {
+            error!(target: "app", "failed to show desktop notification: {}", e);
+        }
+    }
+}
+
+/// Desktop notifications require `notify` feature, this only logs newly alerted regions
+#[cfg(not(feature = "notify"))]
+pub fn notify_desktop(location_uids: &[usize], locale: &str) {
+    for name in location_uids
+        .iter()
+        .filter_map(|uid| region_name(*uid, locale))
+    {
+        debug!(target: "app", "new air raid alert in {}, built without 'notify' feature", name);
+    }
+}
+
+/// Minimal time between two terminal bells
+pub const BELL_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// Terminal bell, rung at most once per cooldown period
+#[derive(Debug)]
+pub struct Bell {
+    cooldown: Duration,
+    last_rung_at: Option<Instant>,
+}
+
+impl Default for Bell {
+    fn default() -> Self {
+        Self::new(BELL_COOLDOWN)
+    }
+}
+
+impl Bell {
+    pub fn new(cooldown: Duration) -> Self {
+        Self {
+            cooldown,
+            last_rung_at: None,
+        }
+    }
+
+    /// Whether bell may ring at `now`, remembers `now` as last ring if so
+    pub fn try_ring_at(&mut self, now: Instant) -> bool {
+        let cooled_down = match self.last_rung_at {
+            Some(last) => now.duration_since(last) >= self.cooldown,
+            None => true,
+        };
+        if cooled_down {
+            self.last_rung_at = Some(now);
+        }
+        cooled_down
+    }
+
+    /// Emit terminal bell unless it was rung recently
+    pub fn ring(&mut self) {
+        if !self.try_ring_at(Instant::now()) {
+            debug!(target: "app", "terminal bell skipped, cooldown");
+            return;
+        }
+        let mut out = stdout();
+        if let Err(e) = out.write_all(b"\x07").and_then(|_| out.flush()) {
+            error!(target: "app", "failed to ring terminal bell: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_newly_activated() {
+        let prev = "NNNNNNNNNNNNNNNNNNNNNNNNNNN";
+        let next = "ANNNNNNNNAPNNNNNNNNNNNNNNNN";
+
+        // Crimea (29) and Kyiv (31) became active, partial alert in Kyiv Oblast is ignored
+        assert_eq!(newly_activated(prev, next), vec![29, 31]);
+        // already active regions are not reported again
+        assert!(newly_activated(next, next).is_empty());
+        // nothing to compare with on first fetch
+        assert!(newly_activated("", next).is_empty());
+    }
+
+    #[test]
+    fn test_newly_activated_pairs() {
+        let cases = [
+            (
+                "NNNNNNNNNNNNNNNNNNNNNNNNNNN",
+                "NNNNNNNNNNNNNNNNNNNNNNNNNNN",
+                vec![],
+            ),
+            (
+                "AAAAAAAAAAAAAAAAAAAAAAAAAAA",
+                "AAAAAAAAAAAAAAAAAAAAAAAAAAA",
+                vec![],
+            ),
+            (
+                "ANNNNNNNNNNNNNNNNNNNNNNNNNN",
+                "NNNNNNNNNNNNNNNNNNNNNNNNNNN",
+                vec![],
+            ),
+            (
+                "PNNNNNNNNNNNNNNNNNNNNNNNNNN",
+                "ANNNNNNNNNNNNNNNNNNNNNNNNNN",
+                vec![29],
+            ),
+            (
+                "NNNNNNNNNNNNNNNNNNNNNNNNNNN",
+                "NANNNNNNNNNNNNNNNNNNNNNNNNA",
+                vec![8, 25],
+            ),
+            (
+                "\"NNNNNNNNNNNNNNNNNNNNNNNNNNN\"",
+                "\"NNNNNNNNNANNNNNNNNNNNNNNNNN\"",
+                vec![31],
+            ),
+        ];
+
+        for (prev, next, expected) in cases {
+            assert_eq!(
+                newly_activated(prev, next),
+                expected,
+                "{} -> {}",
+                prev,
+                next
+            );
+        }
+    }
+
+    #[test]
+    fn test_bell_cooldown() {
+        let mut bell = Bell::new(Duration::from_secs(60));
+        let now = Instant::now();
+
+        assert!(bell.try_ring_at(now));
+        assert!(!bell.try_ring_at(now + Duration::from_secs(30)));
+        assert!(bell.try_ring_at(now + Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn test_region_name() {
+        assert_eq!(region_name(31, "en"), Some("Kyiv"));
+        assert_eq!(region_name(31, "uk"), Some("Київ"));
+        assert_eq!(region_name(0, "en"), None);
+    }
+}
@@ -0,0 +1,493 @@
+use clap::ValueEnum;
+use ralertsinua_geo::{AlertsInUaGeo, Location};
+use ralertsinua_http::{AlertsInUaApi, ApiError};
+use ralertsinua_models::{
+    AirRaidAlertOblastStatus, AirRaidAlertOblastStatuses, AlertStatus, Alerts,
+};
+use serde::Serialize;
+use std::{
+    io::Write,
+    time::{Duration, Instant},
+};
+use strum::EnumProperty;
+use time::macros::format_description;
+
+use crate::{error::*, utils::alert_type_label};
+
+type Result<T> = miette::Result<T, AppError>;
+
+/// Non-TUI output format, prints alerts once and exits
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// JSON array of `{ region, status }`
+    Json,
+    /// Aligned plain-text table
+    Table,
+}
+
+#[derive(Debug, Serialize)]
+struct RegionStatus<'a> {
+    region: &'a str,
+    status: &'a AlertStatus,
+}
+
+/// Fetch current statuses once and write them to `out` in given format,
+/// `color` enables ANSI colors in table, e.g. when stdout is a TTY
+pub async fn fetch_and_print<W: Write>(
+    api_client: &dyn AlertsInUaApi,
+    format: OutputFormat,
+    locale: &str,
+    color: bool,
+    out: &mut W,
+) -> Result<()> {
+    let statuses = api_client.get_air_raid_alert_statuses_by_location().await?;
+    let rendered = match format {
+        OutputFormat::Json => render_json(&statuses, locale)?,
+        OutputFormat::Table => render_table(&statuses, locale, color),
+    };
+    writeln!(out, "{}", rendered)?;
+    Ok(())
+}
+
+/// Make a single authenticated request and write whether API is reachable and token
+/// is valid, with latency. Returns `true` on success
+pub async fn check_connectivity<W: Write>(
+    api_client: &dyn AlertsInUaApi,
+    out: &mut W,
+) -> Result<bool> {
+    let started_at = Instant::now();
+    let result = api_client.get_air_raid_alert_statuses_by_location().await;
+    let latency = started_at.elapsed().as_millis();
+    match result {
+        Ok(_) => {
+            writeln!(out, "OK: API is reachable, token is valid ({} ms)", latency)?;
+            Ok(true)
+        }
+        Err(ApiError::UnauthorizedError(_)) => {
+            writeln!(out, "FAILED: invalid token ({} ms)", latency)?;
+            Ok(false)
+        }
+        Err(e) => {
+            writeln!(out, "FAILED: API is unreachable: {} ({} ms)", e, latency)?;
+            Ok(false)
+        }
+    }
+}
+
+pub fn render_json(statuses: &AirRaidAlertOblastStatuses, locale: &str) -> Result<String> {
+    let regions: Vec<RegionStatus> = statuses
+        .iter()
+        .map(|s| RegionStatus {
+            region: region_name(s, locale),
+            status: s.status(),
+        })
+        .collect();
+    Ok(serde_json::to_string_pretty(&regions)?)
+}
+
+pub fn render_table(
+    statuses: &AirRaidAlertOblastStatuses,
+    locale: &str,
+    color: bool,
+) -> String {
+    const REGION_HEADER: &str = "Region";
+    const STATUS_HEADER: &str = "Status";
+    let width = statuses
+        .iter()
+        .map(|s| region_name(s, locale).chars().count())
+        .chain(std::iter::once(REGION_HEADER.len()))
+        .max()
+        .unwrap_or_default();
+    let mut lines = vec![
+        format!("{:<width$}  {}", REGION_HEADER, STATUS_HEADER),
+        format!("{}  {}", "-".repeat(width), "-".repeat(STATUS_HEADER.len())),
+    ];
+    lines.extend(statuses.iter().map(|s| {
+        let status = s.status().to_string();
+        let status = match ansi_color(s.status()) {
+            Some(code) if color => format!("\x1b[{}m{}\x1b[0m", code, status),
+            _ => status,
+        };
+        format!("{:<width$}  {}", region_name(s, locale), status)
+    }));
+    lines.join("\n")
+}
+
+/// ANSI SGR foreground code for status color property
+fn ansi_color(status: &AlertStatus) -> Option<u8> {
+    match status.get_str("color")? {
+        "red" => Some(31),
+        "yellow" => Some(33),
+        "blue" => Some(34),
+        "gray" => Some(37),
+        "darkgray" => Some(90),
+        _ => None,
+    }
+}
+
+/// Max edit distance between `--region` argument and a region name, to treat it as a typo
+const REGION_MAX_DISTANCE: usize = 3;
+/// Suffixes that may be omitted from region names, lowercase
+const REGION_NAME_SUFFIXES: [&str; 2] = [" область", " oblast"];
+
+/// Location by `--region` argument: uid (e.g. "31") or name, either Ukrainian or English.
+/// Inexact name falls back to the closest one by Levenshtein distance if it is unique.
+/// Unknown region error lists all valid names, ambiguous one lists the closest candidates
+pub fn resolve_region(geo_client: &dyn AlertsInUaGeo, region: &str) -> Result<Location> {
+    let region = region.trim();
+    let locations = geo_client.locations();
+    let location = match region.parse::<i32>() {
+        Ok(uid) => geo_client.get_location_by_uid(uid),
+        Err(_) => geo_client.get_location_by_name(region),
+    };
+    if let Some(location) = location {
+        return Ok(location);
+    }
+    let query = region.to_lowercase();
+    if let Some(location) = locations
+        .iter()
+        .find(|l| l.name.to_lowercase() == query || l.name_en.to_lowercase() == query)
+    {
+        return Ok(location.clone());
+    }
+    let distances: Vec<(usize, &Location)> = locations
+        .iter()
+        .map(|l| (region_distance(l, &query), l))
+        .collect();
+    let best = distances
+        .iter()
+        .map(|(d, _)| *d)
+        .min()
+        .unwrap_or(usize::MAX);
+    let candidates: Vec<&Location> = distances
+        .iter()
+        .filter(|(d, _)| *d == best && best <= REGION_MAX_DISTANCE)
+        .map(|(_, l)| *l)
+        .collect();
+    let describe = |locations: &[&Location]| {
+        locations
+            .iter()
+            .map(|l| format!("{} ({})", l.name, l.location_uid))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    match candidates.as_slice() {
+        [location] => Ok((*location).clone()),
+        [] => Err(AppError::UnknownRegion(
+            region.to_string(),
+            describe(&locations.iter().collect::<Vec<_>>()),
+        )),
+        _ => Err(AppError::AmbiguousRegion(
+            region.to_string(),
+            describe(&candidates),
+        )),
+    }
+}
+
+/// Smallest edit distance from lowercase `query` to any name of `location`,
+/// names are also compared without "область"/"Oblast" suffix
+fn region_distance(location: &Location, query: &str) -> usize {
+    [&location.name, &location.name_en]
+        .iter()
+        .flat_map(|name| {
+            let name = name.to_lowercase();
+            let short = REGION_NAME_SUFFIXES
+                .iter()
+                .find_map(|suffix| name.strip_suffix(suffix))
+                .map(str::to_string);
+            std::iter::once(name).chain(short)
+        })
+        .map(|name| strsim::levenshtein(&name, query))
+        .min()
+        .unwrap_or(usize::MAX)
+}
+
+/// Status line of `location` followed by one line per its active alert
+pub fn render_region(location: &Location, alerts: &Alerts, locale: &str) -> String {
+    let name = if locale == "uk" {
+        &location.name
+    } else {
+        &location.name_en
+    };
+    let alerts = alerts.get_alerts_by_oblast_uid(location.location_uid);
+    let status = AlertStatus::for_alerts(&alerts);
+    let mut lines = vec![format!("{}: {}", name, status)];
+    lines.extend(alerts.iter().filter(|a| a.is_active()).map(|a| {
+        let started_at = a
+            .started_at
+            .format(format_description!("[hour]:[minute] UTC"))
+            .unwrap_or_default();
+        let icon = a.alert_type.get_str("icon").unwrap_or("!");
+        format!(
+            "  {} {}, {} ({})",
+            icon,
+            alert_type_label(&a.alert_type, locale),
+            a.location_title,
+            started_at
+        )
+    }));
+    lines.join("\n")
+}
+
+/// Print status of a single region every `interval` until Ctrl+C, repeating output
+/// only when it changed. Fetch errors are printed and polling goes on
+pub async fn watch_region<W: Write>(
+    api_client: &dyn AlertsInUaApi,
+    location: &Location,
+    locale: &str,
+    interval: Duration,
+    out: &mut W,
+) -> Result<()> {
+    let mut last_rendered = String::new();
+    loop {
+        let rendered = match api_client.get_active_alerts().await {
+            Ok(alerts) => render_region(location, &alerts, locale),
+            Err(e) => format!("FAILED: {}", e),
+        };
+        if rendered != last_rendered {
+            writeln!(out, "{}", rendered)?;
+            out.flush()?;
+            last_rendered = rendered;
+        }
+        tokio::select! {
+            _ = tokio::time::sleep(interval) => {}
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        }
+    }
+}
+
+fn region_name<'a>(status: &'a AirRaidAlertOblastStatus, locale: &str) -> &'a str {
+    if locale == "uk" {
+        status.location_title()
+    } else {
+        status.location_title_en()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::demo::DemoApi;
+    use async_trait::async_trait;
+    use mockito::Server as MockServer;
+    use ralertsinua_http::AlertsInUaClient;
+    use ralertsinua_models::Alerts;
+
+    /// Statuses from given raw data (server error if `None`), the rest is served by [`DemoApi`]
+    #[derive(Debug)]
+    struct MockApi(Option<&'static str>);
+
+    #[async_trait]
+    impl AlertsInUaApi for MockApi {
+        async fn get_active_alerts(&self) -> miette::Result<Alerts, ApiError> {
+            DemoApi.get_active_alerts().await
+        }
+
+        async fn get_alerts_history(
+            &self,
+            location_aid: &i8,
+            period: &str,
+        ) -> miette::Result<Alerts, ApiError> {
+            DemoApi.get_alerts_history(location_aid, period).await
+        }
+
+        async fn get_air_raid_alert_status(
+            &self,
+            location_aid: &i8,
+        ) -> miette::Result<String, ApiError> {
+            DemoApi.get_air_raid_alert_status(location_aid).await
+        }
+
+        async fn get_air_raid_alert_statuses_by_location(
+            &self,
+        ) -> miette::Result<AirRaidAlertOblastStatuses, ApiError> {
+            match self.0 {
+                Some(data) => Ok(AirRaidAlertOblastStatuses::new(
+                    data.to_string(),
+                    Some(false),
+                )),
+                None => Err(ApiError::InternalServerError),
+            }
+        }
+    }
+
+    const TABLE_SNAPSHOT: &str = "
+Region                         Status
+-----------------------------  ------
+Autonomous Republic of Crimea  Active
+Vinnytsia Oblast               No info
+Volyn Oblast                   No info
+Dnipropetrovsk Oblast          Active
+Donetsk Oblast                 Active
+Zhytomyr Oblast                No info
+Zakarpattia Oblast             No info
+Zaporizhia Oblast              Active
+Ivano-Frankivsk Oblast         No info
+Kyiv                           No info
+Kyiv Oblast                    No info
+Kirovohrad Oblast              Partial
+Luhansk Oblast                 Active
+Lviv Oblast                    No info
+Mykolaiv Oblast                Active
+Odesa Oblast                   No info
+Poltava Oblast                 Active
+Rivne Oblast                   No info
+Sevastopol                     No info
+Sumy Oblast                    No info
+Ternopil Oblast                No info
+Kharkiv Oblast                 Active
+Kherson Oblast                 Active
+Khmelnytskyi Oblast            No info
+Cherkasy Oblast                No info
+Chernivtsi Oblast              No info
+Chernihiv Oblast               No info";
+
+    #[tokio::test]
+    async fn test_fetch_and_print_json() -> Result<()> {
+        let api = MockApi(Some("ANNAANNANNNPANANANNNNAANNNN"));
+        let mut out = Vec::new();
+
+        fetch_and_print(&api, OutputFormat::Json, "en", false, &mut out).await?;
+
+        let json: serde_json::Value = serde_json::from_slice(&out)?;
+        let regions = json.as_array().unwrap();
+        assert_eq!(regions.len(), 27);
+        assert_eq!(regions[0]["status"], "A");
+        assert!(regions[0]["region"].is_string());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_fetch_and_print_error() {
+        let api = MockApi(None);
+        let mut out = Vec::new();
+
+        let result = fetch_and_print(&api, OutputFormat::Json, "en", false, &mut out).await;
+
+        assert!(result.is_err());
+        assert!(out.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_check_connectivity() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+        server
+            .mock("GET", "/v1/iot/active_air_raid_alerts_by_oblast.json")
+            .match_header("Authorization", "Bearer valid")
+            .with_header("Last-Modified", "Tue, 14 May 2024 18:18:18 GMT")
+            .with_body(r#""NNNNNNNNNNNNNNNNNNNNNNNNNNN""#)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/v1/iot/active_air_raid_alerts_by_oblast.json")
+            .match_header("Authorization", "Bearer invalid")
+            .with_status(401)
+            .create_async()
+            .await;
+
+        let mut out = Vec::new();
+        let api = AlertsInUaClient::new(&server.url(), "valid").unwrap();
+        assert!(check_connectivity(&api, &mut out).await?);
+        assert!(String::from_utf8_lossy(&out).starts_with("OK: API is reachable"));
+
+        let mut out = Vec::new();
+        let api = AlertsInUaClient::new(&server.url(), "invalid").unwrap();
+        assert!(!check_connectivity(&api, &mut out).await?);
+        assert!(String::from_utf8_lossy(&out).starts_with("FAILED: invalid token"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve_region() -> Result<()> {
+        let geo = ralertsinua_geo::AlertsInUaGeoClient::default();
+
+        assert_eq!(resolve_region(&geo, "31")?.name, "Київ");
+        assert_eq!(resolve_region(&geo, "Луганська область")?.location_uid, 16);
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve_region_fuzzy() -> Result<()> {
+        let geo = ralertsinua_geo::AlertsInUaGeoClient::default();
+
+        assert_eq!(resolve_region(&geo, "kyiv oblast")?.location_uid, 14);
+        assert_eq!(resolve_region(&geo, "Kherkiv Oblast")?.location_uid, 22);
+        assert_eq!(resolve_region(&geo, "Odessa")?.location_uid, 18);
+        assert_eq!(resolve_region(&geo, "Lviv")?.location_uid, 27);
+        // Latin "i" instead of Cyrillic "і"
+        assert_eq!(resolve_region(&geo, "Харкiвська")?.location_uid, 22);
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve_region_ambiguous() {
+        let geo = ralertsinua_geo::AlertsInUaGeoClient::default();
+
+        // as close to "Kyiv" as to "Kyiv Oblast" without suffix
+        match resolve_region(&geo, "Kyi") {
+            Err(AppError::AmbiguousRegion(arg, candidates)) => {
+                assert_eq!(arg, "Kyi");
+                assert_eq!(candidates, "Київ (31), Київська область (14)");
+            }
+            other => panic!("expected ambiguous region, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_resolve_region_unknown() {
+        let geo = ralertsinua_geo::AlertsInUaGeoClient::default();
+
+        for region in ["0", "Atlantis"] {
+            match resolve_region(&geo, region) {
+                Err(AppError::UnknownRegion(arg, names)) => {
+                    assert_eq!(arg, region);
+                    assert!(names.contains("Київ (31)"));
+                }
+                other => panic!("expected unknown region, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_render_region() {
+        let geo = ralertsinua_geo::AlertsInUaGeoClient::default();
+        let alerts: Alerts =
+            serde_json::from_str(crate::demo::DEMO_ALERTS_RESPONSE).unwrap();
+
+        let kharkiv = geo.get_location_by_uid(22).unwrap();
+        assert_eq!(
+            render_region(&kharkiv, &alerts, "en"),
+            "Kharkiv Oblast: Partial\n  ✈ Air raid, Куп'янська територіальна громада (16:02 UTC)"
+        );
+        let lviv = geo.get_location_by_uid(27).unwrap();
+        assert_eq!(
+            render_region(&lviv, &alerts, "uk"),
+            "Львівська область: No info"
+        );
+    }
+
+    #[test]
+    fn test_render_table() {
+        let statuses = AirRaidAlertOblastStatuses::new(
+            "ANNAANNANNNPANANANNNNAANNNN".to_string(),
+            Some(false),
+        );
+
+        let table = render_table(&statuses, "en", false);
+
+        assert_eq!(table, TABLE_SNAPSHOT.trim_start_matches('\n'));
+    }
+
+    #[test]
+    fn test_render_table_color() {
+        let statuses = AirRaidAlertOblastStatuses::new(
+            "ANNAANNANNNPANANANNNNAANNNN".to_string(),
+            Some(false),
+        );
+
+        let table = render_table(&statuses, "en", true);
+
+        assert!(table.contains("\x1b[31mActive\x1b[0m"));
+        assert!(table.contains("\x1b[33mPartial\x1b[0m"));
+    }
+}
@@ -0,0 +1,152 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{File, OpenOptions},
+    io::Write,
+    path::Path,
+};
+use time::OffsetDateTime;
+use tokio::{
+    sync::mpsc::UnboundedSender,
+    task::JoinHandle,
+    time::{sleep, Duration},
+};
+use tokio_util::sync::CancellationToken;
+
+use crate::{action::Action, error::*};
+
+type Result<T> = miette::Result<T, AppError>;
+
+/// Longest pause between replayed actions, idle periods of a recording are shortened to it
+pub const REPLAY_MAX_DELAY: Duration = Duration::from_secs(2);
+
+/// Dispatched action with the time it was dispatched, one line of a recording
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedAction {
+    #[serde(with = "time::serde::rfc3339")]
+    pub at: OffsetDateTime,
+    pub action: Action,
+}
+
+/// Appends dispatched actions to a JSONL file, see `--record`
+#[derive(Debug)]
+pub struct Recorder {
+    file: File,
+}
+
+impl Recorder {
+    /// Open recording at `path` for appending, creating parent directories and file if missing
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self { file })
+    }
+
+    /// Append `action` with current time, `Tick` and `Render` are skipped to keep recording small
+    pub fn record(&mut self, action: &Action) -> Result<()> {
+        if matches!(action, Action::Tick | Action::Render) {
+            return Ok(());
+        }
+        let recorded = RecordedAction {
+            at: OffsetDateTime::now_utc(),
+            action: action.clone(),
+        };
+        writeln!(self.file, "{}", serde_json::to_string(&recorded)?)?;
+        Ok(())
+    }
+}
+
+/// Recorded actions from JSONL file at `path` in recording order, blank lines are skipped
+pub fn load_recording(path: &Path) -> Result<Vec<RecordedAction>> {
+    let recording = std::fs::read_to_string(path)?
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(serde_json::from_str)
+        .collect::<serde_json::Result<_>>()?;
+    Ok(recording)
+}
+
+/// Spawn a task sending `recording` actions, keeping recorded pauses between them
+/// (at most [`REPLAY_MAX_DELAY`]), until done or `cancellation_token` is cancelled
+pub fn spawn_replay(
+    recording: Vec<RecordedAction>,
+    action_tx: UnboundedSender<Action>,
+    cancellation_token: CancellationToken,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut previous_at = recording.first().map(|r| r.at);
+        for RecordedAction { at, action } in recording {
+            let delay = previous_at
+                .map(|previous_at| (at - previous_at).unsigned_abs())
+                .unwrap_or_default()
+                .min(REPLAY_MAX_DELAY);
+            previous_at = Some(at);
+            tokio::select! {
+                _ = cancellation_token.cancelled() => break,
+                _ = sleep(delay) => {
+                    if action_tx.send(action).is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_record_and_load() -> Result<()> {
+        let path = std::env::temp_dir().join("ralertsinua_test_recording.jsonl");
+        let _ = std::fs::remove_file(&path);
+        let mut recorder = Recorder::open(&path)?;
+
+        for action in [
+            Action::Tick,
+            Action::SelectIndex(3),
+            Action::Render,
+            Action::Error("oops".to_string()),
+        ] {
+            recorder.record(&action)?;
+        }
+
+        let recording = load_recording(&path)?;
+        let actions: Vec<Action> = recording.into_iter().map(|r| r.action).collect();
+        assert_eq!(
+            actions,
+            vec![Action::SelectIndex(3), Action::Error("oops".to_string())]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_spawn_replay() {
+        let (action_tx, mut action_rx) = tokio::sync::mpsc::unbounded_channel();
+        let now = OffsetDateTime::now_utc();
+        let recording = vec![
+            RecordedAction {
+                at: now,
+                action: Action::SelectTop,
+            },
+            RecordedAction {
+                // long idle period is not waited out
+                at: now + time::Duration::hours(1),
+                action: Action::Quit,
+            },
+        ];
+
+        tokio::time::timeout(
+            REPLAY_MAX_DELAY * 2,
+            spawn_replay(recording, action_tx, CancellationToken::new()),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(action_rx.recv().await, Some(Action::SelectTop));
+        assert_eq!(action_rx.recv().await, Some(Action::Quit));
+    }
+}
@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+use crate::{error::*, utils::get_data_dir};
+
+type Result<T> = miette::Result<T, AppError>;
+
+pub const STATE_FILE: &str = "state.json";
+
+/// UI state persisted across restarts
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct State {
+    /// `location_uid` of the selected location
+    pub selected: Option<usize>,
+}
+
+impl State {
+    /// Default state file path in data directory
+    pub fn path() -> PathBuf {
+        get_data_dir().join(STATE_FILE)
+    }
+
+    /// Load state from file, missing or corrupt file results in default state
+    pub fn load(path: &Path) -> Self {
+        let Ok(contents) = std::fs::read_to_string(path) else {
+            return Self::default();
+        };
+        serde_json::from_str(&contents)
+            .inspect_err(|e| {
+                warn!(target: "app", "failed to parse state file {:?}: {}", path, e);
+            })
+            .unwrap_or_default()
+    }
+
+    /// Save state to file, creating parent directories if needed
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_round_trip() -> Result<()> {
+        let path = std::env::temp_dir().join("ralertsinua_test_state.json");
+        let state = State { selected: Some(31) };
+
+        state.save(&path)?;
+        assert_eq!(State::load(&path), state);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_state_load_missing_or_corrupt() -> Result<()> {
+        let path = std::env::temp_dir().join("ralertsinua_test_state_missing.json");
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(State::load(&path), State::default());
+
+        std::fs::write(&path, "{ not json")?;
+        assert_eq!(State::load(&path), State::default());
+
+        Ok(())
+    }
+}
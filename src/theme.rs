@@ -0,0 +1,131 @@
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use tracing::warn;
+
+use crate::constants::*;
+
+/// Colors from `[theme]` table of config file, as hex (`#ff0000`), named (`red`) or
+/// indexed (`9`) colors
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeSettings {
+    pub default: Option<String>,
+    pub text: Option<String>,
+    pub marker: Option<String>,
+    pub center: Option<String>,
+    pub alert: Option<String>,
+    pub selected: Option<String>,
+}
+
+impl ThemeSettings {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// UI colors, defaults to [`crate::constants`] colors
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    pub default: Color,
+    pub text: Color,
+    pub marker: Color,
+    pub center: Color,
+    pub alert: Color,
+    pub selected: Color,
+    /// Distinguish statuses by text markers only, see [`Theme::no_color`]
+    pub no_color: bool,
+}
+
+impl Theme {
+    /// Monochrome theme, for colorblind users and terminals without colors
+    pub fn no_color() -> Self {
+        Self {
+            default: Color::Reset,
+            text: Color::Reset,
+            marker: Color::Reset,
+            center: Color::Reset,
+            alert: Color::Reset,
+            selected: Color::Reset,
+            no_color: true,
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            default: *DEFAULT_COLOR,
+            text: *TEXT_COLOR,
+            marker: *MARKER_COLOR,
+            center: *CENTER_COLOR,
+            alert: *ALERT_ROW_COLOR,
+            selected: *SELECTED_STYLE_FG,
+            no_color: false,
+        }
+    }
+}
+
+impl From<&ThemeSettings> for Theme {
+    fn from(settings: &ThemeSettings) -> Self {
+        let default = Self::default();
+        Self {
+            default: parse_color(settings.default.as_deref(), default.default),
+            text: parse_color(settings.text.as_deref(), default.text),
+            marker: parse_color(settings.marker.as_deref(), default.marker),
+            center: parse_color(settings.center.as_deref(), default.center),
+            alert: parse_color(settings.alert.as_deref(), default.alert),
+            selected: parse_color(settings.selected.as_deref(), default.selected),
+            no_color: false,
+        }
+    }
+}
+
+/// Parse color, falling back to `default` if missing or invalid
+pub fn parse_color(value: Option<&str>, default: Color) -> Color {
+    let Some(value) = value else {
+        return default;
+    };
+    Color::from_str(value).unwrap_or_else(|_| {
+        warn!("Invalid theme color '{}', using default {}", value, default);
+        default
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_color_formats() {
+        assert_eq!(
+            parse_color(Some("#ff0000"), Color::Reset),
+            Color::Rgb(255, 0, 0)
+        );
+        assert_eq!(parse_color(Some("red"), Color::Reset), Color::Red);
+        assert_eq!(
+            parse_color(Some("light-blue"), Color::Reset),
+            Color::LightBlue
+        );
+        assert_eq!(parse_color(Some("9"), Color::Reset), Color::Indexed(9));
+    }
+
+    #[test]
+    fn test_parse_color_fallback() {
+        assert_eq!(parse_color(None, Color::Blue), Color::Blue);
+        assert_eq!(parse_color(Some("#ff00"), Color::Blue), Color::Blue);
+        assert_eq!(parse_color(Some("not-a-color"), Color::Blue), Color::Blue);
+    }
+
+    #[test]
+    fn test_theme_from_settings() {
+        let settings: ThemeSettings =
+            toml::from_str("alert = \"#800000\"\nmarker = \"invalid\"").unwrap();
+
+        let theme = Theme::from(&settings);
+
+        assert_eq!(theme.alert, Color::Rgb(128, 0, 0));
+        assert_eq!(theme.marker, *MARKER_COLOR);
+        assert_eq!(theme.selected, *SELECTED_STYLE_FG);
+    }
+}
@@ -5,12 +5,13 @@ use crossterm::{
         DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste,
         EnableMouseCapture, Event as CrosstermEvent, KeyEvent, KeyEventKind, MouseEvent,
     },
-    terminal::{EnterAlternateScreen, LeaveAlternateScreen},
+    terminal::{EnterAlternateScreen, LeaveAlternateScreen, SetTitle},
 };
 use futures::{FutureExt, StreamExt};
 use ratatui::backend::CrosstermBackend as Backend;
 use serde::{Deserialize, Serialize};
 use std::{
+    io::Write,
     ops::{Deref, DerefMut},
     time::Duration,
 };
@@ -30,6 +31,10 @@ pub fn io() -> IO {
 }
 pub type Frame<'a> = ratatui::Frame<'a>;
 
+/// XTWINOPS sequences to save original window title on terminal's stack and restore it
+const PUSH_TITLE: &str = "\x1b[22;0t";
+const POP_TITLE: &str = "\x1b[23;0t";
+
 type Result<T> = miette::Result<T, AppError>;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -59,6 +64,8 @@ pub struct Tui {
     pub tick_rate: f64,
     pub mouse: bool,
     pub paste: bool,
+    /// Window title was changed and original one is to be restored on exit
+    title_changed: bool,
 }
 
 impl Tui {
@@ -81,6 +88,7 @@ impl Tui {
             tick_rate,
             mouse,
             paste,
+            title_changed: false,
         })
     }
 
@@ -210,6 +218,23 @@ impl Tui {
             crossterm::execute!(io(), LeaveAlternateScreen, cursor::Show)?;
             crossterm::terminal::disable_raw_mode()?;
         }
+        if self.title_changed {
+            let mut io = io();
+            io.write_all(POP_TITLE.as_bytes())?;
+            io.flush()?;
+            self.title_changed = false;
+        }
+        Ok(())
+    }
+
+    /// Set window title with OSC sequence, original title is saved first and restored on exit
+    pub fn set_title(&mut self, title: &str) -> Result<()> {
+        let mut io = io();
+        if !self.title_changed {
+            io.write_all(PUSH_TITLE.as_bytes())?;
+            self.title_changed = true;
+        }
+        crossterm::execute!(io, SetTitle(title))?;
         Ok(())
     }
 
@@ -8,7 +8,7 @@ use std::collections::HashMap;
 use std::{rc::Rc, str::FromStr};
 use strum::EnumProperty;
 #[allow(unused_imports)]
-use tracing::{debug, info};
+use tracing::{debug, info, trace};
 
 use crate::layout::*;
 
@@ -48,6 +48,46 @@ where
     line
 }
 
+/// Position of terminal cell relative to inner area of bordered `area`, if inside of it
+pub fn get_inner_position(area: Rect, column: u16, row: u16) -> Option<(u16, u16)> {
+    let inner = area.inner(&Margin::new(1, 1));
+    let is_inside = column >= inner.left()
+        && column < inner.right()
+        && row >= inner.top()
+        && row < inner.bottom();
+    is_inside.then(|| (column - inner.left(), row - inner.top()))
+}
+
+/// Text marker distinguishing status without colors, for no-color mode
+pub fn get_marker_by_status(status: &AlertStatus) -> &'static str {
+    match status {
+        AlertStatus::A => "[!]",
+        AlertStatus::P => "[~]",
+        AlertStatus::L => "[.]",
+        AlertStatus::O => "[?]",
+        AlertStatus::N => "[ ]",
+    }
+}
+
+/// Builds new [`Line`] with status text marker instead of icon and color
+pub fn get_plain_line_by_status<'a, S>(
+    text: S,
+    status: &AlertStatus,
+    is_selected: &bool,
+) -> Line<'a>
+where
+    S: Into<String>,
+{
+    let marker = get_marker_by_status(status);
+    let line: Line = Line::from(format!("{} {}", marker, text.into()));
+
+    if *is_selected || *status == AlertStatus::A {
+        line.add_modifier(Modifier::BOLD)
+    } else {
+        line
+    }
+}
+
 /// Builds new [`Line`] with styled text
 pub fn get_styled_line_icon_by_status<'a>(
     status: &AlertStatus,
@@ -126,32 +166,60 @@ pub fn get_bottom_left_rect(r: Rect, p_x: u16, p_y: u16) -> Rect {
     area
 }
 
-#[memoized(key_expr = (frame_size, cmp_name, cmp_area), store_type = HashMap<(Rect, &'static str, LayoutArea), Rect>)]
-pub fn get_component_area(
+/// Rects of all layout areas in `frame_size`, split by `settings`
+#[memoized(key_expr = (frame_size, settings), store_type = HashMap<(Rect, LayoutSettings), HashMap<LayoutArea, Rect>>)]
+pub fn get_layout_areas(
     frame_size: Rect,
-    cmp_name: &'static str,
-    cmp_area: LayoutArea,
-) -> Rect {
-    let vertical = Layout::vertical([Length(1), Min(0), Length(1)]);
-    let [header_area, inner_area, footer_area] = vertical.areas(frame_size);
+    settings: LayoutSettings,
+) -> HashMap<LayoutArea, Rect> {
+    let vertical = Layout::vertical([
+        Length(1),
+        Min(0),
+        Length(settings.bottom_height),
+        Length(1),
+        Length(1),
+    ]);
+    let [header_area, inner_area, bottom_area, legend_area, footer_area] =
+        vertical.areas(frame_size);
 
     let horizontal = Layout::horizontal([Min(0), Length(20)]);
     let [tabs_area, title_area] = horizontal.areas(header_area);
 
-    let main = Layout::horizontal([Percentage(75), Percentage(25)]);
+    let main = Layout::horizontal([
+        Percentage(settings.left_percent),
+        Percentage(100 - settings.left_percent),
+    ]);
     let [left_area, right_area] = main.areas(inner_area);
 
-    let area = match &cmp_area {
-        LayoutArea::Header => header_area,
-        LayoutArea::Tabs => tabs_area,
-        LayoutArea::Title => title_area,
-        LayoutArea::Inner => inner_area,
-        LayoutArea::Left => left_area,
-        LayoutArea::Right => right_area,
-        LayoutArea::Footer => footer_area,
-        LayoutArea::Hidden => Rect::default(),
-    };
-    debug!(target:"app", "calculated area for component '{}' - {:?}", cmp_name, area);
+    // details are moved to bottom strip if it is shown
+    let details_height = if settings.bottom_height > 0 { 0 } else { 8 };
+    let right = Layout::vertical([Min(0), Length(3), Length(details_height)]);
+    let [right_top_area, right_middle_area, right_bottom_area] = right.areas(right_area);
+
+    HashMap::from([
+        (LayoutArea::Header, header_area),
+        (LayoutArea::Tabs, tabs_area),
+        (LayoutArea::Title, title_area),
+        (LayoutArea::Inner, inner_area),
+        (LayoutArea::Left, left_area),
+        (LayoutArea::Right, right_top_area),
+        (LayoutArea::RightMiddle, right_middle_area),
+        (LayoutArea::RightBottom, right_bottom_area),
+        (LayoutArea::Bottom, bottom_area),
+        (LayoutArea::Legend, legend_area),
+        (LayoutArea::Footer, footer_area),
+        (LayoutArea::Hidden, Rect::default()),
+    ])
+}
+
+pub fn get_component_area(
+    frame_size: Rect,
+    cmp_name: &'static str,
+    cmp_area: LayoutArea,
+    settings: LayoutSettings,
+) -> Rect {
+    let area = get_layout_areas(frame_size, settings)[&cmp_area];
+    trace!(target:"app", "calculated area for component '{}' - {:?}", cmp_name, area);
 
     area
 }
@@ -184,17 +252,45 @@ mod tests {
             LayoutArea::Inner,
             LayoutArea::Left,
             LayoutArea::Right,
+            LayoutArea::RightMiddle,
+            LayoutArea::RightBottom,
+            LayoutArea::Legend,
             LayoutArea::Footer,
         ];
 
         for &area in &areas {
-            let result = get_component_area(frame_size, cmp_name, area);
+            let result =
+                get_component_area(frame_size, cmp_name, area, LayoutSettings::default());
 
-            // Check that the result is a valid Rect
-            assert!(result.x >= 0);
-            assert!(result.y >= 0);
+            // Check that the result is a non-empty Rect
             assert!(result.width > 0);
             assert!(result.height > 0);
         }
     }
+
+    #[test]
+    fn test_get_layout_areas() {
+        let frame_size = Rect::new(0, 0, 100, 30);
+
+        let areas = get_layout_areas(frame_size, LayoutSettings::default());
+        assert_eq!(areas[&LayoutArea::Left], Rect::new(0, 1, 75, 27));
+        assert_eq!(areas[&LayoutArea::Right], Rect::new(75, 1, 25, 16));
+        assert_eq!(areas[&LayoutArea::RightMiddle], Rect::new(75, 17, 25, 3));
+        assert_eq!(areas[&LayoutArea::RightBottom], Rect::new(75, 20, 25, 8));
+        assert_eq!(areas[&LayoutArea::Bottom].height, 0);
+        assert_eq!(areas[&LayoutArea::Legend], Rect::new(0, 28, 100, 1));
+        assert_eq!(areas[&LayoutArea::Footer], Rect::new(0, 29, 100, 1));
+
+        let settings = LayoutSettings {
+            left_percent: 60,
+            bottom_height: 6,
+        };
+        let areas = get_layout_areas(frame_size, settings);
+        assert_eq!(areas[&LayoutArea::Left], Rect::new(0, 1, 60, 21));
+        assert_eq!(areas[&LayoutArea::Right], Rect::new(60, 1, 40, 18));
+        assert_eq!(areas[&LayoutArea::RightMiddle], Rect::new(60, 19, 40, 3));
+        assert_eq!(areas[&LayoutArea::RightBottom].height, 0);
+        assert_eq!(areas[&LayoutArea::Bottom], Rect::new(0, 22, 100, 6));
+        assert_eq!(settings.details_area(), LayoutArea::Bottom);
+    }
 }
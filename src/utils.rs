@@ -1,10 +1,13 @@
 use directories::ProjectDirs;
 use lazy_static::lazy_static;
-use std::path::PathBuf;
+use ralertsinua_models::AlertType;
+use rust_i18n::t;
+use std::path::{Path, PathBuf};
 use std::{any::type_name, env};
+use tracing_appender::rolling::RollingFileAppender;
 use tracing_error::ErrorLayer;
 use tracing_subscriber::{
-    self, prelude::__tracing_subscriber_SubscriberExt, util::SubscriberInitExt,
+    self, prelude::__tracing_subscriber_SubscriberExt, util::SubscriberInitExt, EnvFilter,
 };
 
 use crate::error::*;
@@ -91,15 +94,65 @@ pub fn get_config_dir() -> PathBuf {
     directory
 }
 
-pub fn initialize_logging(log_path: Option<impl Into<PathBuf>>) -> Result<()> {
+/// Verbosity of logs, passed as `--log-level`, `ALERTSINUA_LOG_LEVEL` or `log_level` in config file
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    clap::ValueEnum,
+    strum_macros::Display,
+    serde::Deserialize,
+    serde::Serialize,
+)]
+#[strum(serialize_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    #[default]
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    /// `EnvFilter` directive enabling this level for all targets
+    pub fn filter_directive(&self) -> String {
+        self.to_string()
+    }
+}
+
+/// Logs filter from `level` if given, otherwise from `RUST_LOG` env, defaults to `info`
+pub fn log_filter(level: Option<LogLevel>) -> EnvFilter {
+    match level {
+        Some(level) => EnvFilter::new(level.filter_directive()),
+        None => EnvFilter::try_from_default_env()
+            .unwrap_or_else(|_| EnvFilter::new(LogLevel::default().filter_directive())),
+    }
+}
+
+/// Daily rolling file appender, e.g. `ralertsinua.log.2024-05-05` for `log_path`
+/// `ralertsinua.log`, relative paths are placed in data directory
+pub fn rolling_file_appender(log_path: &Path) -> RollingFileAppender {
+    let log_path = get_data_dir().join(log_path);
+    let directory = log_path.parent().unwrap_or(Path::new("."));
+    let prefix = log_path.file_name().unwrap_or(LOG_FILE.as_ref());
+    tracing_appender::rolling::daily(directory, prefix)
+}
+
+pub fn initialize_logging(
+    log_path: Option<impl Into<PathBuf>>,
+    filter: EnvFilter,
+) -> Result<()> {
     let disable_file_logging: bool = log_path.is_none();
 
-    if log_path.is_some() {
-        let log_path: PathBuf = log_path.unwrap().into();
-        // let directory = get_data_dir();
-        // std::fs::create_dir_all(directory.clone())?;
-        // let log_path: PathBuf = directory.join(LOG_FILE.clone());
-        let log_file = std::fs::File::create(log_path)?;
+    if let Some(log_path) = log_path {
+        let log_path: PathBuf = log_path.into();
+        // file only, stdout belongs to TUI
+        let log_file = rolling_file_appender(&log_path);
 
         let file_logger = tracing_subscriber::fmt::layer()
             .with_file(true)
@@ -109,12 +162,14 @@ pub fn initialize_logging(log_path: Option<impl Into<PathBuf>>) -> Result<()> {
             .with_ansi(false);
 
         tracing_subscriber::registry()
+            .with(filter)
             .with(file_logger)
             .with(ErrorLayer::default())
             .with(tui_logger::tracing_subscriber_layer())
             .init();
     } else {
         tracing_subscriber::registry()
+            .with(filter)
             .with(ErrorLayer::default())
             .with(tui_logger::tracing_subscriber_layer())
             .init();
@@ -171,6 +226,17 @@ pub fn type_of<T>(_: T) -> &'static str {
     type_name::<T>().split("::").last().unwrap()
 }
 
+/// Translated name of `alert_type` in `locale`, raw API value for unknown types
+pub fn alert_type_label(alert_type: &AlertType, locale: &str) -> String {
+    match alert_type {
+        AlertType::Unknown(raw) => raw.clone(),
+        known => {
+            let key = format!("alert_types.{}", known);
+            t!(&key, locale = locale).to_string()
+        }
+    }
+}
+
 #[inline]
 pub fn str_to_bool(s: impl Into<String>) -> bool {
     match s.into().to_lowercase().as_str() {
@@ -188,12 +254,32 @@ mod tests {
     use std::fs::File;
     use std::io::Read;
 
+    /// Contents of daily log files written for `log_path`
+    fn read_rolled_logs(log_path: &Path) -> Result<String> {
+        let prefix = log_path.file_name().unwrap().to_string_lossy().to_string();
+        let mut contents = String::new();
+        for entry in std::fs::read_dir(log_path.parent().unwrap())? {
+            let path = entry?.path();
+            if path
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .starts_with(&prefix)
+            {
+                File::open(path)?.read_to_string(&mut contents)?;
+            }
+        }
+        Ok(contents)
+    }
+
     #[test]
     fn test_initialize_logging_with_path() -> Result<()> {
-        let log_path = std::env::temp_dir().join("tmp.log");
-        File::create(&log_path).map_err(|err| AppError::Io(err))?;
+        let log_dir = std::env::temp_dir().join("ralertsinua_test_logging");
+        let _ = std::fs::remove_dir_all(&log_dir);
+        let log_path = log_dir.join("tmp.log");
 
-        let result = initialize_logging(Some(log_path.clone()));
+        let result =
+            initialize_logging(Some(log_path.clone()), log_filter(Some(LogLevel::Info)));
         assert!(result.is_ok());
 
         // Set the log level to Info
@@ -203,16 +289,49 @@ mod tests {
         let log_line = "This is a test log line.";
         info!("{}", log_line);
 
-        // Read the log file
-        let mut log_file = File::open(&log_path).map_err(|err| AppError::Io(err))?;
-        let mut log_contents = String::new();
-        log_file
-            .read_to_string(&mut log_contents)
-            .map_err(|err| AppError::Io(err))?;
+        // Check that the rolled log file contains the log line
+        assert!(read_rolled_logs(&log_path)?.contains(log_line));
 
-        // Check that the log file contains the log line
-        assert!(log_contents.contains(log_line));
+        Ok(())
+    }
 
+    #[test]
+    fn test_rolling_file_appender() -> Result<()> {
+        let log_dir = std::env::temp_dir().join("ralertsinua_test_rolling");
+        let _ = std::fs::remove_dir_all(&log_dir);
+        let log_path = log_dir.join("app.log");
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(rolling_file_appender(&log_path))
+            .with_ansi(false)
+            .finish();
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::warn!("rolled log line");
+        });
+
+        let contents = read_rolled_logs(&log_path)?;
+        assert!(contents.contains("rolled log line"));
+        // daily file is named after the date, e.g. "app.log.2024-05-05"
+        assert!(!log_path.exists());
         Ok(())
     }
+
+    #[test]
+    fn test_log_filter() {
+        assert_eq!(LogLevel::default(), LogLevel::Info);
+        assert_eq!(LogLevel::Warn.filter_directive(), "warn");
+        assert_eq!(log_filter(Some(LogLevel::Debug)).to_string(), "debug");
+        assert_eq!(log_filter(Some(LogLevel::Error)).to_string(), "error");
+    }
+
+    #[test]
+    fn test_alert_type_label() {
+        assert_eq!(alert_type_label(&AlertType::AirRaid, "en"), "Air raid");
+        assert_eq!(
+            alert_type_label(&AlertType::AirRaid, "uk"),
+            "Повітряна тривога"
+        );
+        let unknown = AlertType::Unknown("drones".to_string());
+        assert_eq!(alert_type_label(&unknown, "en"), "drones");
+    }
 }